@@ -0,0 +1,166 @@
+//! Build script detecting duplicate service method names at compile time.
+//!
+//! Every `#[jsonrpc_service_fn...(method = "...")]` attribute is collected per crate
+//! (the library sources, and each test and example file on its own). A method name
+//! registered twice in one crate fails the build, complementing the marker constant
+//! the service macros emit (which only catches duplicates within one module) and
+//! the runtime panic raised when the routing table is built. The library's method
+//! names are exported to the compiler as `REGISTERED_METHODS`, a comma-separated list.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    for dir in ["src", "tests", "examples"] {
+        println!("cargo:rerun-if-changed={}", dir);
+    }
+
+    let mut lib_files = Vec::new();
+    collect_rs_files(Path::new("src"), &mut lib_files);
+    let methods = registered_methods(&lib_files);
+    println!("cargo:rustc-env=REGISTERED_METHODS={}", methods.join(","));
+
+    for dir in ["tests", "examples"] {
+        let Ok(entries) = fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                registered_methods(&[path]);
+            }
+        }
+    }
+}
+
+/// Recursively collects the `.rs` files below `dir`.
+fn collect_rs_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_rs_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+/// Returns the method names registered in the files of one crate, panicking with
+/// the locations of every name that is registered more than once.
+fn registered_methods(files: &[PathBuf]) -> Vec<String> {
+    let mut seen: HashMap<String, Vec<String>> = HashMap::new();
+    let mut methods = Vec::new();
+    for file in files {
+        let Ok(source) = fs::read_to_string(file) else {
+            continue;
+        };
+        for (line, method) in scan_service_methods(&source) {
+            let locations = seen.entry(method.clone()).or_default();
+            if locations.is_empty() {
+                methods.push(method);
+            }
+            locations.push(format!("{}:{}", file.display(), line));
+        }
+    }
+    let mut duplicates: Vec<String> = seen
+        .into_iter()
+        .filter(|(_, locations)| locations.len() > 1)
+        .map(|(method, locations)| format!("`{}` at {}", method, locations.join(", ")))
+        .collect();
+    if !duplicates.is_empty() {
+        duplicates.sort();
+        panic!("Duplicate method registered: {}", duplicates.join("; "));
+    }
+    methods.sort();
+    methods
+}
+
+/// Finds the `method` key of every service attribute in `source`, returning it
+/// with its 1-based line number. Comment lines, including doc examples, are skipped.
+fn scan_service_methods(source: &str) -> Vec<(usize, String)> {
+    let code: String = source
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("//") {
+                ""
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut found = Vec::new();
+    let mut rest = code.as_str();
+    while let Some(pos) = rest.find("jsonrpc_service_fn") {
+        let offset = code.len() - rest.len() + pos;
+        let after = &rest[pos + "jsonrpc_service_fn".len()..];
+        rest = after;
+        let args = ["_array(", "_obj(", "("]
+            .iter()
+            .find_map(|suffix| after.strip_prefix(suffix));
+        let Some(args) = args else {
+            continue;
+        };
+        let args = &args[..closing_paren(args)];
+        if let Some(method) = method_value(args) {
+            let line = code[..offset].matches('\n').count() + 1;
+            found.push((line, method));
+        }
+    }
+    found
+}
+
+/// Returns the index of the `)` closing an argument list that starts at `args`.
+fn closing_paren(args: &str) -> usize {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in args.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' => depth += 1,
+            ')' if depth == 0 => return i,
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    args.len()
+}
+
+/// Extracts the string assigned to the `method` key in an attribute argument list.
+fn method_value(args: &str) -> Option<String> {
+    let mut rest = args;
+    while let Some(pos) = rest.find("method") {
+        let preceded_by_ident = rest[..pos]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_');
+        let after = rest[pos + "method".len()..].trim_start();
+        rest = &rest[pos + "method".len()..];
+        if preceded_by_ident {
+            continue;
+        }
+        let Some(value) = after.strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start().strip_prefix('"')?;
+        return value.find('"').map(|end| value[..end].to_string());
+    }
+    None
+}
@@ -1,4 +1,4 @@
-use a_rs_jsonrpc::{RpcError, response::JsonRpcError};
+use a_rs_jsonrpc::{JsonRpcBody, RpcError};
 use tracing::Level;
 
 /// A simple echo RPC service example using a-rs-jsonrpc and receives parameters as an array.
@@ -27,41 +27,11 @@ async fn main() {
         .try_init()
         .ok();
 
-    a_rs_jsonrpc::init_rpc_service();
+    a_rs_jsonrpc::init_rpc_service(a_rs_jsonrpc::ServiceConfig::default());
 
-    let app = axum::Router::new().route(
-        "/",
-        axum::routing::post(|body: axum::body::Bytes| async move {
-            match a_rs_jsonrpc::dispatch_rpc_request(&body).await {
-                Ok(resp_body) => resp_body,
-                Err(err) => response_error(&body, err),
-            }
-        }),
-    );
+    let app = axum::Router::new().route("/", axum::routing::post(JsonRpcBody::handler));
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
     tracing::info!("RPC Server running on http://localhost:3000");
     axum::serve(listener, app).await.unwrap();
 }
-
-fn response_error(req_body: &axum::body::Bytes, err: RpcError) -> String {
-    let Ok((id, version)) = serde_json::from_slice::<serde_json::Value>(req_body).map(|v| {
-        (
-            v.get("id").cloned().unwrap_or(serde_json::Value::Null),
-            v.get("jsonrpc").cloned().unwrap_or(serde_json::Value::Null),
-        )
-    }) else {
-        return serde_json::to_string(&serde_json::json!({
-            "jsonrpc": null,
-            "error": { "code": -32603, "message": err.to_string() },
-            "id": null
-        }))
-        .unwrap_or_default();
-    };
-    serde_json::to_string(&serde_json::json!({
-        "jsonrpc": version,
-        "error": JsonRpcError::from(err),
-        "id": id
-    }))
-    .unwrap_or_default()
-}
@@ -0,0 +1,33 @@
+//! Calls a JSON-RPC server that speaks MessagePack instead of JSON.
+//!
+//! Run with `cargo run --example msgpack_client --features msgpack` against a server
+//! accepting `application/msgpack` requests on `http://localhost:3000/`.
+
+use a_rs_jsonrpc::{JsonRpcClient, JsonRpcResponse, RpcError, rpc_method};
+use tracing::Level;
+
+const URL: &str = "http://localhost:3000/";
+
+#[rpc_method(
+    url = "http://localhost:3000/",
+    method = "addArray",
+    version = "v2",
+    encoding = "msgpack"
+)]
+async fn add(a: i64, b: i64) -> Result<JsonRpcResponse<i64>, RpcError> {}
+
+#[tokio::main]
+async fn main() -> Result<(), RpcError> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::DEBUG)
+        .try_init()
+        .ok();
+
+    let sum = add(10, 20).await?;
+    tracing::info!("addArray via rpc_method: {:?}", sum.result);
+
+    let product: JsonRpcResponse<i64> = (6, 7).send_v2_request_msgpack(URL, "mulArray").await?;
+    tracing::info!("mulArray via JsonRpcClient: {:?}", product.result);
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+//! Serves JSON-RPC over stdin/stdout.
+//!
+//! Run with `cargo run --example stdio_server --features stdio` and type one request
+//! per line, or pass `--lsp` to use `Content-Length` framing instead.
+
+use a_rs_jsonrpc::{
+    RpcError,
+    stdio::{FramingMode, StdioServer},
+};
+use tracing::Level;
+
+#[a_rs_jsonrpc::jsonrpc_service_fn_array(method = "addArray", version = "v2")]
+async fn add(a: i64, b: i64) -> Result<i64, RpcError> {
+    Ok(a + b)
+}
+
+#[a_rs_jsonrpc::jsonrpc_service_fn_array(method = "ping", version = "v2")]
+async fn ping() -> Result<String, RpcError> {
+    Ok("pong".to_string())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), RpcError> {
+    // stdout carries the protocol, so logs go to stderr
+    tracing_subscriber::fmt()
+        .with_max_level(Level::DEBUG)
+        .with_writer(std::io::stderr)
+        .try_init()
+        .ok();
+
+    a_rs_jsonrpc::init_rpc_service(a_rs_jsonrpc::ServiceConfig::default());
+
+    let framing = if std::env::args().any(|arg| arg == "--lsp") {
+        FramingMode::ContentLength
+    } else {
+        FramingMode::Newline
+    };
+    StdioServer::run_with_framing(framing).await
+}
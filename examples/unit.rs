@@ -1,4 +1,4 @@
-use a_rs_jsonrpc::RpcError;
+use a_rs_jsonrpc::{JsonRpcRequest, RpcError, response::JsonRpcError};
 use serde::Serialize;
 use tracing::Level;
 
@@ -39,7 +39,7 @@ async fn main() {
         .try_init()
         .ok();
 
-    a_rs_jsonrpc::init_rpc_service();
+    a_rs_jsonrpc::init_rpc_service(a_rs_jsonrpc::ServiceConfig::default());
 
     let app = axum::Router::new().route(
         "/",
@@ -56,24 +56,14 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
-fn response_error(req_body: &axum::body::Bytes, err: impl std::fmt::Display) -> String {
-    let Ok((id, version)) = serde_json::from_slice::<serde_json::Value>(req_body).map(|v| {
-        (
-            v.get("id").cloned().unwrap_or(serde_json::Value::Null),
-            v.get("jsonrpc").cloned().unwrap_or(serde_json::Value::Null),
-        )
-    }) else {
-        return serde_json::to_string(&serde_json::json!({
+fn response_error(req_body: &axum::body::Bytes, err: RpcError) -> String {
+    match serde_json::from_slice::<JsonRpcRequest<serde_json::Value>>(req_body) {
+        Ok(req) => err.into_jsonrpc_error_response(req.id, req.jsonrpc),
+        Err(_) => serde_json::to_string(&serde_json::json!({
             "jsonrpc": null,
-            "error": { "code": -32603, "message": err.to_string() },
+            "error": JsonRpcError::from(err),
             "id": null
         }))
-        .unwrap_or_default();
-    };
-    serde_json::to_string(&serde_json::json!({
-        "jsonrpc": version,
-        "error": { "code": -32603, "message": err.to_string() },
-        "id": id
-    }))
-    .unwrap_or_default()
+        .unwrap_or_default(),
+    }
 }
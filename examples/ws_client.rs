@@ -0,0 +1,33 @@
+//! Sends several calls concurrently over one WebSocket connection.
+//!
+//! Run with `cargo run --example ws_client --features ws` against a JSON-RPC server
+//! accepting WebSocket connections on `ws://localhost:3000/ws`.
+
+use a_rs_jsonrpc::{JsonRpcResponse, RpcError, WsJsonRpcClient};
+use tracing::Level;
+
+#[tokio::main]
+async fn main() -> Result<(), RpcError> {
+    tracing_subscriber::fmt()
+        .with_max_level(Level::DEBUG)
+        .try_init()
+        .ok();
+
+    let client = WsJsonRpcClient::new("ws://localhost:3000/ws").await?;
+
+    let (sum, product, pong) = tokio::join!(
+        client.call_v2::<_, i64>("addArray", (10, 20)),
+        client.call_v2::<_, i64>("mulArray", (6, 7)),
+        client.call_v2::<_, String>("ping", serde_json::json!([])),
+    );
+    let (sum, product, pong): (
+        JsonRpcResponse<i64>,
+        JsonRpcResponse<i64>,
+        JsonRpcResponse<String>,
+    ) = (sum?, product?, pong?);
+
+    tracing::info!("addArray: {:?}", sum.result);
+    tracing::info!("mulArray: {:?}", product.result);
+    tracing::info!("ping: {:?}", pong.result);
+    Ok(())
+}
@@ -7,8 +7,11 @@
 //! ## Core Macros
 //! - **`JsonRpcClient`**: A derive macro for request structures.
 //! - **`rpc_method`**: An attribute macro for defining client-side calling interfaces.
+//! - **`rpc_interface`**: An attribute macro turning a trait into a typed client interface.
 //! - **`jsonrpc_service_fn_array`**: A server-side attribute for positional (array) parameters.
 //! - **`jsonrpc_service_fn_obj`**: A server-side attribute for named (object) parameters.
+//! - **`jsonrpc_service_fn`**: A server-side attribute that picks array or object mode
+//!   from the function signature.
 use convert_case::{Case, Casing};
 use darling::{FromDeriveInput, FromField, FromVariant};
 use proc_macro::TokenStream;
@@ -35,6 +38,37 @@ struct FieldOpts {
 struct VariantOpts {
     #[darling(default)]
     skip: bool,
+    #[darling(default)]
+    method: Option<String>,
+}
+
+/// Reads the predicate path of `#[serde(skip_serializing_if = "...")]` on a field, if any.
+fn serde_skip_serializing_if(field: &syn::Field) -> Option<syn::ExprPath> {
+    let mut pred = None;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip_serializing_if") {
+                let lit: syn::LitStr = meta.value()?.parse()?;
+                pred = Some(lit.parse()?);
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+    }
+    pred
+}
+
+/// Generates the `add_param` call for a field bound by reference in an enum match arm.
+fn variant_add_param(
+    binding: proc_macro2::TokenStream,
+    field: &syn::Field,
+) -> proc_macro2::TokenStream {
+    let add = quote! { body.add_param(serde_json::to_value(#binding.clone()).unwrap_or(serde_json::Value::Null)); };
+    match serde_skip_serializing_if(field) {
+        Some(pred) => quote! { if !#pred(#binding) { #add } },
+        None => add,
+    }
 }
 
 /// # `JsonRpcClient`
@@ -46,11 +80,34 @@ struct VariantOpts {
 /// 1. **Positional (Array)**: Maps fields/variants to a JSON array.
 /// 2. **Named (Object)**: Maps the entire structure to a JSON object.
 ///
+/// The generated impls create a new `reqwest::Client` per call. To reuse one, call
+/// the generated `with_client(client)`, which returns a `{Name}WithClient` newtype
+/// implementing the same traits with the given client.
+///
 /// ### Container Attributes
 /// - `#[jsonrpc(url = "...", content_type = "...", method = "...")]`
 ///
 /// ### Field/Variant Attributes
 /// - `#[jsonrpc(skip)]`: Excludes the field or variant from parameter serialization.
+/// - `#[serde(skip_serializing_if = "path")]`: Leaves the field out of the positional
+///   parameters whenever the predicate returns `true`, mirroring the object mode.
+/// - `#[jsonrpc(method = "...")]` on an enum variant: The method the `call_rpc_*`
+///   functions call for that variant, instead of the container's `method`. It also
+///   applies to a skipped variant, which is then called without parameters.
+///
+/// ```rust
+/// # use a_rs_jsonrpc::{JsonRpcClient, JsonRpcResponse, async_trait};
+/// # use serde::Serialize;
+/// #[derive(Clone, Serialize, JsonRpcClient)]
+/// #[jsonrpc(url = "http://localhost:8080", content_type = "application/json", method = "getBlock")]
+/// enum BlockQuery {
+///     ByNumber(u64),
+///     #[jsonrpc(method = "getBlockByHash")]
+///     ByHash(String),
+///     #[jsonrpc(skip, method = "getLatestBlock")]
+///     Latest,
+/// }
+/// ```
 #[proc_macro_derive(JsonRpcClient, attributes(jsonrpc))]
 pub fn derive_json_rpc_client(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -62,8 +119,11 @@ pub fn derive_json_rpc_client(input: TokenStream) -> TokenStream {
 
     let struct_name = &input.ident;
     let (url, content_type, method) = (opts.url, opts.content_type, opts.method);
+    let url_warning = cleartext_url_warning(&url);
 
-    let add_params_arm = match input.data {
+    // `this` is the expression holding the parameters: `self` for the derived type
+    // itself, `(&self.0)` for its `WithClient` newtype.
+    let add_params = |this: &proc_macro2::TokenStream| match input.data {
         Data::Struct(ref data) => {
             let fields = data.fields.iter().enumerate().map(|(i, f)| {
                 let field_opts = FieldOpts::from_field(f).unwrap();
@@ -71,11 +131,17 @@ pub fn derive_json_rpc_client(input: TokenStream) -> TokenStream {
                     return quote! {};
                 }
 
-                if let Some(ref ident) = f.ident {
-                    quote! { body.add_param(serde_json::to_value(self.#ident.clone()).unwrap_or(serde_json::Value::Null)); }
-                } else {
-                    let idx = syn::Index::from(i);
-                    quote! { body.add_param(serde_json::to_value(self.#idx.clone()).unwrap_or(serde_json::Value::Null)); }
+                let access = match f.ident {
+                    Some(ref ident) => quote! { #this.#ident },
+                    None => {
+                        let idx = syn::Index::from(i);
+                        quote! { #this.#idx }
+                    }
+                };
+                let add = quote! { body.add_param(serde_json::to_value(#access.clone()).unwrap_or(serde_json::Value::Null)); };
+                match serde_skip_serializing_if(f) {
+                    Some(pred) => quote! { if !#pred(&#access) { #add } },
+                    None => add,
                 }
             });
             quote! { #(#fields)* }
@@ -86,174 +152,265 @@ pub fn derive_json_rpc_client(input: TokenStream) -> TokenStream {
                 let variant_opts = VariantOpts::from_variant(variant).unwrap();
 
                 if variant_opts.skip {
-                    return quote! { Self::#variant_ident { .. } => {} };
+                    return quote! { #struct_name::#variant_ident { .. } => {} };
                 }
 
                 match variant.fields {
                     syn::Fields::Named(ref fields) => {
                         let names = fields.named.iter().map(|f| &f.ident);
-                        let idents = names.clone();
+                        let adds = fields.named.iter().map(|f| {
+                            let ident = &f.ident;
+                            variant_add_param(quote! { #ident }, f)
+                        });
                         quote! {
-                            Self::#variant_ident { #(#names),* } => {
-                                #( body.add_param(serde_json::to_value(#idents.clone()).unwrap_or(serde_json::Value::Null)); )*
+                            #struct_name::#variant_ident { #(#names),* } => {
+                                #( #adds )*
                             }
                         }
                     }
                     syn::Fields::Unnamed(ref fields) => {
                         let placeholder =
                             (0..fields.unnamed.len()).map(|i| quote::format_ident!("arg{}", i));
-                        let idents = placeholder.clone();
+                        let adds = fields.unnamed.iter().enumerate().map(|(i, f)| {
+                            let ident = quote::format_ident!("arg{}", i);
+                            variant_add_param(quote! { #ident }, f)
+                        });
                         quote! {
-                            Self::#variant_ident ( #(#placeholder),* ) => {
-                                #( body.add_param(serde_json::to_value(#idents.clone()).unwrap_or(serde_json::Value::Null)); )*
+                            #struct_name::#variant_ident ( #(#placeholder),* ) => {
+                                #( #adds )*
                             }
                         }
                     }
                     syn::Fields::Unit => {
-                        quote! { Self::#variant_ident => {} }
+                        quote! { #struct_name::#variant_ident => {} }
                     }
                 }
             });
             quote! {
-                match self {
+                match #this {
                     #(#arms)*
                 }
             }
         }
         _ => panic!("JsonRpcClient only support Structs"),
     };
-    let obj_add_params_block = quote! {
-        use serde_json::Map;
-        let mut val = serde_json::to_value(self.clone()).unwrap_or(serde_json::Value::Object(Map::new()));
-        body.set_params(val);
+    let obj_add_params = |this: &proc_macro2::TokenStream| {
+        quote! {
+            use serde_json::Map;
+            let mut val = serde_json::to_value(#this.clone()).unwrap_or(serde_json::Value::Object(Map::new()));
+            body.set_params(val);
+        }
+    };
+    // The method called by the `call_rpc_*` functions, which enum variants may
+    // override.
+    let call_method = |this: &proc_macro2::TokenStream| {
+        let Data::Enum(ref data) = input.data else {
+            return quote! { #method };
+        };
+        let arms: Vec<_> = data
+            .variants
+            .iter()
+            .filter_map(|variant| {
+                let variant_ident = &variant.ident;
+                let variant_method = VariantOpts::from_variant(variant).unwrap().method?;
+                Some(quote! { #struct_name::#variant_ident { .. } => #variant_method, })
+            })
+            .collect();
+        if arms.is_empty() {
+            return quote! { #method };
+        }
+        let default_arm = if arms.len() < data.variants.len() {
+            quote! { _ => #method, }
+        } else {
+            quote! {}
+        };
+        quote! {
+            match #this {
+                #(#arms)*
+                #default_arm
+            }
+        }
     };
+    let add_params_arm = add_params(&quote! { self });
+    let obj_add_params_block = obj_add_params(&quote! { self });
+
+    // The trait impls send through `client`: a fresh `reqwest::Client` for the
+    // derived type, the bound one for its `WithClient` newtype.
+    let client_impls = |ty: &proc_macro2::TokenStream,
+                        this: proc_macro2::TokenStream,
+                        client: proc_macro2::TokenStream| {
+        let arm = add_params(&this);
+        let obj = obj_add_params(&this);
+        let method = call_method(&this);
+        quote! {
+            #[async_trait::async_trait]
+            impl ::a_rs_jsonrpc::client::JsonRpcClient for #ty {
+                async fn send_v1_request<R>(
+                    &self,
+                    url: &str,
+                    content_type: &str,
+                    method: &str,
+                ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
+                where
+                    R: serde::de::DeserializeOwned,
+                {
+                    let id = ::a_rs_jsonrpc::JsonRpcId::next_number();
+                    let mut body: ::a_rs_jsonrpc::request::JsonRpcRequest<std::vec::Vec<serde_json::Value>> = ::a_rs_jsonrpc::request::JsonRpcRequest::new_v1(id, method);
+
+                    #arm
+                    tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
+
+                    let resp = #client
+                        .post(url)
+                        .header("Content-Type", content_type)
+                        .headers(::a_rs_jsonrpc::client::trace_context_headers())
+                        .json(&body)
+                        .send()
+                        .await?;
+                    let text = resp.text().await?;
+                    tracing::debug!("jsonrpc response body: {}", text);
+                    Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
+                }
 
-    let expanded = quote! {
-        #[async_trait::async_trait]
-        impl ::a_rs_jsonrpc::client::JsonRpcClient for #struct_name {
-            async fn send_v1_request<R>(
-                &self,
-                url: &str,
-                content_type: &str,
-                method: &str,
-            ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
-            where
-                R: serde::de::DeserializeOwned,
-            {
-                let id = ::a_rs_jsonrpc::JsonRpcId::next_number();
-                let mut body: ::a_rs_jsonrpc::request::JsonRpcRequest<std::vec::Vec<serde_json::Value>> = ::a_rs_jsonrpc::request::JsonRpcRequest::new_v1(id, method);
+                async fn send_v2_request<R>(
+                    &self,
+                    url: &str,
+                    content_type: &str,
+                    method: &str,
+                ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
+                where
+                    R: serde::de::DeserializeOwned,
+                {
+                    let id = ::a_rs_jsonrpc::JsonRpcId::next_number();
+                    let mut body: ::a_rs_jsonrpc::request::JsonRpcRequest<std::vec::Vec<serde_json::Value>> = ::a_rs_jsonrpc::request::JsonRpcRequest::new_v2(id, method);
+
+                    #arm
+                    tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
+
+                    let resp = #client
+                        .post(url)
+                        .header("Content-Type", content_type)
+                        .headers(::a_rs_jsonrpc::client::trace_context_headers())
+                        .json(&body)
+                        .send()
+                        .await?;
+                    let text = resp.text().await?;
+                    tracing::debug!("jsonrpc response body: {}", text);
+                    Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
+                }
 
-                #add_params_arm
-                tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
-
-                let resp = reqwest::Client::new()
-                    .post(url)
-                    .header("Content-Type", content_type)
-                    .json(&body)
-                    .send()
-                    .await?;
-                let text = resp.text().await?;
-                tracing::debug!("jsonrpc response body: {}", text);
-                Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
-            }
-
-            async fn send_v2_request<R>(
-                &self,
-                url: &str,
-                content_type: &str,
-                method: &str,
-            ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
-            where
-                R: serde::de::DeserializeOwned,
-            {
-                let id = ::a_rs_jsonrpc::JsonRpcId::next_number();
-                let mut body: ::a_rs_jsonrpc::request::JsonRpcRequest<std::vec::Vec<serde_json::Value>> = ::a_rs_jsonrpc::request::JsonRpcRequest::new_v2(id, method);
+                async fn send_v1_request_obj<R>(
+                    &self,
+                    url: &str,
+                    content_type: &str,
+                    method: &str,
+                ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
+                where R: serde::de::DeserializeOwned
+                {
+                    let id = ::a_rs_jsonrpc::JsonRpcId::next_number();
+                    let mut body = ::a_rs_jsonrpc::request::JsonRpcRequest::new_v1(id, method);
+                    #obj
+                    tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
+                    let resp = #client.post(url).header("Content-Type", content_type).headers(::a_rs_jsonrpc::client::trace_context_headers()).json(&body).send().await?;
+                    let text = resp.text().await?;
+                    tracing::debug!("jsonrpc response body: {}", text);
+                    Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
+                }
 
-                #add_params_arm
-                tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
-
-                let resp = reqwest::Client::new()
-                    .post(url)
-                    .header("Content-Type", content_type)
-                    .json(&body)
-                    .send()
-                    .await?;
-                let text = resp.text().await?;
-                tracing::debug!("jsonrpc response body: {}", text);
-                Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
-            }
-
-            async fn send_v1_request_obj<R>(
-                &self,
-                url: &str,
-                content_type: &str,
-                method: &str,
-            ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
-            where R: serde::de::DeserializeOwned
-            {
-                let id = ::a_rs_jsonrpc::JsonRpcId::next_number();
-                let mut body = ::a_rs_jsonrpc::request::JsonRpcRequest::new_v1(id, method);
-                #obj_add_params_block
-                tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
-                let resp = reqwest::Client::new().post(url).header("Content-Type", content_type).json(&body).send().await?;
-                let text = resp.text().await?;
-                tracing::debug!("jsonrpc response body: {}", text);
-                Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
-            }
-
-            async fn send_v2_request_obj<R>(
-                &self,
-                url: &str,
-                content_type: &str,
-                method: &str,
-            ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
-            where R: serde::de::DeserializeOwned
-            {
-                let id = ::a_rs_jsonrpc::JsonRpcId::next_number();
-                let mut body = ::a_rs_jsonrpc::request::JsonRpcRequest::new_v2(id, method);
-                #obj_add_params_block
-                tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
-                let resp = reqwest::Client::new().post(url).header("Content-Type", content_type).json(&body).send().await?;
-                let text = resp.text().await?;
-                tracing::debug!("jsonrpc response body: {}", text);
-                Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
-            }
-        }
+                async fn send_v2_request_obj<R>(
+                    &self,
+                    url: &str,
+                    content_type: &str,
+                    method: &str,
+                ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
+                where R: serde::de::DeserializeOwned
+                {
+                    let id = ::a_rs_jsonrpc::JsonRpcId::next_number();
+                    let mut body = ::a_rs_jsonrpc::request::JsonRpcRequest::new_v2(id, method);
+                    #obj
+                    tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
+                    let resp = #client.post(url).header("Content-Type", content_type).headers(::a_rs_jsonrpc::client::trace_context_headers()).json(&body).send().await?;
+                    let text = resp.text().await?;
+                    tracing::debug!("jsonrpc response body: {}", text);
+                    Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
+                }
 
-        #[async_trait::async_trait]
-        impl ::a_rs_jsonrpc::client::JsonRpcClientCall for #struct_name {
-            async fn call_rpc_v1<R>(
-                &self,
-            ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
-            where
-                R: serde::de::DeserializeOwned,
-            {
-                self.send_v1_request(&#url, &#content_type, &#method).await
+                fn to_params(&self) -> std::result::Result<serde_json::Value, ::a_rs_jsonrpc::error::RpcError> {
+                    let mut body: ::a_rs_jsonrpc::request::JsonRpcRequest<std::vec::Vec<serde_json::Value>> = ::a_rs_jsonrpc::request::JsonRpcRequest::new_v2(::a_rs_jsonrpc::JsonRpcId::Null, "");
+                    #arm
+                    Ok(serde_json::Value::Array(body.params.into_option().unwrap_or_default()))
+                }
             }
 
-            async fn call_rpc_v2<R>(
-                &self,
-            ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
-            where
-                R: serde::de::DeserializeOwned,
-            {
-                self.send_v2_request(&#url, &#content_type, &#method).await
-            }
+            #[async_trait::async_trait]
+            impl ::a_rs_jsonrpc::client::JsonRpcClientCall for #ty {
+                async fn call_rpc_v1<R>(
+                    &self,
+                ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
+                where
+                    R: serde::de::DeserializeOwned,
+                {
+                    self.send_v1_request(&#url, &#content_type, #method).await
+                }
 
-            async fn call_rpc_v1_obj<R>(
-                &self,
-            ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
-            where R: serde::de::DeserializeOwned
-            {
-                self.send_v1_request_obj(&#url, &#content_type, &#method).await
+                async fn call_rpc_v2<R>(
+                    &self,
+                ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
+                where
+                    R: serde::de::DeserializeOwned,
+                {
+                    self.send_v2_request(&#url, &#content_type, #method).await
+                }
+
+                async fn call_rpc_v1_obj<R>(
+                    &self,
+                ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
+                where R: serde::de::DeserializeOwned
+                {
+                    self.send_v1_request_obj(&#url, &#content_type, #method).await
+                }
+
+                async fn call_rpc_v2_obj<R>(
+                    &self,
+                ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
+                where R: serde::de::DeserializeOwned
+                {
+                    self.send_v2_request_obj(&#url, &#content_type, #method).await
+                }
             }
+        }
+    };
+    let with_client_ident = quote::format_ident!("{}WithClient", struct_name);
+    let vis = &input.vis;
+    let derived_impls = client_impls(
+        &quote! { #struct_name },
+        quote! { self },
+        quote! { reqwest::Client::new() },
+    );
+    let with_client_impls = client_impls(
+        &quote! { #with_client_ident },
+        quote! { (&self.0) },
+        quote! { self.1 },
+    );
 
-            async fn call_rpc_v2_obj<R>(
-                &self,
-            ) -> std::result::Result<::a_rs_jsonrpc::response::JsonRpcResponse<R>, ::a_rs_jsonrpc::error::RpcError>
-            where R: serde::de::DeserializeOwned
-            {
-                self.send_v2_request_obj(&#url, &#content_type, &#method).await
+    let expanded = quote! {
+        #url_warning
+
+        #derived_impls
+
+        #with_client_impls
+
+        /// Parameters bound to a `reqwest::Client`, produced by `with_client`.
+        ///
+        /// Sends through the stored client instead of creating one per call, so
+        /// repeated calls reuse its connection pool.
+        #[derive(Clone)]
+        #vis struct #with_client_ident(pub #struct_name, pub ::reqwest::Client);
+
+        impl #struct_name {
+            /// Binds these parameters to `client`, so the call reuses its connection pool.
+            pub fn with_client(self, client: ::reqwest::Client) -> #with_client_ident {
+                #with_client_ident(self, client)
             }
         }
 
@@ -264,7 +421,7 @@ pub fn derive_json_rpc_client(input: TokenStream) -> TokenStream {
                     ::a_rs_jsonrpc::JsonRpcId::Number(1), "debug"
                 );
                 #add_params_arm
-                body.params
+                body.params.into_option()
             }
 
             pub fn debug_params_obj(&self) -> Option<serde_json::Value> {
@@ -272,7 +429,7 @@ pub fn derive_json_rpc_client(input: TokenStream) -> TokenStream {
                     ::a_rs_jsonrpc::JsonRpcId::Number(1), "debug"
                 );
                 #obj_add_params_block
-                body.params
+                body.params.into_option()
             }
         }
     };
@@ -286,13 +443,99 @@ use syn::{FnArg, ItemFn, Pat};
 #[derive(Debug, FromMeta)]
 struct RpcMethodArgs {
     url: String,
-    method: String,
+    #[darling(default)]
+    method: Option<String>,
+    #[darling(default)]
+    method_fn: Option<String>,
     #[darling(default = "default_content_type")]
     content_type: String,
     #[darling(default = "default_version")]
     version: String,
     #[darling(default)]
     mode: String,
+    #[darling(default)]
+    on_success: Option<String>,
+    #[darling(default)]
+    timeout_ms: Option<i64>,
+    #[darling(default)]
+    retry: Option<i64>,
+    #[darling(default)]
+    retry_delay_ms: Option<i64>,
+    #[darling(default)]
+    headers: HeaderList,
+    #[darling(default)]
+    log_level: Option<String>,
+    #[darling(default)]
+    encoding: Option<String>,
+    #[darling(default)]
+    unwrap_result: bool,
+    #[darling(default)]
+    map_error: Option<String>,
+    #[darling(default)]
+    id: Option<FixedId>,
+}
+
+/// A fixed request id written as `id = 42` or `id = "my-fixed-id"`.
+#[derive(Debug)]
+enum FixedId {
+    Number(u64),
+    String(String),
+}
+
+impl FromMeta for FixedId {
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        match value {
+            syn::Lit::Int(n) => Ok(FixedId::Number(n.base10_parse()?)),
+            syn::Lit::Str(s) => Ok(FixedId::String(s.value())),
+            _ => Err(
+                darling::Error::custom("expected a non-negative integer or a string literal")
+                    .with_span(value),
+            ),
+        }
+    }
+}
+
+/// Literal HTTP header pairs written as `headers = [("X-Api-Key", "secret"), ...]`.
+#[derive(Debug, Default)]
+struct HeaderList(Vec<(String, String)>);
+
+impl FromMeta for HeaderList {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        let syn::Expr::Array(array) = expr else {
+            return Err(
+                darling::Error::custom("expected a list of (name, value) pairs").with_span(expr),
+            );
+        };
+        let str_lit = |expr: &syn::Expr| match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(s),
+                ..
+            }) => Ok(s.value()),
+            _ => Err(darling::Error::custom("expected a string literal").with_span(expr)),
+        };
+        array
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                syn::Expr::Tuple(tuple) if tuple.elems.len() == 2 => {
+                    Ok((str_lit(&tuple.elems[0])?, str_lit(&tuple.elems[1])?))
+                }
+                _ => Err(darling::Error::custom("expected a (name, value) pair").with_span(elem)),
+            })
+            .collect::<darling::Result<Vec<_>>>()
+            .map(HeaderList)
+    }
+}
+
+/// Returns `value` as `u64`, or a compile error naming `key` if it is not positive.
+fn positive_arg(key: &str, value: i64) -> syn::Result<u64> {
+    if value <= 0 {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            format!("`{}` must be greater than zero", key),
+        ));
+    }
+    Ok(value as u64)
 }
 
 fn default_version() -> String {
@@ -313,11 +556,37 @@ fn default_content_type() -> String {
 /// ### Arguments
 /// - `url`: The RPC endpoint URL.
 /// - `method`: The remote method name.
+/// - `method_fn`: (Instead of `method`) Path of a function called on every request to
+///   get the method name at runtime, e.g. from configuration. It may return anything
+///   implementing `AsRef<str>`.
 /// - `mode`: (Optional) Use `"obj"` for named parameters.
 /// - `version`: (Optional) `"v1"` or `"v2"`.
+/// - `on_success`: (Optional) Path of a `fn(&JsonRpcResponse<T>)` called when a
+///   response without an `error` object is received, e.g. for success-path logging.
+/// - `timeout_ms`: (Optional) Time limit of each attempt; an elapsed attempt fails
+///   with `RpcError::Timeout`.
+/// - `retry`: (Optional) Number of retries after a `ReqwestError` or `Timeout`.
+/// - `retry_delay_ms`: (Optional) Delay between retries, 100 ms by default.
+/// - `headers`: (Optional) Extra HTTP headers as literal pairs, e.g.
+///   `headers = [("X-Api-Key", "secret"), ("X-Tenant", "acme")]`.
+/// - `log_level`: (Optional) The `tracing` level of the request/response body logs:
+///   `"trace"`, `"debug"` (default), `"info"`, `"warn"` or `"error"`.
+/// - `encoding`: (Optional) `"json"` (default) or `"msgpack"`, which sends the request
+///   and reads the response as MessagePack with `Content-Type: application/msgpack`,
+///   ignoring `content_type`. Requires the library's `msgpack` feature.
+/// - `unwrap_result`: (Optional) When `true`, the generated function returns
+///   `Result<T, RpcError>` instead of `Result<JsonRpcResponse<T>, RpcError>`, turning
+///   a response `error` object into the `Err` variant.
+/// - `map_error`: (Optional) Path of a `fn(JsonRpcError) -> RpcError` converting a
+///   response `error` object into the returned error, instead of the `From` impl.
+///   Without `unwrap_result`, a response carrying an `error` object is returned as
+///   that error.
+/// - `id`: (Optional) A fixed request id, `id = 42` or `id = "my-fixed-id"`, sent on
+///   every call instead of the next number from `JsonRpcId::next_number()`.
 ///
 /// ### Example
 /// ```rust
+/// # use a_rs_jsonrpc::{JsonRpcResponse, RpcError, rpc_method};
 /// #[rpc_method(url = "http://localhost:8080", method = "sum")]
 /// async fn get_sum(a: i32, b: i32) -> Result<JsonRpcResponse<i32>, RpcError> {}
 /// ```
@@ -342,6 +611,11 @@ pub fn rpc_method(attr: TokenStream, item: TokenStream) -> TokenStream {
     let crate_root = quote! { ::a_rs_jsonrpc };
 
     let inner_t = extract_actual_data_type(&sig.output);
+    let map_error = match args.map_error.as_deref().map(syn::parse_str::<syn::Path>) {
+        Some(Ok(map_error)) => Some(map_error),
+        Some(Err(e)) => return TokenStream::from(e.to_compile_error()),
+        None => None,
+    };
 
     let mut fields = Vec::new();
     let mut field_idents = Vec::new();
@@ -357,12 +631,120 @@ pub fn rpc_method(attr: TokenStream, item: TokenStream) -> TokenStream {
     }
 
     let is_obj = args.mode == "obj";
+    let url_warning = cleartext_url_warning(&args.url);
     let url = args.url;
-    let method = args.method;
+    let (method_prelude, method) = match (args.method, args.method_fn) {
+        (Some(method), None) => (quote! {}, quote! { #method }),
+        (None, Some(method_fn)) => match syn::parse_str::<syn::Path>(&method_fn) {
+            Ok(method_fn) => (
+                quote! {
+                    let __rpc_method = #method_fn();
+                    let __rpc_method: &str = ::std::convert::AsRef::as_ref(&__rpc_method);
+                },
+                quote! { __rpc_method },
+            ),
+            Err(e) => return TokenStream::from(e.to_compile_error()),
+        },
+        _ => {
+            return TokenStream::from(
+                syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "exactly one of `method` and `method_fn` must be set",
+                )
+                .to_compile_error(),
+            )
+        }
+    };
     let content_type = args.content_type;
     let version_str = args.version.to_lowercase();
+    let header_names = args.headers.0.iter().map(|(name, _)| name);
+    let header_values = args.headers.0.iter().map(|(_, value)| value);
+    let extra_headers = quote! { #(.header(#header_names, #header_values))* };
+    let log_level = match args.log_level.as_deref() {
+        None => format_ident!("debug"),
+        Some(level @ ("trace" | "debug" | "info" | "warn" | "error")) => format_ident!("{}", level),
+        Some(_) => return TokenStream::from(
+            syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "log_level must be one of \"trace\", \"debug\", \"info\", \"warn\" or \"error\"",
+            )
+            .to_compile_error(),
+        ),
+    };
+
+    let msgpack = match args.encoding.as_deref() {
+        None | Some("json") => false,
+        Some("msgpack") => true,
+        Some(_) => {
+            return TokenStream::from(
+                syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "encoding must be \"json\" or \"msgpack\"",
+                )
+                .to_compile_error(),
+            )
+        }
+    };
+    let send_body = if msgpack {
+        quote! {
+            let request = ::reqwest::Client::new().post(#url).headers(#crate_root::client::trace_context_headers())#extra_headers;
+            #crate_root::client::post_msgpack_request::<_, #inner_t>(request, &body).await
+        }
+    } else {
+        quote! {
+            let resp = ::reqwest::Client::new().post(#url).header("Content-Type", #content_type).headers(#crate_root::client::trace_context_headers())#extra_headers.json(&body).send().await?;
+
+            let text = resp.text().await?;
+            tracing::#log_level!("jsonrpc response body: {}", text);
+
+            Ok(::serde_json::from_str::<#crate_root::response::JsonRpcResponse<#inner_t>>(&text)?)
+        }
+    };
+
+    let next_id = match &args.id {
+        None => quote! { #crate_root::JsonRpcId::next_number() },
+        Some(FixedId::Number(n)) => quote! { #crate_root::JsonRpcId::Number(#n) },
+        Some(FixedId::String(s)) => {
+            quote! { #crate_root::JsonRpcId::String(::std::string::String::from(#s)) }
+        }
+    };
 
-    let call_block = if !is_obj {
+    let no_params = field_idents.is_empty();
+    let call_block = if no_params
+        || (!is_obj
+            && (!args.headers.0.is_empty()
+                || args.log_level.is_some()
+                || msgpack
+                || args.id.is_some()))
+    {
+        // The `JsonRpcClient` trait has no way to pass extra headers, pick the log
+        // level, change the encoding or fix the id, so build the positional request
+        // here, with the parameters `JsonRpcClient::send_v2_request` would send. A
+        // method without arguments sends no `params` key at all, whatever its mode.
+        let new_request_fn = if version_str.contains("v1") {
+            format_ident!("new_v1")
+        } else {
+            format_ident!("new_v2")
+        };
+        let set_params = if no_params {
+            quote! { let body = body.no_params(); }
+        } else {
+            quote! {
+                let params = (#(#field_idents.clone()),*);
+                body.set_params(#crate_root::client::JsonRpcClient::to_params(&params)?);
+            }
+        };
+        quote! {
+            let id = #next_id;
+            #[allow(unused_mut)]
+            let mut body: #crate_root::request::JsonRpcRequest<::serde_json::Value> =
+                #crate_root::request::JsonRpcRequest::#new_request_fn(id, #method);
+            #set_params
+
+            tracing::#log_level!("jsonrpc request body: {:?}", serde_json::to_string(&body));
+            #send_body
+        }
+    } else if !is_obj {
         let send_method = if version_str.contains("v1") {
             format_ident!("send_v1_request")
         } else {
@@ -386,24 +768,138 @@ pub fn rpc_method(attr: TokenStream, item: TokenStream) -> TokenStream {
 
             let helper = Helper { #(#field_idents: #field_idents.clone()),* };
 
-            let id = #crate_root::JsonRpcId::next_number();
+            let id = #next_id;
             let mut body = #crate_root::request::JsonRpcRequest::#new_request_fn(id, #method);
 
             let val = ::serde_json::to_value(helper).unwrap_or(::serde_json::Value::Object(::serde_json::Map::new()));
             body.set_params(val);
 
-            tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
-            let resp = ::reqwest::Client::new().post(#url).header("Content-Type", #content_type).json(&body).send().await?;
+            tracing::#log_level!("jsonrpc request body: {:?}", serde_json::to_string(&body));
+            #send_body
+        }
+    };
 
-            let text = resp.text().await?;
-            tracing::debug!("jsonrpc response body: {}", text);
+    let timeout_ms = match args
+        .timeout_ms
+        .map(|v| positive_arg("timeout_ms", v))
+        .transpose()
+    {
+        Ok(v) => v,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+    let retry = match args.retry.map(|v| positive_arg("retry", v)).transpose() {
+        Ok(v) => v,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+    let retry_delay_ms = match args
+        .retry_delay_ms
+        .map(|v| positive_arg("retry_delay_ms", v))
+        .transpose()
+    {
+        Ok(v) => v.unwrap_or(100),
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+    let on_success = match args.on_success {
+        Some(ref on_success) => match syn::parse_str::<syn::Path>(on_success) {
+            Ok(callback) => quote! {
+                if let Ok(ref resp) = __rpc_result {
+                    if resp.error.is_none() {
+                        #callback(resp);
+                    }
+                }
+            },
+            Err(e) => return TokenStream::from(e.to_compile_error()),
+        },
+        None => quote! {},
+    };
 
-            Ok(::serde_json::from_str::<#crate_root::response::JsonRpcResponse<#inner_t>>(&text)?)
+    let call_block = if timeout_ms.is_some() || retry.is_some() || args.on_success.is_some() {
+        let attempt = match timeout_ms {
+            Some(ms) => quote! {
+                match #crate_root::tokio::time::timeout(
+                    ::std::time::Duration::from_millis(#ms),
+                    async { #call_block },
+                )
+                .await
+                {
+                    Ok(result) => result,
+                    Err(elapsed) => Err(#crate_root::error::RpcError::from(elapsed)),
+                }
+            },
+            None => quote! { async { #call_block }.await },
+        };
+        let result = match retry {
+            Some(retries) => quote! {{
+                let mut __rpc_retried: u64 = 0;
+                loop {
+                    let __rpc_attempt: ::std::result::Result<
+                        #crate_root::response::JsonRpcResponse<#inner_t>,
+                        #crate_root::error::RpcError,
+                    > = #attempt;
+                    match __rpc_attempt {
+                        Err(
+                            #crate_root::error::RpcError::ReqwestError(_)
+                            | #crate_root::error::RpcError::Timeout,
+                        ) if __rpc_retried < #retries => {
+                            __rpc_retried += 1;
+                            tracing::warn!("jsonrpc request {} failed, retry {}", #method, __rpc_retried);
+                            #crate_root::tokio::time::sleep(
+                                ::std::time::Duration::from_millis(#retry_delay_ms),
+                            )
+                            .await;
+                        }
+                        __rpc_attempt => break __rpc_attempt,
+                    }
+                }
+            }},
+            None => attempt,
+        };
+        quote! {
+            let __rpc_result: ::std::result::Result<
+                #crate_root::response::JsonRpcResponse<#inner_t>,
+                #crate_root::error::RpcError,
+            > = #result;
+            #on_success
+            __rpc_result.map_err(::std::convert::Into::into)
+        }
+    } else {
+        call_block
+    };
+
+    let mut sig = sig.clone();
+    let call_block = if args.unwrap_result || map_error.is_some() {
+        let convert_error = match map_error {
+            Some(map_error) => quote! { #map_error(err) },
+            None => quote! { #crate_root::error::RpcError::from(err) },
+        };
+        let finish = if args.unwrap_result {
+            sig.output = syn::parse_quote! {
+                -> ::std::result::Result<#inner_t, #crate_root::error::RpcError>
+            };
+            quote! { __rpc_response.into_result() }
+        } else {
+            quote! { Ok(__rpc_response) }
+        };
+        quote! {
+            let __rpc_response: ::std::result::Result<
+                #crate_root::response::JsonRpcResponse<#inner_t>,
+                #crate_root::error::RpcError,
+            > = async { #call_block }.await;
+            let mut __rpc_response = __rpc_response?;
+            if let Some(err) = __rpc_response.error.take() {
+                return Err(::std::convert::Into::into(#convert_error));
+            }
+            #finish
         }
+    } else {
+        call_block
     };
 
+    // `#sig` already ends with the where clause.
     let expanded = quote! {
-        #vis #sig #where_clause {
+        #vis #sig {
+            #url_warning
+            #method_prelude
             #call_block
         }
     };
@@ -411,6 +907,28 @@ pub fn rpc_method(attr: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Emits a compile-time warning when `url` uses cleartext HTTP to a non-local host.
+///
+/// Stable Rust has no warning API for proc-macros, so this references a
+/// `#[deprecated]` constant whose note carries the message.
+fn cleartext_url_warning(url: &str) -> proc_macro2::TokenStream {
+    if !url.starts_with("http://") || url.contains("localhost") || url.contains("127.0.0.1") {
+        return quote! {};
+    }
+    let note = format!(
+        "JSON-RPC url `{}` uses cleartext HTTP; use https:// outside local development",
+        url
+    );
+    quote! {
+        const _: () = {
+            #[deprecated(note = #note)]
+            #[allow(non_upper_case_globals)]
+            const cleartext_url: () = ();
+            cleartext_url
+        };
+    }
+}
+
 fn extract_actual_data_type(rt: &syn::ReturnType) -> proc_macro2::TokenStream {
     if let syn::ReturnType::Type(_, ty) = rt {
         if let syn::Type::Path(tp) = ty.as_ref() {
@@ -442,84 +960,537 @@ fn extract_actual_data_type(rt: &syn::ReturnType) -> proc_macro2::TokenStream {
     panic!("Macro requires return type: Result<JsonRpcResponse<T>, E>");
 }
 
-/// # `jsonrpc_service_fn_array`
+#[derive(Debug, FromMeta)]
+struct RpcInterfaceArgs {
+    base_url: String,
+    #[darling(default = "default_content_type")]
+    content_type: String,
+    #[darling(default = "default_version")]
+    version: String,
+}
+
+#[derive(Debug, Default, FromMeta)]
+struct RpcInterfaceMethodArgs {
+    #[darling(default)]
+    method: Option<String>,
+    #[darling(default)]
+    mode: String,
+}
+
+/// # `rpc_interface`
 ///
-/// **Server-side attribute macro for positional parameters.**
+/// **Client-side attribute macro for a whole trait of RPC methods.**
 ///
-/// Use this on an `async fn` to register it as a JSON-RPC service. Incoming
-/// `params` are expected to be a JSON Array.
+/// Every method of the annotated trait becomes a JSON-RPC call, and a concrete
+/// `DefaultClient` struct implementing the trait is generated in the same module.
+/// `DefaultClient` sends all calls through one shared `reqwest::Client`.
 ///
-/// ### Features
-/// 1. Generates a boilerplate `RequestArray` struct for deserialization.
-/// 2. Implements `JsonRpcServiceFn` for the generated struct.
-/// 3. Automatically registers the function into the global `RPC_SERVICES` slice
-///    using `linkme` for zero-cost discovery.
+/// ### Arguments
+/// - `base_url`: The RPC endpoint URL.
+/// - `version`: (Optional) `"v1"` or `"v2"`, defaults to `"v2"`.
+/// - `content_type`: (Optional) Defaults to `"application/json"`.
+///
+/// ### Method Attributes
+/// - `#[rpc(method = "actualName")]`: Overrides the remote method name, which is
+///   otherwise the Rust method name.
+/// - `#[rpc(mode = "obj")]`: Sends named (`camelCase`) instead of positional parameters.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{JsonRpcResponse, RpcError, rpc_interface};
+/// #[rpc_interface(base_url = "http://localhost:3000/")]
+/// trait Arith {
+///     #[rpc(method = "addArray")]
+///     async fn add(&self, a: i64, b: i64) -> Result<JsonRpcResponse<i64>, RpcError>;
+///
+///     #[rpc(method = "addObj", mode = "obj")]
+///     async fn add_obj(&self, lhs: i64, rhs: i64) -> Result<JsonRpcResponse<i64>, RpcError>;
+/// }
+///
+/// # async fn example() -> Result<(), RpcError> {
+/// let resp = DefaultClient::new().add(10, 20).await?;
+/// # Ok(())
+/// # }
+/// ```
 #[proc_macro_attribute]
-pub fn jsonrpc_service_fn_array(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let input_fn = parse_macro_input!(item as ItemFn);
-    let fn_name = &input_fn.sig.ident;
-    let name_str = fn_name.to_string();
-    let struct_name_ident = format_ident!("{}RequestArray", name_str.to_case(Case::Pascal));
+pub fn rpc_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr_args = match darling::ast::NestedMeta::parse_meta_list(attr.into()) {
+        Ok(v) => v,
+        Err(e) => return TokenStream::from(darling::Error::from(e).write_errors()),
+    };
+    let args = match RpcInterfaceArgs::from_list(&attr_args) {
+        Ok(v) => v,
+        Err(e) => return TokenStream::from(e.write_errors()),
+    };
+    let mut item_trait = parse_macro_input!(item as syn::ItemTrait);
 
-    let param_types: Vec<&Type> = input_fn
-        .sig
-        .inputs
-        .iter()
-        .filter_map(|arg| {
-            if let FnArg::Typed(pat_type) = arg {
-                Some(&*pat_type.ty)
-            } else {
-                None
-            }
-        })
-        .collect();
+    let crate_root = quote! { ::a_rs_jsonrpc };
+    let (call_fn, new_request_fn) = if args.version.to_lowercase().contains("v1") {
+        (format_ident!("call_v1"), format_ident!("new_v1"))
+    } else {
+        (format_ident!("call_v2"), format_ident!("new_v2"))
+    };
 
-    let result_inner_type = extract_result_t(&input_fn.sig.output);
-    let args = parse_macro_input!(attr as RpcAttr);
-    let version_val = args.version;
-    let method_val = args.method;
-    let registration_ident = format_ident!("REG_{}", fn_name.to_string().to_uppercase());
+    let mut methods = Vec::new();
+    for trait_item in &mut item_trait.items {
+        let syn::TraitItem::Fn(trait_fn) = trait_item else {
+            continue;
+        };
+        let mut overrides = RpcInterfaceMethodArgs::default();
+        let mut errors = Vec::new();
+        trait_fn.attrs.retain(|attr| {
+            if !attr.path().is_ident("rpc") {
+                return true;
+            }
+            match RpcInterfaceMethodArgs::from_meta(&attr.meta) {
+                Ok(v) => overrides = v,
+                Err(e) => errors.push(e),
+            }
+            false
+        });
+        if !errors.is_empty() {
+            return TokenStream::from(darling::Error::multiple(errors).write_errors());
+        }
 
-    let rpc = quote! { ::a_rs_jsonrpc };
+        let sig = &trait_fn.sig;
+        let inner_t = extract_actual_data_type(&sig.output);
+        let method = overrides.method.unwrap_or_else(|| sig.ident.to_string());
+
+        let mut idents = Vec::new();
+        for arg in &sig.inputs {
+            match arg {
+                FnArg::Receiver(_) => {}
+                FnArg::Typed(pat_type) => match &*pat_type.pat {
+                    Pat::Ident(pat_ident) => idents.push(&pat_ident.ident),
+                    other => {
+                        return TokenStream::from(
+                            syn::Error::new_spanned(
+                                other,
+                                "rpc_interface parameters must be plain identifiers",
+                            )
+                            .to_compile_error(),
+                        )
+                    }
+                },
+            }
+        }
 
-    let has_params = !param_types.is_empty();
+        if idents.is_empty() {
+            // Without arguments no `params` key is sent, whatever the mode.
+            methods.push(quote! {
+                #sig {
+                    let request = #crate_root::request::JsonRpcRequest::<()>::#new_request_fn(
+                        #crate_root::JsonRpcId::next_number(),
+                        #method,
+                    );
+                    Ok(self.inner.send_request::<_, #inner_t>(&request).await?)
+                }
+            });
+            continue;
+        }
 
-    let (tuple_params, call_logic) = if has_params {
-        let ty = if param_types.len() == 1 {
-            let t = param_types[0];
-            quote! { (#t,) }
+        let params = if overrides.mode == "obj" {
+            let keys = idents
+                .iter()
+                .map(|ident| ident.to_string().to_case(Case::Camel));
+            quote! {
+                let mut params = ::serde_json::Map::new();
+                #( params.insert(#keys.to_string(), ::serde_json::to_value(&#idents)?); )*
+            }
         } else {
-            quote! { (#(#param_types),*) }
+            quote! {
+                let params: ::std::vec::Vec<::serde_json::Value> =
+                    vec![#(::serde_json::to_value(&#idents)?),*];
+            }
         };
 
-        let param_indices: Vec<_> = (0..param_types.len())
-            .map(|i| {
-                let index = syn::Index::from(i);
-                quote! { params.#index }
-            })
-            .collect();
+        methods.push(quote! {
+            #sig {
+                #params
+                Ok(self.inner.#call_fn::<_, #inner_t>(#method, params).await?)
+            }
+        });
+    }
 
-        (
-            quote! { #ty },
-            quote! {
-                let params = request.params.ok_or_else(|| {
-                    #rpc::RpcError::InvalidParams(format!("Method '{}' requires array parameters", #method_val))
+    let vis = &item_trait.vis;
+    let trait_ident = &item_trait.ident;
+    let base_url = args.base_url;
+    let content_type = args.content_type;
+    let url_warning = cleartext_url_warning(&base_url);
+
+    let expanded = quote! {
+        #[#crate_root::async_trait::async_trait]
+        #item_trait
+
+        #url_warning
+
+        /// The generated client implementing the RPC interface.
+        #[derive(Debug, Clone)]
+        #vis struct DefaultClient {
+            inner: #crate_root::SharedClient,
+        }
+
+        impl DefaultClient {
+            /// Creates a client with its own `reqwest::Client`.
+            pub fn new() -> Self {
+                Self::with_client(::reqwest::Client::new())
+            }
+
+            /// Creates a client that sends every call through `client`.
+            pub fn with_client(client: ::reqwest::Client) -> Self {
+                DefaultClient {
+                    inner: #crate_root::SharedClient::new(client, #base_url, #content_type),
+                }
+            }
+        }
+
+        impl ::std::default::Default for DefaultClient {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        #[#crate_root::async_trait::async_trait]
+        impl #trait_ident for DefaultClient {
+            #(#methods)*
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// # `jsonrpc_service_fn_array`
+///
+/// **Server-side attribute macro for positional parameters.**
+///
+/// Use this on an `async fn` to register it as a JSON-RPC service. Incoming
+/// `params` are expected to be a JSON Array.
+///
+/// ### Features
+/// 1. Generates a boilerplate `RequestArray` struct for deserialization.
+/// 2. Implements `JsonRpcServiceFn` for the generated struct.
+/// 3. Automatically registers the function into the global `RPC_SERVICES` slice
+///    using `linkme` for zero-cost discovery.
+///
+/// ### Description
+/// `desc = "..."` sets the description reported by `rpc.describe`. Without it, the
+/// function's own `///` doc comment is used. The same key is accepted by
+/// `jsonrpc_service_fn_obj` and `jsonrpc_service_fn`.
+///
+/// ### Unit Testing
+/// Under `#[cfg(test)]`, a `test_{fn_name}(params)` function is generated that takes
+/// the parameters as one tuple and calls the function directly, without building a
+/// JSON-RPC request:
+///
+/// ```rust
+/// # use a_rs_jsonrpc::{RpcError, jsonrpc_service_fn_array};
+/// #[jsonrpc_service_fn_array(method = "add", version = "v2")]
+/// async fn add(a: i32, b: i32) -> Result<i32, RpcError> { Ok(a + b) }
+///
+/// #[cfg(test)]
+/// mod tests {
+///     #[tokio::test]
+///     async fn adds() -> Result<(), a_rs_jsonrpc::RpcError> {
+///         assert_eq!(super::test_add((1, 2)).await?, 3);
+///         Ok(())
+///     }
+/// }
+/// ```
+///
+/// An `{FnName}ArrayRequestBuilder` is also generated, with a `param_{name}` setter
+/// per parameter, to build requests for integration tests:
+///
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{RpcError, dispatch_rpc_request, jsonrpc_service_fn_array};
+/// # #[jsonrpc_service_fn_array(method = "add", version = "v2")]
+/// # async fn add(a: i32, b: i32) -> Result<i32, RpcError> { Ok(a + b) }
+/// # async fn example() -> Result<(), RpcError> {
+/// let req = AddArrayRequestBuilder::new().param_a(10).param_b(20).build_v2(1);
+/// let resp = dispatch_rpc_request(&serde_json::to_vec(&req)?).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Schema
+/// `schema = true` records a JSON Schema of the parameters, served by
+/// `rpc.getSchema`. It requires the library's `schema` feature, and every parameter
+/// type must implement `schemars::JsonSchema`.
+///
+/// ### Streaming Results
+/// A function returning `Result<impl Stream<Item = T>, RpcError>` is answered with
+/// the collected items, as a `Vec<T>` result. With `streaming = true`, the method is
+/// also served by `dispatch_streaming`, which sends one response per item as
+/// newline-delimited JSON:
+///
+/// ```rust
+/// # use a_rs_jsonrpc::futures::{self, Stream};
+/// # use a_rs_jsonrpc::{RpcError, jsonrpc_service_fn_array};
+/// #[jsonrpc_service_fn_array(method = "tail", version = "v2", streaming = true)]
+/// async fn tail(lines: usize) -> Result<impl Stream<Item = String>, RpcError> {
+///     Ok(futures::stream::iter((0..lines).map(|i| format!("line {}", i))))
+/// }
+/// ```
+///
+/// The stream must be `Send + 'static`, so with `has_context = true` it must not
+/// borrow the context; on edition 2024, declare it as `impl Stream<Item = T> + use<>`.
+///
+/// ### Fallback
+/// `fallback = true`, given instead of `method`, registers the function as the
+/// catch-all handler of requests whose method is not registered. At most one
+/// fallback may be registered; a second one is a compile error in the same module
+/// and a panic when the service starts otherwise. Declare it with
+/// `has_context = true` to read the requested method from the context:
+///
+/// ```rust
+/// # use a_rs_jsonrpc::{RpcContext, RpcError, jsonrpc_service_fn_array};
+/// #[jsonrpc_service_fn_array(fallback = true, version = "v2", has_context = true)]
+/// async fn unknown(ctx: &RpcContext) -> Result<String, RpcError> {
+///     Ok(format!("{} is not implemented yet", ctx.method))
+/// }
+/// ```
+///
+/// ### Other Attributes
+/// Attributes placed below this one, such as `#[tracing::instrument]`, are kept on
+/// the function and expanded after it, so they see the function as written, minus
+/// the parameter attributes this macro consumes. Put this attribute first:
+///
+/// ```rust
+/// # use a_rs_jsonrpc::{RpcError, jsonrpc_service_fn_array};
+/// #[jsonrpc_service_fn_array(method = "add", version = "v2")]
+/// #[tracing::instrument(level = "debug")]
+/// async fn add(a: i32, b: i32) -> Result<i32, RpcError> { Ok(a + b) }
+/// ```
+///
+/// An attribute placed above it is expanded first, and this macro then sees its
+/// output; `#[tracing::instrument]` keeps the signature and works in either
+/// position, but other function-rewriting attributes may not. The same applies to
+/// `jsonrpc_service_fn_obj`, `jsonrpc_service_fn` and `jsonrpc_service_fn_both`.
+#[proc_macro_attribute]
+pub fn jsonrpc_service_fn_array(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_fn = parse_macro_input!(item as ItemFn);
+    let args = parse_macro_input!(attr as RpcAttr);
+    TokenStream::from(expand_service_fn_array(args, input_fn, false, true))
+}
+
+/// Expands an array-mode service.
+///
+/// When `spread_tuple` is set, the function takes a single tuple-typed parameter
+/// and the positional `params` array is deserialized directly into that tuple.
+/// Without `register`, only the request struct and its `JsonRpcServiceFn` impl are
+/// generated, leaving the function itself and its registration to the caller.
+fn expand_service_fn_array(
+    args: RpcAttr,
+    input_fn: ItemFn,
+    spread_tuple: bool,
+    register: bool,
+) -> proc_macro2::TokenStream {
+    let fn_name = &input_fn.sig.ident;
+    let name_str = fn_name.to_string();
+    let struct_name_ident = format_ident!("{}RequestArray", name_str.to_case(Case::Pascal));
+
+    let context = match ContextTokens::new(&args, &input_fn) {
+        Ok(v) => v,
+        Err(e) => return e.to_compile_error(),
+    };
+    let ContextTokens {
+        skip,
+        user_data,
+        build_ctx,
+        ctx_arg,
+    } = context;
+
+    let param_types: Vec<&Type> = input_fn
+        .sig
+        .inputs
+        .iter()
+        .skip(skip)
+        .filter_map(|arg| {
+            if let FnArg::Typed(pat_type) = arg {
+                Some(&*pat_type.ty)
+            } else {
+                None
+            }
+        })
+        .collect();
+    if args.rename.is_some() && !param_types.is_empty() {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "rename only applies to object-mode services",
+        )
+        .to_compile_error();
+    }
+
+    let result_inner_type = extract_result_t(&input_fn.sig.output);
+    let stream_item = stream_item_type(&input_fn.sig.output);
+    if args.streaming && stream_item.is_none() {
+        return syn::Error::new_spanned(
+            &input_fn.sig.output,
+            "streaming = true requires a `Result<impl Stream<Item = T>, RpcError>` return type",
+        )
+        .to_compile_error();
+    }
+    let (handler_result_type, collect_stream) = match stream_item {
+        Some(item) => (
+            quote! { Vec<#item> },
+            quote! {
+                let result = ::a_rs_jsonrpc::futures::StreamExt::collect::<Vec<_>>(result).await;
+            },
+        ),
+        None => (result_inner_type.clone(), quote! {}),
+    };
+    let version_val = args.version.clone();
+    let description = service_description(&args, &input_fn);
+    let method_val = args.method.clone();
+    let registration_ident = format_ident!("REG_{}", fn_name.to_string().to_uppercase());
+    let method_marker = method_marker(&method_val);
+
+    let rpc = quote! { ::a_rs_jsonrpc };
+
+    let has_params = !param_types.is_empty();
+
+    let (tuple_params, call_logic) = if spread_tuple {
+        let ty = param_types[0];
+        (
+            quote! { #ty },
+            quote! {
+                let params = request.params.ok_or_else(|| {
+                    #rpc::RpcError::InvalidParams(format!("Method '{}' requires array parameters", #method_val).into())
                 })?;
-                let result = #fn_name(#(#param_indices),*).await?;
+                let result = #fn_name(#ctx_arg params).await?;
+            },
+        )
+    } else if has_params {
+        let ty = if param_types.len() == 1 {
+            let t = param_types[0];
+            quote! { (#t,) }
+        } else {
+            quote! { (#(#param_types),*) }
+        };
+
+        let param_indices: Vec<_> = (0..param_types.len())
+            .map(|i| {
+                let index = syn::Index::from(i);
+                quote! { params.#index }
+            })
+            .collect();
+
+        (
+            quote! { #ty },
+            quote! {
+                let params = request.params.ok_or_else(|| {
+                    #rpc::RpcError::InvalidParams(format!("Method '{}' requires array parameters", #method_val).into())
+                })?;
+                let result = #fn_name(#ctx_arg #(#param_indices),*).await?;
             },
         )
     } else {
         (
             quote! { #rpc::serde_json::Value },
             quote! {
-                let result = #fn_name().await?;
+                let result = #fn_name(#ctx_arg).await?;
             },
         )
     };
 
-    let expanded = quote! {
+    let schema = service_schema(&args, &tuple_params);
+
+    let test_helper_ident = format_ident!("test_{}", fn_name);
+    let test_helper_doc = format!(
+        "Calls `{}` directly with its positional parameters, bypassing the JSON-RPC envelope.",
+        fn_name
+    );
+    let (ctx_param, ctx_pass) = if args.has_context {
+        (quote! { ctx: &#rpc::RpcContext, }, quote! { ctx, })
+    } else {
+        (quote! {}, quote! {})
+    };
+    let (params_param, call_args) = if spread_tuple {
+        (quote! { params: #tuple_params }, quote! { params })
+    } else if has_params {
+        let indices = (0..param_types.len()).map(syn::Index::from);
+        (
+            quote! { params: #tuple_params },
+            quote! { #(params.#indices),* },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+    let vis = &input_fn.vis;
+
+    let check_version = quote! {
+        #rpc::service::check_version(&request.jsonrpc, #version_val)?;
+    };
+    let stream_handler = if args.streaming {
+        quote! {
+            ::std::option::Option::Some(|req_bytes, #user_data| {
+                Box::pin(async move {
+                    let request: #struct_name_ident = #rpc::service::deserialize_request(&req_bytes)?;
+                    #check_version
+                    #build_ctx
+                    #call_logic
+                    Ok(#rpc::service::stream_responses(result, request.jsonrpc.parse()?, request.id))
+                })
+            })
+        }
+    } else {
+        quote! { ::std::option::Option::None }
+    };
+
+    let registration = if register {
+        quote! {
+            #method_marker
+
+            // The registration static lives in an anonymous const block so its name
+            // cannot clash with items in the user's module. `distributed_slice` already
+            // marks it `#[used]` and places it in the slice's link section, which keeps
+            // it through LTO; an extra `#[used]` would be a duplicate attribute and
+            // `#[must_use]` has no effect on statics.
+            const _: () = {
+                #[#rpc::linkme::distributed_slice(#rpc::RPC_SERVICES)]
+                static #registration_ident: #rpc::RpcServiceEntry = #rpc::RpcServiceEntry {
+                    method: #method_val,
+                    description: #description,
+                    param_mode: #rpc::ParamMode::Array,
+                    schema: #schema,
+                    handler: |req_bytes, user_data| {
+                        Box::pin(async move {
+                            use #rpc::JsonRpcServiceFn;
+                            let response = #struct_name_ident::handle_with_context(&req_bytes, user_data).await?;
+                            Ok(#rpc::serde_json::to_string(&response)?)
+                        })
+                    },
+                    stream_handler: #stream_handler,
+                };
+            };
+        }
+    } else {
+        quote! {}
+    };
+    let request_builder = if spread_tuple {
+        quote! {}
+    } else {
+        array_request_builder(&input_fn, skip, &method_val)
+    };
+    let input_fn = if register {
+        quote! { #input_fn }
+    } else {
+        quote! {}
+    };
+
+    quote! {
         #input_fn
 
+        #[cfg(test)]
+        #[doc = #test_helper_doc]
+        #[allow(dead_code, clippy::needless_question_mark)]
+        #vis async fn #test_helper_ident(
+            #ctx_param #params_param
+        ) -> ::std::result::Result<#result_inner_type, #rpc::RpcError> {
+            Ok(#fn_name(#ctx_pass #call_args).await?)
+        }
+
+        #request_builder
+
         #[derive(Debug, #rpc::serde::Deserialize)]
         pub struct #struct_name_ident {
             pub jsonrpc: String,
@@ -531,22 +1502,25 @@ pub fn jsonrpc_service_fn_array(attr: TokenStream, item: TokenStream) -> TokenSt
 
         #[#rpc::async_trait::async_trait]
         impl #rpc::JsonRpcServiceFn for #struct_name_ident {
-            type Result = #result_inner_type;
+            type Result = #handler_result_type;
 
             async fn handle(
                 req: &[u8],
             ) -> Result<#rpc::JsonRpcResponse<Self::Result>, #rpc::RpcError> {
-                let request: #struct_name_ident = #rpc::serde_json::from_slice(req)
-                    .map_err(|e| #rpc::RpcError::SerdeError(e))?;
-
-                if request.jsonrpc != #version_val {
-                    return Err(#rpc::RpcError::InvalidJsonRpcVersion(format!(
-                        "Expected JSON-RPC version {}, got {}",
-                        #version_val, request.jsonrpc
-                    )));
-                }
+                Self::handle_with_context(req, ::std::sync::Arc::new(())).await
+            }
+
+            async fn handle_with_context(
+                req: &[u8],
+                #user_data: #rpc::service::RpcUserData,
+            ) -> Result<#rpc::JsonRpcResponse<Self::Result>, #rpc::RpcError> {
+                let request: #struct_name_ident = #rpc::service::deserialize_request(req)?;
+
+                #check_version
 
+                #build_ctx
                 #call_logic
+                #collect_stream
 
                 let response = #rpc::JsonRpcResponse {
                     jsonrpc: request.jsonrpc.parse()?,
@@ -559,22 +1533,103 @@ pub fn jsonrpc_service_fn_array(attr: TokenStream, item: TokenStream) -> TokenSt
             }
         }
 
-        #[#rpc::linkme::distributed_slice(#rpc::RPC_SERVICES)]
-        #[doc(hidden)]
-        pub static #registration_ident: #rpc::RpcServiceEntry = #rpc::RpcServiceEntry {
-            method: #method_val,
-            handler: |req_bytes| {
-                let req_bytes = req_bytes.to_vec();
-                Box::pin(async move {
-                    use #rpc::JsonRpcServiceFn;
-                    let response = #struct_name_ident::handle(&req_bytes).await?;
-                    Ok(#rpc::serde_json::to_string(&response)?)
-                })
-            },
-        };
-    };
+        #registration
+    }
+}
 
-    TokenStream::from(expanded)
+/// Generates the `{Name}ArrayRequestBuilder` of an array-mode service, with one
+/// `param_{name}` setter per parameter after the first `skip` ones.
+fn array_request_builder(input_fn: &ItemFn, skip: usize, method: &str) -> proc_macro2::TokenStream {
+    let rpc = quote! { ::a_rs_jsonrpc };
+    let fn_name = &input_fn.sig.ident;
+    let builder_ident = format_ident!(
+        "{}ArrayRequestBuilder",
+        fn_name.to_string().to_case(Case::Pascal)
+    );
+    let vis = &input_fn.vis;
+    let builder_doc = format!(
+        "Builds positional requests to `{}`, e.g. to set up integration tests.",
+        method
+    );
+
+    let setters = input_fn
+        .sig
+        .inputs
+        .iter()
+        .skip(skip)
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pat_type) => Some(pat_type),
+            FnArg::Receiver(_) => None,
+        })
+        .enumerate()
+        .map(|(index, pat_type)| {
+            let name = match &*pat_type.pat {
+                Pat::Ident(pat_ident) => pat_ident.ident.to_string(),
+                _ => index.to_string(),
+            };
+            // The name is kept as-is, so `_a` and `a` get distinct setters.
+            let setter_ident = format_ident!("param_{}", name);
+            let setter_doc = format!("Sets the `{}` parameter, at position {}.", name, index);
+            quote! {
+                #[doc = #setter_doc]
+                pub fn #setter_ident<V: #rpc::serde::Serialize>(mut self, value: V) -> Self {
+                    self.params[#index] = #rpc::serde_json::to_value(value)
+                        .unwrap_or(#rpc::serde_json::Value::Null);
+                    self
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let len = setters.len();
+
+    quote! {
+        #[doc = #builder_doc]
+        ///
+        /// Parameters that are not set are sent as `null`.
+        #[derive(Debug, Clone)]
+        #[allow(dead_code)]
+        #vis struct #builder_ident {
+            params: ::std::vec::Vec<#rpc::serde_json::Value>,
+        }
+
+        impl ::std::default::Default for #builder_ident {
+            fn default() -> Self {
+                #builder_ident {
+                    params: ::std::vec![#rpc::serde_json::Value::Null; #len],
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl #builder_ident {
+            /// Creates a builder with every parameter unset.
+            pub fn new() -> Self {
+                ::std::default::Default::default()
+            }
+
+            #(#setters)*
+
+            /// Builds a JSON-RPC 1.0 request with `id`.
+            pub fn build_v1(
+                self,
+                id: impl ::std::convert::Into<#rpc::JsonRpcId>,
+            ) -> #rpc::request::JsonRpcRequest<::std::vec::Vec<#rpc::serde_json::Value>> {
+                let mut request = #rpc::request::JsonRpcRequest::new_v1(id.into(), #method);
+                request.set_params(self.params);
+                request
+            }
+
+            /// Builds a JSON-RPC 2.0 request with `id`.
+            pub fn build_v2(
+                self,
+                id: impl ::std::convert::Into<#rpc::JsonRpcId>,
+            ) -> #rpc::request::JsonRpcRequest<::std::vec::Vec<#rpc::serde_json::Value>> {
+                let mut request = #rpc::request::JsonRpcRequest::new_v2(id.into(), #method);
+                request.set_params(self.params);
+                request
+            }
+        }
+    }
 }
 
 fn extract_result_t(rt: &ReturnType) -> proc_macro2::TokenStream {
@@ -594,6 +1649,43 @@ fn extract_result_t(rt: &ReturnType) -> proc_macro2::TokenStream {
     panic!("Unable to extract Result<T> type from function return type");
 }
 
+/// Returns `T` if the function returns `Result<impl Stream<Item = T>, _>`.
+fn stream_item_type(rt: &ReturnType) -> Option<Type> {
+    let ReturnType::Type(_, ty) = rt else {
+        return None;
+    };
+    let Type::Path(tp) = ty.as_ref() else {
+        return None;
+    };
+    let seg = tp
+        .path
+        .segments
+        .last()
+        .filter(|seg| seg.ident == "Result")?;
+    let PathArguments::AngleBracketed(args) = &seg.arguments else {
+        return None;
+    };
+    let Some(GenericArgument::Type(Type::ImplTrait(impl_trait))) = args.args.first() else {
+        return None;
+    };
+    impl_trait.bounds.iter().find_map(|bound| {
+        let syn::TypeParamBound::Trait(trait_bound) = bound else {
+            return None;
+        };
+        let seg = trait_bound.path.segments.last()?;
+        if seg.ident != "Stream" {
+            return None;
+        }
+        let PathArguments::AngleBracketed(args) = &seg.arguments else {
+            return None;
+        };
+        args.args.iter().find_map(|arg| match arg {
+            GenericArgument::AssocType(assoc) if assoc.ident == "Item" => Some(assoc.ty.clone()),
+            _ => None,
+        })
+    })
+}
+
 /// # `jsonrpc_service_fn_obj`
 ///
 /// **Server-side attribute macro for named parameters.**
@@ -604,56 +1696,246 @@ fn extract_result_t(rt: &ReturnType) -> proc_macro2::TokenStream {
 /// ### Registration
 /// The function is registered as a `RpcServiceEntry` at compile-time, allowing
 /// the server dispatcher to find it by the `method` string.
+///
+/// ### Returning `serde_json::Value`
+/// A handler may return `Result<serde_json::Value, RpcError>`. The value is placed
+/// verbatim into the response `result` field, so an object that itself contains a
+/// `"result"` key is nested as `{"result": {"result": ...}}`. The envelope is never
+/// merged with the returned value; return the inner payload directly if the extra
+/// level is not wanted.
+///
+/// ### Parameter Names
+/// JSON keys default to the `camelCase` form of the argument names. `rename = "..."`
+/// picks another `rename_all` rule for the whole struct (`"snake_case"`,
+/// `"PascalCase"`, `"SCREAMING_SNAKE_CASE"`, ...), and `#[rpc_param(rename = "...")]`
+/// sets the key of a single parameter:
+///
+/// ```rust
+/// # use a_rs_jsonrpc::{RpcError, jsonrpc_service_fn_obj};
+/// // params: {"msg": "hi", "repeat_count": 2}
+/// #[jsonrpc_service_fn_obj(method = "say", version = "v2", rename = "snake_case")]
+/// async fn say(#[rpc_param(rename = "msg")] message: String, repeat_count: u32) -> Result<String, RpcError> {
+///     Ok(message.repeat(repeat_count as usize))
+/// }
+/// ```
+///
+/// ### Flattened Parameters
+/// A parameter annotated with `#[jsonrpc(flatten)]` gets `#[serde(flatten)]` in the
+/// generated params struct, so the fields of its type are read from the top level
+/// of `params` instead of a nested object:
+///
+/// ```rust
+/// # use a_rs_jsonrpc::{RpcError, jsonrpc_service_fn_obj};
+/// # use serde::Deserialize;
+/// #[derive(Debug, Deserialize)]
+/// struct Paging { offset: u32, limit: u32 }
+///
+/// // params: {"query": "rust", "offset": 0, "limit": 10}
+/// #[jsonrpc_service_fn_obj(method = "search", version = "v2")]
+/// async fn search(query: String, #[jsonrpc(flatten)] paging: Paging) -> Result<Vec<String>, RpcError> {
+///     todo!()
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn jsonrpc_service_fn_obj(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_fn = parse_macro_input!(item as ItemFn);
+    let args = parse_macro_input!(attr as RpcAttr);
+    TokenStream::from(expand_service_fn_obj(args, input_fn, true))
+}
+
+/// Strips `#[jsonrpc(flatten)]` from the function parameters, returning the
+/// indices of the parameters that carried it.
+fn take_flatten_attrs(input_fn: &mut ItemFn) -> Vec<usize> {
+    let mut flattened = Vec::new();
+    for (i, arg) in input_fn.sig.inputs.iter_mut().enumerate() {
+        let FnArg::Typed(pat_type) = arg else {
+            continue;
+        };
+        pat_type.attrs.retain(|attr| {
+            let is_flatten = attr.path().is_ident("jsonrpc")
+                && attr
+                    .parse_args::<syn::Ident>()
+                    .is_ok_and(|ident| ident == "flatten");
+            if is_flatten {
+                flattened.push(i);
+            }
+            !is_flatten
+        });
+    }
+    flattened
+}
+
+#[derive(Debug, Default, FromMeta)]
+struct RpcParamArgs {
+    #[darling(default)]
+    rename: Option<String>,
+}
 
+/// Strips `#[rpc_param(...)]` from the function parameters, returning the options
+/// of each parameter in order.
+fn take_rpc_param_attrs(input_fn: &mut ItemFn) -> Result<Vec<RpcParamArgs>, darling::Error> {
+    let mut params = Vec::new();
+    let mut errors = Vec::new();
+    for arg in input_fn.sig.inputs.iter_mut() {
+        let mut opts = RpcParamArgs::default();
+        if let FnArg::Typed(pat_type) = arg {
+            pat_type.attrs.retain(|attr| {
+                if !attr.path().is_ident("rpc_param") {
+                    return true;
+                }
+                match RpcParamArgs::from_meta(&attr.meta) {
+                    Ok(v) => opts = v,
+                    Err(e) => errors.push(e),
+                }
+                false
+            });
+        }
+        params.push(opts);
+    }
+    if !errors.is_empty() {
+        return Err(darling::Error::multiple(errors));
+    }
+    Ok(params)
+}
+
+/// Expands an object-mode service.
+///
+/// Without `register`, only the params and request structs and the
+/// `JsonRpcServiceFn` impl are generated, like [`expand_service_fn_array`].
+fn expand_service_fn_obj(
+    args: RpcAttr,
+    mut input_fn: ItemFn,
+    register: bool,
+) -> proc_macro2::TokenStream {
+    let flattened = take_flatten_attrs(&mut input_fn);
+    let param_opts = match take_rpc_param_attrs(&mut input_fn) {
+        Ok(v) => v,
+        Err(e) => return e.write_errors(),
+    };
+    if args.streaming || stream_item_type(&input_fn.sig.output).is_some() {
+        return syn::Error::new_spanned(
+            &input_fn.sig.output,
+            "returning a stream is only supported by array-mode services",
+        )
+        .to_compile_error();
+    }
     let fn_name = input_fn.sig.ident.clone();
     let name_pascal = fn_name.to_string().to_case(Case::Pascal);
 
     let params_struct_ident = format_ident!("{}ObjParams", name_pascal);
     let request_struct_ident = format_ident!("{}ObjRequest", name_pascal);
 
+    let context = match ContextTokens::new(&args, &input_fn) {
+        Ok(v) => v,
+        Err(e) => return e.to_compile_error(),
+    };
+    let ContextTokens {
+        skip,
+        user_data,
+        build_ctx,
+        ctx_arg,
+    } = context;
+
     let mut param_names = Vec::new();
     let mut param_types = Vec::new();
+    let mut param_attrs = Vec::new();
 
-    for arg in &input_fn.sig.inputs {
+    for (i, arg) in input_fn.sig.inputs.iter().enumerate().skip(skip) {
         if let FnArg::Typed(pat_type) = arg {
             if let Pat::Ident(pat_ident) = &*pat_type.pat {
                 param_names.push(&pat_ident.ident);
                 param_types.push(&*pat_type.ty);
+                let flatten = if flattened.contains(&i) {
+                    quote! { #[serde(flatten)] }
+                } else {
+                    quote! {}
+                };
+                let rename = match param_opts[i].rename {
+                    Some(ref name) => quote! { #[serde(rename = #name)] },
+                    None => quote! {},
+                };
+                param_attrs.push(quote! { #flatten #rename });
             }
         }
     }
 
     let result_inner_type = extract_result_t(&input_fn.sig.output);
-    let args = parse_macro_input!(attr as RpcAttr);
-    let version_val = args.version;
-    let method_val = args.method;
+    let version_val = args.version.clone();
+    let description = service_description(&args, &input_fn);
+    let method_val = args.method.clone();
 
     let registration_ident = format_ident!("REG_{}", fn_name.to_string().to_uppercase());
+    let method_marker = method_marker(&method_val);
 
     let rpc = quote! { ::a_rs_jsonrpc };
     let call_logic = if param_names.is_empty() {
         quote! {
-            let result = #fn_name().await?;
+            let result = #fn_name(#ctx_arg).await?;
         }
     } else {
         quote! {
             let params = request.params.ok_or_else(|| {
-                #rpc::RpcError::InvalidParams(format!("Method '{}' requires parameters", #method_val))
+                #rpc::RpcError::InvalidParams(format!("Method '{}' requires parameters", #method_val).into())
             })?;
-            let result = #fn_name( #(params.#param_names),* ).await?;
+            let result = #fn_name( #ctx_arg #(params.#param_names),* ).await?;
         }
     };
 
-    let expanded = quote! {
+    let schema = service_schema(&args, &quote! { #params_struct_ident });
+    let rename_all = args.rename.as_deref().unwrap_or("camelCase");
+    let schema_derive = if args.schema {
+        quote! {
+            #[derive(#rpc::schemars::JsonSchema)]
+            #[schemars(crate = "::a_rs_jsonrpc::schemars")]
+        }
+    } else {
+        quote! {}
+    };
+
+    let registration = if register {
+        quote! {
+            #method_marker
+
+            // The registration static lives in an anonymous const block so its name
+            // cannot clash with items in the user's module. `distributed_slice` already
+            // marks it `#[used]` and places it in the slice's link section, which keeps
+            // it through LTO; an extra `#[used]` would be a duplicate attribute and
+            // `#[must_use]` has no effect on statics.
+            const _: () = {
+                #[#rpc::linkme::distributed_slice(#rpc::RPC_SERVICES)]
+                static #registration_ident: #rpc::RpcServiceEntry = #rpc::RpcServiceEntry {
+                    method: #method_val,
+                    description: #description,
+                    param_mode: #rpc::ParamMode::Object,
+                    schema: #schema,
+                    handler: |req_data, user_data| {
+                        Box::pin(async move {
+                            use #rpc::JsonRpcServiceFn;
+                            let response = #request_struct_ident::handle_with_context(&req_data, user_data).await?;
+                            Ok(#rpc::serde_json::to_string(&response)?)
+                        })
+                    },
+                    stream_handler: ::std::option::Option::None,
+                };
+            };
+        }
+    } else {
+        quote! {}
+    };
+    let input_fn = if register {
+        quote! { #input_fn }
+    } else {
+        quote! {}
+    };
+
+    quote! {
         #input_fn
 
         #[derive(Debug, #rpc::serde::Deserialize)]
-        #[serde(rename_all = "camelCase")]
+        #schema_derive
+        #[serde(rename_all = #rename_all)]
         pub struct #params_struct_ident {
-            #(pub #param_names: #param_types),*
+            #(#param_attrs pub #param_names: #param_types),*
         }
 
         #[derive(Debug, #rpc::serde::Deserialize)]
@@ -673,17 +1955,23 @@ pub fn jsonrpc_service_fn_obj(attr: TokenStream, item: TokenStream) -> TokenStre
                 req: &[u8],
             ) -> Result<#rpc::JsonRpcResponse<Self::Result>, #rpc::RpcError>
             {
-                let request: #request_struct_ident = #rpc::serde_json::from_slice(req)?;
+                Self::handle_with_context(req, ::std::sync::Arc::new(())).await
+            }
 
-                if request.jsonrpc != #version_val {
-                    return Err(#rpc::RpcError::InvalidJsonRpcVersion(format!(
-                        "Expected JSON-RPC version {}, got {}",
-                        #version_val, request.jsonrpc
-                    )));
-                }
+            async fn handle_with_context(
+                req: &[u8],
+                #user_data: #rpc::service::RpcUserData,
+            ) -> Result<#rpc::JsonRpcResponse<Self::Result>, #rpc::RpcError>
+            {
+                let request: #request_struct_ident = #rpc::service::deserialize_request(req)?;
+
+                #rpc::service::check_version(&request.jsonrpc, #version_val)?;
 
+                #build_ctx
                 #call_logic
 
+                // `result` is stored as-is, even when it is a `serde_json::Value`
+                // object with its own `"result"` key; the envelope never unwraps it.
                 Ok(#rpc::JsonRpcResponse {
                     jsonrpc: request.jsonrpc.parse().map_err(|_| #rpc::RpcError::InvalidJsonRpcVersion(request.jsonrpc))?,
                     result: Some(result),
@@ -693,47 +1981,335 @@ pub fn jsonrpc_service_fn_obj(attr: TokenStream, item: TokenStream) -> TokenStre
             }
         }
 
-        #[#rpc::linkme::distributed_slice(#rpc::RPC_SERVICES)]
-        #[doc(hidden)]
-        pub static #registration_ident: #rpc::RpcServiceEntry = #rpc::RpcServiceEntry {
-            method: #method_val,
-            handler: |req_bytes| {
-                let req_data = req_bytes.to_vec();
-                Box::pin(async move {
-                    use #rpc::JsonRpcServiceFn;
-                    let response = #request_struct_ident::handle(&req_data).await?;
-                    Ok(#rpc::serde_json::to_string(&response)?)
-                })
-            },
-        };
-    };
+        #registration
+    }
+}
 
+/// # `jsonrpc_service_fn`
+///
+/// **Server-side attribute macro that selects the parameter mode automatically.**
+///
+/// Accepts the same `method` and `version` keys as the explicit macros and inspects
+/// the function signature:
+/// - No parameters: a handler that ignores `params`.
+/// - Only plainly named parameters (`fn add(lhs: i32, rhs: i32)`): object mode, as
+///   with `jsonrpc_service_fn_obj`.
+/// - Exactly one pattern parameter (`fn add((a, b): (i32, i32))` or `fn f(_: T)`):
+///   array mode. A tuple-typed parameter receives the whole positional `params` array.
+///
+/// Any other combination is rejected with a compile error; use
+/// `jsonrpc_service_fn_array` or `jsonrpc_service_fn_obj` explicitly in that case.
+/// With `has_context = true`, the leading `&RpcContext` parameter is not considered.
+#[proc_macro_attribute]
+pub fn jsonrpc_service_fn(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_fn = parse_macro_input!(item as ItemFn);
+    let args = parse_macro_input!(attr as RpcAttr);
+
+    let typed: Vec<&syn::PatType> = input_fn
+        .sig
+        .inputs
+        .iter()
+        .skip(usize::from(args.has_context))
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pat_type) => Some(pat_type),
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    let named = typed
+        .iter()
+        .filter(|pat_type| matches!(*pat_type.pat, Pat::Ident(_)))
+        .count();
+
+    let expanded = if typed.is_empty() {
+        expand_service_fn_array(args, input_fn, false, true)
+    } else if named == typed.len() {
+        expand_service_fn_obj(args, input_fn, true)
+    } else if typed.len() == 1 {
+        let spread_tuple = matches!(*typed[0].ty, Type::Tuple(_));
+        expand_service_fn_array(args, input_fn, spread_tuple, true)
+    } else {
+        syn::Error::new_spanned(
+            &input_fn.sig.inputs,
+            "cannot infer the parameter mode: use only named parameters for object mode \
+             or a single tuple parameter for array mode, or choose explicitly with \
+             #[jsonrpc_service_fn_array] / #[jsonrpc_service_fn_obj]",
+        )
+        .to_compile_error()
+    };
     TokenStream::from(expanded)
 }
 
+/// # `jsonrpc_service_fn_both`
+///
+/// **Server-side attribute macro accepting positional and named parameters.**
+///
+/// Generates both the array-mode and the object-mode decoding of the function and
+/// registers a single method that picks one per request: `params` given as a JSON
+/// object are decoded like `jsonrpc_service_fn_obj`, anything else like
+/// `jsonrpc_service_fn_array`. The method is reported with the `Both` parameter mode.
+///
+/// It accepts the keys of `jsonrpc_service_fn_obj`, and parameters may carry
+/// `#[rpc_param(rename = "...")]` and `#[jsonrpc(flatten)]`, which only affect
+/// the named form. Stream results are not supported.
+///
+/// ```rust
+/// # use a_rs_jsonrpc::{RpcError, jsonrpc_service_fn_both};
+/// // params: [1, 2] or {"lhs": 1, "rhs": 2}
+/// #[jsonrpc_service_fn_both(method = "add", version = "v2")]
+/// async fn add(lhs: i32, rhs: i32) -> Result<i32, RpcError> {
+///     Ok(lhs + rhs)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn jsonrpc_service_fn_both(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input_fn = parse_macro_input!(item as ItemFn);
+    let args = parse_macro_input!(attr as RpcAttr);
+    TokenStream::from(expand_service_fn_both(args, &mut input_fn))
+}
+
+/// Expands a service registered for both parameter modes.
+fn expand_service_fn_both(args: RpcAttr, input_fn: &mut ItemFn) -> proc_macro2::TokenStream {
+    if args.streaming || stream_item_type(&input_fn.sig.output).is_some() {
+        return syn::Error::new_spanned(
+            &input_fn.sig.output,
+            "returning a stream is only supported by array-mode services",
+        )
+        .to_compile_error();
+    }
+    let obj = expand_service_fn_obj(args.clone(), input_fn.clone(), false);
+    take_flatten_attrs(input_fn);
+    if let Err(e) = take_rpc_param_attrs(input_fn) {
+        return e.write_errors();
+    }
+    let array_args = RpcAttr {
+        rename: None,
+        ..args.clone()
+    };
+    let array = expand_service_fn_array(array_args, input_fn.clone(), false, false);
+
+    let fn_name = &input_fn.sig.ident;
+    let name_pascal = fn_name.to_string().to_case(Case::Pascal);
+    let array_struct_ident = format_ident!("{}RequestArray", name_pascal);
+    let params_struct_ident = format_ident!("{}ObjParams", name_pascal);
+    let obj_struct_ident = format_ident!("{}ObjRequest", name_pascal);
+    let registration_ident = format_ident!("REG_{}", fn_name.to_string().to_uppercase());
+    let method_val = &args.method;
+    let method_marker = method_marker(method_val);
+    let description = service_description(&args, input_fn);
+    let schema = service_schema(&args, &quote! { #params_struct_ident });
+    let rpc = quote! { ::a_rs_jsonrpc };
+
+    quote! {
+        #input_fn
+
+        #array
+
+        #obj
+
+        #method_marker
+
+        // See `expand_service_fn_array` for why the static sits in a const block.
+        const _: () = {
+            #[#rpc::linkme::distributed_slice(#rpc::RPC_SERVICES)]
+            static #registration_ident: #rpc::RpcServiceEntry = #rpc::RpcServiceEntry {
+                method: #method_val,
+                description: #description,
+                param_mode: #rpc::ParamMode::Both,
+                schema: #schema,
+                handler: |req_bytes, user_data| {
+                    Box::pin(async move {
+                        use #rpc::JsonRpcServiceFn;
+                        if #rpc::service::has_named_params(&req_bytes) {
+                            let response = #obj_struct_ident::handle_with_context(&req_bytes, user_data).await?;
+                            Ok(#rpc::serde_json::to_string(&response)?)
+                        } else {
+                            let response = #array_struct_ident::handle_with_context(&req_bytes, user_data).await?;
+                            Ok(#rpc::serde_json::to_string(&response)?)
+                        }
+                    })
+                },
+                stream_handler: ::std::option::Option::None,
+            };
+        };
+    }
+}
+
 use syn::{
     parse::{Parse, ParseStream},
-    LitStr, Token,
+    LitBool, LitStr, Token,
 };
 
+/// The method a `fallback = true` service is registered under, matching
+/// `a_rs_jsonrpc::service::FALLBACK_METHOD`.
+const FALLBACK_METHOD: &str = "rpc.fallback";
+
+/// The `rename_all` rules serde accepts, which the `rename` key forwards.
+const RENAME_RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+#[derive(Clone)]
 struct RpcAttr {
     version: String,
     method: String,
+    has_context: bool,
+    desc: Option<String>,
+    schema: bool,
+    rename: Option<String>,
+    streaming: bool,
+}
+
+/// The `description` of a registration entry: the `desc` key if set, otherwise
+/// the function's own doc comment.
+fn service_description(args: &RpcAttr, input_fn: &ItemFn) -> proc_macro2::TokenStream {
+    let desc = args.desc.clone().or_else(|| {
+        let lines: Vec<String> = input_fn
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .filter_map(|attr| match &attr.meta {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    value:
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(doc),
+                            ..
+                        }),
+                    ..
+                }) => Some(doc.value().trim().to_string()),
+                _ => None,
+            })
+            .collect();
+        let doc = lines.join("\n").trim().to_string();
+        (!doc.is_empty()).then_some(doc)
+    });
+    match desc {
+        Some(desc) => quote! { ::std::option::Option::Some(#desc) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+/// A module-level marker constant named after `method`.
+///
+/// Two services registering the same method in one module define the same
+/// constant, so the duplicate is a compile error instead of a panic when the
+/// routing table is built. Characters that cannot appear in an identifier are
+/// hex-escaped so distinct method names never share a marker.
+fn method_marker(method: &str) -> proc_macro2::TokenStream {
+    let encoded: String = method
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_string()
+            } else {
+                format!("_{:x}_", c as u32)
+            }
+        })
+        .collect();
+    let marker = format_ident!("__JSONRPC_METHOD_{}", encoded);
+    quote! {
+        #[doc(hidden)]
+        #[allow(non_upper_case_globals, dead_code)]
+        const #marker: () = ();
+    }
+}
+
+/// The `schema` of a registration entry: a generator of the JSON Schema of
+/// `params_ty` if `schema = true` is set, otherwise `None`.
+fn service_schema(
+    args: &RpcAttr,
+    params_ty: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if !args.schema {
+        return quote! { ::std::option::Option::None };
+    }
+    quote! {
+        ::std::option::Option::Some(|| ::a_rs_jsonrpc::schemars::schema_for!(#params_ty))
+    }
+}
+
+/// Tokens threading the optional `&RpcContext` argument through a service handler.
+struct ContextTokens {
+    /// The number of leading function arguments that are not JSON-RPC parameters.
+    skip: usize,
+    /// The name of the user data argument of `handle_with_context`.
+    user_data: syn::Ident,
+    /// Statements building `ctx` from the parsed request.
+    build_ctx: proc_macro2::TokenStream,
+    /// The leading argument passed to the user function.
+    ctx_arg: proc_macro2::TokenStream,
+}
+
+impl ContextTokens {
+    fn new(args: &RpcAttr, input_fn: &ItemFn) -> syn::Result<Self> {
+        if !args.has_context {
+            return Ok(ContextTokens {
+                skip: 0,
+                user_data: format_ident!("_user_data"),
+                build_ctx: quote! {},
+                ctx_arg: quote! {},
+            });
+        }
+        if !matches!(input_fn.sig.inputs.first(), Some(FnArg::Typed(_))) {
+            return Err(syn::Error::new_spanned(
+                &input_fn.sig,
+                "has_context = true requires a leading `&RpcContext` parameter",
+            ));
+        }
+        Ok(ContextTokens {
+            skip: 1,
+            user_data: format_ident!("user_data"),
+            build_ctx: quote! {
+                let ctx = ::a_rs_jsonrpc::RpcContext {
+                    id: request.id.clone(),
+                    method: request.method.clone(),
+                    user_data,
+                };
+            },
+            ctx_arg: quote! { &ctx, },
+        })
+    }
 }
 
 impl Parse for RpcAttr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut version = String::new();
         let mut method = String::new();
+        let mut has_context = false;
+        let mut desc = None;
+        let mut schema = false;
+        let mut rename = None;
+        let mut streaming = false;
+        let mut fallback = false;
 
         while !input.is_empty() {
             let ident: syn::Ident = input.parse()?;
             input.parse::<Token![=]>()?;
-            let value: LitStr = input.parse()?;
 
             match ident.to_string().as_str() {
-                "version" => version = value.value(),
-                "method" => method = value.value(),
+                "version" => version = input.parse::<LitStr>()?.value(),
+                "method" => method = input.parse::<LitStr>()?.value(),
+                "has_context" => has_context = input.parse::<LitBool>()?.value,
+                "desc" => desc = Some(input.parse::<LitStr>()?.value()),
+                "schema" => schema = input.parse::<LitBool>()?.value,
+                "streaming" => streaming = input.parse::<LitBool>()?.value,
+                "fallback" => fallback = input.parse::<LitBool>()?.value,
+                "rename" => {
+                    let lit = input.parse::<LitStr>()?;
+                    if !RENAME_RULES.contains(&lit.value().as_str()) {
+                        return Err(syn::Error::new(
+                            lit.span(),
+                            format!("rename must be one of {}", RENAME_RULES.join(", ")),
+                        ));
+                    }
+                    rename = Some(lit.value());
+                }
                 _ => return Err(syn::Error::new(ident.span(), "Unknown attribute")),
             }
 
@@ -751,12 +2327,29 @@ impl Parse for RpcAttr {
                 ))
             }
         }
+        if fallback {
+            if !method.is_empty() {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "a fallback handler takes no method",
+                ));
+            }
+            method = FALLBACK_METHOD.to_string();
+        }
         if method.is_empty() {
             return Err(syn::Error::new(
                 proc_macro2::Span::call_site(),
                 "method cannot be empty",
             ));
         }
-        Ok(RpcAttr { version, method })
+        Ok(RpcAttr {
+            version,
+            method,
+            has_context,
+            desc,
+            schema,
+            rename,
+            streaming,
+        })
     }
 }
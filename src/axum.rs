@@ -0,0 +1,134 @@
+//! # Axum Integration
+//!
+//! This module provides [`JsonRpcBody`], an extractor dispatching the request body to
+//! the registered RPC methods, so an Axum router needs no glue code of its own.
+//! Requires the `axum` feature.
+
+use crate::{
+    RpcError,
+    service::{
+        RpcUserData, accepts_content_type, dispatch_batch, dispatch_bytes_with_context,
+        error_response, status_code_of,
+    },
+};
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, Request},
+    handler::Handler,
+    http::{StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response},
+};
+use futures::future::BoxFuture;
+use std::{any::Any, sync::Arc};
+
+/// The JSON-RPC response to the request body, produced by dispatching it.
+///
+/// Extracting a `JsonRpcBody` reads the body, checks that its `Content-Type` is JSON
+/// and dispatches it like [`crate::dispatch_rpc_batch_request`], so both single
+/// requests and batches are served. A missing `Content-Type` is accepted.
+///
+/// The extraction is rejected with a JSON-RPC error response when the dispatch
+/// fails, using the status chosen by [`status_code_of()`], and with
+/// `415 Unsupported Media Type` when the `Content-Type` is not JSON.
+///
+/// ### Example
+/// ```rust
+/// # use a_rs_jsonrpc::JsonRpcBody;
+/// let app: axum::Router = axum::Router::new().route("/", axum::routing::post(JsonRpcBody::handler));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonRpcBody(pub String);
+
+impl JsonRpcBody {
+    /// A handler answering each request with its JSON-RPC response.
+    pub async fn handler(body: JsonRpcBody) -> Response {
+        body.into_response()
+    }
+
+    /// Returns a handler like [`JsonRpcBody::handler`] that hands `state` to handlers
+    /// declared with `has_context = true`.
+    ///
+    /// The request is dispatched like [`crate::dispatch_rpc_bytes_with_context`], so
+    /// batches are not supported.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use a_rs_jsonrpc::JsonRpcBody;
+    /// # use std::sync::Arc;
+    /// # struct AppState;
+    /// # let app_state = AppState;
+    /// let app: axum::Router = axum::Router::new().route(
+    ///     "/",
+    ///     axum::routing::post(JsonRpcBody::handler_with_state(Arc::new(app_state))),
+    /// );
+    /// ```
+    pub fn handler_with_state<S, R>(state: Arc<S>) -> impl Handler<(), R>
+    where
+        S: Any + Send + Sync,
+        R: Send + 'static,
+    {
+        StatefulHandler(state)
+    }
+}
+
+impl<S: Send + Sync> FromRequest<S> for JsonRpcBody {
+    type Rejection = Response;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        dispatch_request(req, None).await
+    }
+}
+
+impl IntoResponse for JsonRpcBody {
+    fn into_response(self) -> Response {
+        ([(CONTENT_TYPE, "application/json")], self.0).into_response()
+    }
+}
+
+/// The handler returned by [`JsonRpcBody::handler_with_state()`].
+#[derive(Clone)]
+struct StatefulHandler(RpcUserData);
+
+impl<R: Send + 'static> Handler<(), R> for StatefulHandler {
+    type Future = BoxFuture<'static, Response>;
+
+    fn call(self, req: Request, _state: R) -> Self::Future {
+        Box::pin(async move {
+            match dispatch_request(req, Some(self.0)).await {
+                Ok(body) => body.into_response(),
+                Err(rejection) => rejection,
+            }
+        })
+    }
+}
+
+/// Reads and dispatches the body of `req`, turning failures into HTTP responses.
+async fn dispatch_request(
+    req: Request,
+    user_data: Option<RpcUserData>,
+) -> Result<JsonRpcBody, Response> {
+    let json_content = accepts_content_type(req.headers());
+    let body = Bytes::from_request(req, &())
+        .await
+        .map_err(IntoResponse::into_response)?;
+    if !json_content {
+        let err = RpcError::InvalidRequest("content type must be application/json".to_string());
+        return Err(rejection(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            error_response(&body, err),
+        ));
+    }
+    let result = match user_data {
+        Some(user_data) => dispatch_bytes_with_context(body.clone(), user_data).await,
+        None => dispatch_batch(&body).await,
+    };
+    match result {
+        Ok(text) => Ok(JsonRpcBody(text)),
+        Err(err) => Err(rejection(status_code_of(&err), error_response(&body, err))),
+    }
+}
+
+/// Builds an error response carrying a JSON-RPC error object.
+fn rejection(status: StatusCode, text: String) -> Response {
+    (status, [(CONTENT_TYPE, "application/json")], text).into_response()
+}
@@ -9,9 +9,19 @@
 //! directly on the data you wish to send as parameters.
 
 use crate::{JsonRpcId, error::RpcError, request::JsonRpcRequest, response::JsonRpcResponse};
+use futures::{Stream, StreamExt, stream};
 use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
 
 pub use a_rs_jsonrpc_macros::JsonRpcClient;
+pub use a_rs_jsonrpc_macros::rpc_interface;
 pub use a_rs_jsonrpc_macros::rpc_method;
 
 /// The core trait for sending JSON-RPC requests.
@@ -20,9 +30,13 @@ pub use a_rs_jsonrpc_macros::rpc_method;
 /// allowing them to be used as the `params` field in a JSON-RPC call.
 ///
 /// ### Example: Sending a request from a tuple
-/// ```rust
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{JsonRpcClient, RpcError};
+/// # async fn example(url: &str) -> Result<(), RpcError> {
 /// let params = (10, "hello");
 /// let response = params.send_v2_request::<String>(url, "application/json", "myMethod").await?;
+/// # Ok(())
+/// # }
 /// ```
 
 #[async_trait::async_trait]
@@ -74,6 +88,242 @@ pub trait JsonRpcClient {
     {
         self.send_v2_request(url, content_type, method).await
     }
+
+    /// Sends a JSON-RPC 2.0 request, bounding each attempt by `timeout` and retrying
+    /// transient failures according to `policy`.
+    ///
+    /// A failure is transient when [`RpcError::is_retryable`] holds, e.g. the attempt
+    /// timed out or the connection could not be established. Other errors are
+    /// returned immediately. When every attempt
+    /// fails, the error of the last attempt is returned. Requires a Tokio runtime.
+    async fn send_v2_request_with_timeout_and_retry<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+        timeout: Duration,
+        policy: RetryPolicy,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let mut attempt = 1;
+        loop {
+            // Only the error outlives this match: a `JsonRpcResponse<R>` held across
+            // the sleep below would make the future `!Send`.
+            let err = match tokio::time::timeout(
+                timeout,
+                self.send_v2_request::<R>(url, content_type, method),
+            )
+            .await
+            {
+                Ok(Ok(resp)) => return Ok(resp),
+                Ok(Err(err)) => err,
+                Err(elapsed) => elapsed.into(),
+            };
+            if attempt >= policy.max_attempts || !err.is_retryable() {
+                return Err(err);
+            }
+            tracing::warn!(
+                "jsonrpc request {} attempt {} failed: {}, retrying",
+                method,
+                attempt,
+                err
+            );
+            attempt += 1;
+            tokio::time::sleep(policy.delay).await;
+        }
+    }
+
+    /// Checks the health of the server at `url` by calling its `rpc.health` method
+    /// without parameters.
+    ///
+    /// Returns `true` if the result is `{"status": "ok"}`, and `false` for any other
+    /// result, including an error object from a server without the method.
+    ///
+    /// # Errors
+    /// Returns the error of the call if it fails entirely, e.g. when the server is
+    /// unreachable or does not answer with a JSON-RPC response.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// # use a_rs_jsonrpc::{JsonRpcClient, RpcError, SharedClient};
+    /// # async fn example() -> Result<(), RpcError> {
+    /// let healthy = SharedClient::health_check("http://localhost:8080/").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn health_check(url: &str) -> Result<bool, RpcError>
+    where
+        Self: Sized,
+    {
+        let resp =
+            ().send_v2_request::<serde_json::Value>(url, "application/json", "rpc.health")
+                .await?;
+        Ok(resp.result == Some(serde_json::json!({ "status": "ok" })))
+    }
+
+    /// Returns the positional parameters [`JsonRpcClient::send_v2_request`] sends for
+    /// `self`.
+    ///
+    /// The default implementation serializes `self`: an array is used as-is, any other
+    /// value becomes the only parameter. Requests built by hand, like the ones of
+    /// `rpc_method` with extra headers, use it to send the same parameters.
+    fn to_params(&self) -> Result<serde_json::Value, RpcError>
+    where
+        Self: Serialize,
+    {
+        serialize_positional_params(self)
+    }
+
+    /// Builds the JSON-RPC 2.0 request [`JsonRpcClient::send_v2_request`] would send,
+    /// without sending it.
+    ///
+    /// `url` and `content_type` are only logged, so a dry run can stand in for a real
+    /// call in tests and debugging. The parameters are the ones of
+    /// [`JsonRpcClient::to_params`].
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use a_rs_jsonrpc::{JsonRpcClient, RpcError};
+    /// # const URL: &str = "http://localhost:3000/";
+    /// # const APP_JSON: &str = "application/json";
+    /// # fn main() -> Result<(), RpcError> {
+    /// let req = (10, 20).dry_run(URL, APP_JSON, "addArray")?;
+    /// assert_eq!(req.params.into_option(), Some(serde_json::json!([10, 20])));
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn dry_run(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcRequest<serde_json::Value>, RpcError>
+    where
+        Self: Serialize,
+    {
+        Ok(dry_run_request(
+            url,
+            content_type,
+            method,
+            self.to_params()?,
+        ))
+    }
+
+    /// Sends a JSON-RPC 1.0 request encoded as MessagePack, with
+    /// `Content-Type: application/msgpack`, and decodes the MessagePack response.
+    ///
+    /// `self` is sent as the positional parameters of [`JsonRpcClient::to_params`].
+    /// Requires the `msgpack` feature.
+    #[cfg(feature = "msgpack")]
+    async fn send_v1_request_msgpack<R>(
+        &self,
+        url: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        Self: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body = JsonRpcRequest::new_v1(JsonRpcId::next_number(), method);
+        body.set_params(self.to_params()?);
+        tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
+        let request = reqwest::Client::new()
+            .post(url)
+            .headers(trace_context_headers());
+        post_msgpack_request(request, &body).await
+    }
+
+    /// Sends a JSON-RPC 2.0 request encoded as MessagePack, with
+    /// `Content-Type: application/msgpack`, and decodes the MessagePack response.
+    ///
+    /// `self` is sent as the positional parameters of [`JsonRpcClient::to_params`].
+    /// Requires the `msgpack` feature.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// # use a_rs_jsonrpc::{JsonRpcClient, RpcError};
+    /// # const URL: &str = "http://localhost:3000/";
+    /// # async fn example() -> Result<(), RpcError> {
+    /// let resp = (10, 20).send_v2_request_msgpack::<i64>(URL, "addArray").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "msgpack")]
+    async fn send_v2_request_msgpack<R>(
+        &self,
+        url: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        Self: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body = JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+        body.set_params(self.to_params()?);
+        tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
+        let request = reqwest::Client::new()
+            .post(url)
+            .headers(trace_context_headers());
+        post_msgpack_request(request, &body).await
+    }
+}
+
+/// Serializes `params` as positional parameters: an array is used as-is, any other
+/// value becomes the only element.
+fn serialize_positional_params<P: Serialize + ?Sized>(
+    params: &P,
+) -> Result<serde_json::Value, RpcError> {
+    Ok(match serde_json::to_value(params)? {
+        params @ serde_json::Value::Array(_) => params,
+        param => serde_json::Value::Array(vec![param]),
+    })
+}
+
+/// Builds the request of a dry run and logs where it would have been sent.
+#[doc(hidden)]
+pub fn dry_run_request(
+    url: &str,
+    content_type: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> JsonRpcRequest<serde_json::Value> {
+    let mut body = JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+    body.set_params(params);
+    tracing::debug!(
+        "jsonrpc dry run to {} ({}): {:?}",
+        url,
+        content_type,
+        serde_json::to_string(&body)
+    );
+    body
+}
+
+/// Controls how many times a request is attempted and how long to wait in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// The delay between two consecutive attempts.
+    pub delay: Duration,
+}
+
+impl RetryPolicy {
+    /// Creates a policy with `max_attempts` total attempts separated by `delay`.
+    pub fn new(max_attempts: u32, delay: Duration) -> Self {
+        RetryPolicy {
+            max_attempts,
+            delay,
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 100 milliseconds apart.
+    fn default() -> Self {
+        RetryPolicy::new(3, Duration::from_millis(100))
+    }
 }
 
 /// A helper trait for executing RPC calls.
@@ -126,6 +376,7 @@ macro_rules! impl_scalar_jsonrpc_client {
                 let resp = reqwest::Client::new()
                     .post(url)
                     .header("Content-Type", content_type)
+                    .headers(trace_context_headers())
                     .json(&body)
                     .send()
                     .await?;
@@ -151,6 +402,7 @@ macro_rules! impl_scalar_jsonrpc_client {
                 let resp = reqwest::Client::new()
                     .post(url)
                     .header("Content-Type", content_type)
+                    .headers(trace_context_headers())
                     .json(&body)
                     .send()
                     .await
@@ -202,6 +454,7 @@ macro_rules! impl_tuple_jsonrpc_client {
                 let resp = reqwest::Client::new()
                     .post(url)
                     .header("Content-Type", content_type)
+                    .headers(trace_context_headers())
                     .json(&body)
                     .send()
                     .await?;
@@ -232,6 +485,7 @@ macro_rules! impl_tuple_jsonrpc_client {
                 let resp = reqwest::Client::new()
                     .post(url)
                     .header("Content-Type", content_type)
+                    .headers(trace_context_headers())
                     .json(&body)
                     .send()
                     .await?;
@@ -281,6 +535,7 @@ where
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -307,6 +562,7 @@ where
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -340,6 +596,7 @@ where
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -366,6 +623,7 @@ where
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -393,6 +651,7 @@ impl JsonRpcClient for () {
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -417,6 +676,7 @@ impl JsonRpcClient for () {
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -441,6 +701,7 @@ impl JsonRpcClient for () {
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -465,6 +726,7 @@ impl JsonRpcClient for () {
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -472,6 +734,10 @@ impl JsonRpcClient for () {
         tracing::debug!("jsonrpc response body: {}", text);
         Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
     }
+
+    fn to_params(&self) -> Result<serde_json::Value, RpcError> {
+        Ok(serde_json::json!([]))
+    }
 }
 
 #[async_trait::async_trait]
@@ -500,6 +766,7 @@ where
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -528,6 +795,7 @@ where
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -535,6 +803,13 @@ where
         tracing::debug!("jsonrpc response body: {}", text);
         Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
     }
+
+    fn to_params(&self) -> Result<serde_json::Value, RpcError> {
+        match self {
+            Some(inner) => inner.to_params(),
+            None => Ok(serde_json::json!([])),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -555,6 +830,7 @@ impl JsonRpcClient for String {
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -579,6 +855,7 @@ impl JsonRpcClient for String {
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -606,6 +883,7 @@ impl JsonRpcClient for &str {
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -630,6 +908,7 @@ impl JsonRpcClient for &str {
         let resp = reqwest::Client::new()
             .post(url)
             .header("Content-Type", content_type)
+            .headers(trace_context_headers())
             .json(&body)
             .send()
             .await?;
@@ -638,3 +917,1252 @@ impl JsonRpcClient for &str {
         Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
     }
 }
+
+/// Sends a JSON object as the parameters.
+///
+/// The object modes place the map inline as `"params": {...}` without converting it
+/// into a `serde_json::Value` first. The array modes send it as a single positional
+/// parameter, `"params": [{...}]`.
+#[async_trait::async_trait]
+impl JsonRpcClient for serde_json::Map<String, serde_json::Value> {
+    async fn send_v1_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<[&Self; 1]> = JsonRpcRequest::new_v1(id, method);
+        body.set_params([self]);
+        post_request(&reqwest::Client::new(), url, content_type, &body).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<[&Self; 1]> = JsonRpcRequest::new_v2(id, method);
+        body.set_params([self]);
+        post_request(&reqwest::Client::new(), url, content_type, &body).await
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<&Self> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(self);
+        post_request(&reqwest::Client::new(), url, content_type, &body).await
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<&Self> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(self);
+        post_request(&reqwest::Client::new(), url, content_type, &body).await
+    }
+}
+
+/// Sends the referenced parameters, so borrowed values can be passed uniformly.
+///
+/// Every method delegates to `T`, including overrides of [`JsonRpcClient::to_params`],
+/// [`JsonRpcClient::health_check`] and the MessagePack methods. Forwarding those
+/// needs the `Serialize` and `Send` bounds they place on `T` itself.
+#[async_trait::async_trait]
+impl<T: JsonRpcClient + Serialize + Send + Sync> JsonRpcClient for &T {
+    async fn send_v1_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        (**self).send_v1_request(url, content_type, method).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        (**self).send_v2_request(url, content_type, method).await
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        (**self)
+            .send_v1_request_obj(url, content_type, method)
+            .await
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        (**self)
+            .send_v2_request_obj(url, content_type, method)
+            .await
+    }
+
+    async fn send_v2_request_with_timeout_and_retry<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+        timeout: Duration,
+        policy: RetryPolicy,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        (**self)
+            .send_v2_request_with_timeout_and_retry(url, content_type, method, timeout, policy)
+            .await
+    }
+
+    async fn health_check(url: &str) -> Result<bool, RpcError> {
+        T::health_check(url).await
+    }
+
+    fn to_params(&self) -> Result<serde_json::Value, RpcError> {
+        (**self).to_params()
+    }
+
+    fn dry_run(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcRequest<serde_json::Value>, RpcError> {
+        (**self).dry_run(url, content_type, method)
+    }
+
+    #[cfg(feature = "msgpack")]
+    async fn send_v1_request_msgpack<R>(
+        &self,
+        url: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        (**self).send_v1_request_msgpack(url, method).await
+    }
+
+    #[cfg(feature = "msgpack")]
+    async fn send_v2_request_msgpack<R>(
+        &self,
+        url: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        (**self).send_v2_request_msgpack(url, method).await
+    }
+}
+
+/// Sends the elements of a one-dimensional array as positional parameters.
+///
+/// `[1.0, 2.0, 3.0]` is sent as `"params": [1.0, 2.0, 3.0]`. Requires the `ndarray` feature.
+#[cfg(feature = "ndarray")]
+#[async_trait::async_trait]
+impl<T> JsonRpcClient for ndarray::Array1<T>
+where
+    T: Serialize + Send + Sync,
+{
+    async fn send_v1_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(array_params(self.iter())?);
+        post_request(&reqwest::Client::new(), url, content_type, &body).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(array_params(self.iter())?);
+        post_request(&reqwest::Client::new(), url, content_type, &body).await
+    }
+
+    fn to_params(&self) -> Result<serde_json::Value, RpcError> {
+        Ok(serde_json::Value::Array(array_params(self.iter())?))
+    }
+}
+
+/// Sends the rows of a two-dimensional array as positional parameters.
+///
+/// `[[1, 2], [3, 4]]` is sent as `"params": [[1, 2], [3, 4]]`, one parameter per row.
+/// Requires the `ndarray` feature.
+#[cfg(feature = "ndarray")]
+#[async_trait::async_trait]
+impl<T> JsonRpcClient for ndarray::Array2<T>
+where
+    T: Serialize + Send + Sync,
+{
+    async fn send_v1_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(matrix_params(self)?);
+        post_request(&reqwest::Client::new(), url, content_type, &body).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(matrix_params(self)?);
+        post_request(&reqwest::Client::new(), url, content_type, &body).await
+    }
+
+    fn to_params(&self) -> Result<serde_json::Value, RpcError> {
+        Ok(serde_json::Value::Array(matrix_params(self)?))
+    }
+}
+
+/// Serializes each element into one positional parameter.
+#[cfg(feature = "ndarray")]
+fn array_params<'a, T>(
+    items: impl Iterator<Item = &'a T>,
+) -> Result<Vec<serde_json::Value>, RpcError>
+where
+    T: Serialize + 'a,
+{
+    Ok(items
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Serializes each row into one array parameter.
+#[cfg(feature = "ndarray")]
+fn matrix_params<T: Serialize>(
+    matrix: &ndarray::Array2<T>,
+) -> Result<Vec<serde_json::Value>, RpcError> {
+    matrix
+        .rows()
+        .into_iter()
+        .map(|row| Ok(serde_json::Value::Array(array_params(row.iter())?)))
+        .collect()
+}
+
+/// Returns the headers propagating the current trace context to the server.
+///
+/// With the `opentelemetry` feature, the context of the current `tracing` span is
+/// injected as W3C `traceparent` / `tracestate` headers using the globally
+/// configured propagator. Without it, the map is empty.
+pub fn trace_context_headers() -> reqwest::header::HeaderMap {
+    #[allow(unused_mut)]
+    let mut headers = reqwest::header::HeaderMap::new();
+    #[cfg(feature = "opentelemetry")]
+    {
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        let cx = tracing::Span::current().context();
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&cx, &mut opentelemetry_http::HeaderInjector(&mut headers))
+        });
+    }
+    headers
+}
+
+/// Posts a serialized JSON-RPC request body with the given `reqwest::Client` and
+/// parses the JSON-RPC response.
+async fn post_request<B, R>(
+    client: &reqwest::Client,
+    url: &str,
+    content_type: &str,
+    body: &B,
+) -> Result<JsonRpcResponse<R>, RpcError>
+where
+    B: Serialize + Sync,
+    R: serde::de::DeserializeOwned,
+{
+    tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(body));
+    let resp = client
+        .post(url)
+        .header("Content-Type", content_type)
+        .headers(trace_context_headers())
+        .json(body)
+        .send()
+        .await?;
+    let text = resp.text().await?;
+    tracing::debug!("jsonrpc response body: {}", text);
+    Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
+}
+
+/// Sends `body` encoded as MessagePack with `Content-Type: application/msgpack` and
+/// decodes the MessagePack response. Requires the `msgpack` feature.
+///
+/// The struct fields are encoded by name, so the optional keys of requests and
+/// responses can be omitted exactly as in JSON.
+#[cfg(feature = "msgpack")]
+#[doc(hidden)]
+pub async fn post_msgpack_request<B, R>(
+    request: reqwest::RequestBuilder,
+    body: &B,
+) -> Result<JsonRpcResponse<R>, RpcError>
+where
+    B: Serialize + ?Sized,
+    R: serde::de::DeserializeOwned,
+{
+    let resp = request
+        .header("Content-Type", "application/msgpack")
+        .body(rmp_serde::to_vec_named(body)?)
+        .send()
+        .await?;
+    let bytes = resp.bytes().await?;
+    Ok(rmp_serde::from_slice::<JsonRpcResponse<R>>(&bytes)?)
+}
+
+/// A JSON-RPC client bound to a single endpoint that reuses one `reqwest::Client`.
+///
+/// `reqwest::Client` keeps an internal connection pool, so sharing one instance
+/// between callers avoids a new connection per request. `SharedClient` is cheap to
+/// clone and can be handed to every component talking to the same endpoint.
+///
+/// Parameters passed to [`SharedClient::call_v1`] and [`SharedClient::call_v2`] are
+/// serialized as-is: tuples and `Vec`s become arrays, structs and maps become objects.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{JsonRpcResponse, RpcError, SharedClient};
+/// # async fn example() -> Result<(), RpcError> {
+/// let client = SharedClient::new(reqwest::Client::new(), "http://localhost:3000/", "application/json");
+/// let resp: JsonRpcResponse<i32> = client.call_v2("addArray", (10, 20)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SharedClient {
+    client: reqwest::Client,
+    url: String,
+    content_type: String,
+}
+
+impl SharedClient {
+    /// Creates a client for `url` that sends every request through `client`.
+    pub fn new(client: reqwest::Client, url: &str, content_type: &str) -> Self {
+        SharedClient {
+            client,
+            url: url.to_string(),
+            content_type: content_type.to_string(),
+        }
+    }
+
+    /// Returns the underlying `reqwest::Client`.
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Returns the endpoint URL this client is bound to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the `Content-Type` header value sent with each request.
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// Sends a JSON-RPC 1.0 request to the bound endpoint.
+    pub async fn call_v1<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v1(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        post_request(&self.client, &self.url, &self.content_type, &body).await
+    }
+
+    /// Sends a JSON-RPC 2.0 request to the bound endpoint.
+    pub async fn call_v2<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        post_request(&self.client, &self.url, &self.content_type, &body).await
+    }
+
+    /// Sends a prepared request as-is.
+    ///
+    /// Unlike [`SharedClient::call_v1`] and [`SharedClient::call_v2`], this keeps the
+    /// request's `params` state, so a request without a `params` key is sent without one.
+    pub async fn send_request<T, R>(
+        &self,
+        request: &JsonRpcRequest<T>,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        T: Serialize + Sync,
+        R: serde::de::DeserializeOwned,
+    {
+        post_request(&self.client, &self.url, &self.content_type, request).await
+    }
+
+    /// Sends a JSON-RPC 2.0 request and reads a streaming `application/x-ndjson` response.
+    ///
+    /// Subscription-style servers answer with one JSON-RPC response object per line.
+    /// Each line is parsed as a [`JsonRpcResponse<R>`] and yielded as its result, so an
+    /// `error` object on one line surfaces as an `Err` item without ending the stream.
+    /// Blank lines are skipped.
+    ///
+    /// ### Example
+    /// ```rust,no_run
+    /// # use a_rs_jsonrpc::{RpcError, SharedClient};
+    /// # use futures::StreamExt;
+    /// # #[derive(Debug, serde::Deserialize)]
+    /// # struct Event { height: u64 }
+    /// # async fn example(client: SharedClient) -> Result<(), RpcError> {
+    /// let mut events = std::pin::pin!(client.send_v2_request_streaming::<_, Event>("subscribe", ["blocks"]));
+    /// while let Some(event) = events.next().await {
+    ///     println!("{:?}", event?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_v2_request_streaming<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> impl Stream<Item = Result<R, RpcError>>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let request = serde_json::to_value(params).map(|params| {
+            let mut body: JsonRpcRequest<serde_json::Value> =
+                JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+            body.set_params(params);
+            tracing::debug!("jsonrpc request body: {:?}", serde_json::to_string(&body));
+            self.client
+                .post(&self.url)
+                .header("Content-Type", &self.content_type)
+                .headers(trace_context_headers())
+                .header("Accept", "application/x-ndjson")
+                .json(&body)
+        });
+        stream::once(async move { Ok::<_, RpcError>(request?.send().await?) })
+            .map(|resp| match resp {
+                Ok(resp) => ndjson_results(resp).left_stream(),
+                Err(err) => stream::iter([Err(err)]).right_stream(),
+            })
+            .flatten()
+    }
+}
+
+/// Splits a response body into lines and parses each non-blank line as a JSON-RPC response.
+fn ndjson_results<R>(resp: reqwest::Response) -> impl Stream<Item = Result<R, RpcError>>
+where
+    R: serde::de::DeserializeOwned,
+{
+    stream::unfold(
+        (resp, Vec::<u8>::new(), false),
+        |(mut resp, mut buf, mut done)| async move {
+            loop {
+                let line = match buf.iter().position(|b| *b == b'\n') {
+                    Some(pos) => buf.drain(..=pos).collect::<Vec<u8>>(),
+                    None if done => std::mem::take(&mut buf),
+                    None => {
+                        match resp.chunk().await {
+                            Ok(Some(chunk)) => buf.extend_from_slice(&chunk),
+                            Ok(None) => done = true,
+                            Err(err) => return Some((Err(err.into()), (resp, Vec::new(), true))),
+                        }
+                        continue;
+                    }
+                };
+                if line.trim_ascii().is_empty() {
+                    if done && buf.is_empty() {
+                        return None;
+                    }
+                    continue;
+                }
+                tracing::debug!("jsonrpc response line: {}", String::from_utf8_lossy(&line));
+                let item = serde_json::from_slice::<JsonRpcResponse<R>>(&line)
+                    .map_err(RpcError::from)
+                    .and_then(JsonRpcResponse::into_result);
+                return Some((item, (resp, buf, done)));
+            }
+        },
+    )
+}
+
+/// Sends a request without parameters through the shared `reqwest::Client`.
+///
+/// The `url` and `content_type` arguments take precedence over the bound endpoint,
+/// so the shared connection pool can also be used for other endpoints.
+#[async_trait::async_trait]
+impl JsonRpcClient for SharedClient {
+    async fn send_v1_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(vec![]);
+        post_request(&self.client, url, content_type, &body).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(vec![]);
+        post_request(&self.client, url, content_type, &body).await
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(serde_json::json!({}));
+        post_request(&self.client, url, content_type, &body).await
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(serde_json::json!({}));
+        post_request(&self.client, url, content_type, &body).await
+    }
+}
+
+/// Call counters of one method, as returned by [`JsonRpcClientSession::metrics_snapshot`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonRpcClientMetrics {
+    /// The number of calls made.
+    pub calls: u64,
+    /// The number of calls that failed or were answered with an error object.
+    pub errors: u64,
+    /// The total size of the request bodies sent, in bytes.
+    pub bytes_sent: u64,
+    /// The total size of the response bodies received, in bytes.
+    pub bytes_received: u64,
+}
+
+/// The live counters behind a [`JsonRpcClientMetrics`] snapshot.
+#[derive(Debug, Default)]
+struct ClientMethodCounters {
+    calls: AtomicU64,
+    errors: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+}
+
+/// A [`SharedClient`] that counts calls, errors and transferred bytes per method.
+///
+/// Counters are updated atomically after each call. Clones share the counters.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{JsonRpcClientSession, JsonRpcResponse, RpcError, SharedClient};
+/// # const URL: &str = "http://localhost:3000/";
+/// # const APP_JSON: &str = "application/json";
+/// # async fn example() -> Result<(), RpcError> {
+/// let session = JsonRpcClientSession::new(SharedClient::new(reqwest::Client::new(), URL, APP_JSON));
+/// let resp: JsonRpcResponse<i32> = session.call_v2("addArray", (10, 20)).await?;
+/// assert_eq!(session.metrics_snapshot()["addArray"].calls, 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct JsonRpcClientSession {
+    client: SharedClient,
+    metrics: Arc<Mutex<HashMap<String, Arc<ClientMethodCounters>>>>,
+}
+
+impl JsonRpcClientSession {
+    /// Creates a session sending through `client`, with all counters at zero.
+    pub fn new(client: SharedClient) -> Self {
+        JsonRpcClientSession {
+            client,
+            metrics: Arc::default(),
+        }
+    }
+
+    /// Returns the underlying [`SharedClient`].
+    pub fn client(&self) -> &SharedClient {
+        &self.client
+    }
+
+    /// Returns a copy of the counters of every method called so far.
+    pub fn metrics_snapshot(&self) -> HashMap<String, JsonRpcClientMetrics> {
+        self.metrics
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(method, counters)| {
+                let metrics = JsonRpcClientMetrics {
+                    calls: counters.calls.load(Ordering::Relaxed),
+                    errors: counters.errors.load(Ordering::Relaxed),
+                    bytes_sent: counters.bytes_sent.load(Ordering::Relaxed),
+                    bytes_received: counters.bytes_received.load(Ordering::Relaxed),
+                };
+                (method.clone(), metrics)
+            })
+            .collect()
+    }
+
+    /// Sends a JSON-RPC 1.0 request to the bound endpoint.
+    pub async fn call_v1<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v1(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.send(&body).await
+    }
+
+    /// Sends a JSON-RPC 2.0 request to the bound endpoint.
+    pub async fn call_v2<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.send(&body).await
+    }
+
+    /// Returns the counters of `method`, registering them on first use.
+    fn counters(&self, method: &str) -> Arc<ClientMethodCounters> {
+        self.metrics
+            .lock()
+            .unwrap()
+            .entry(method.to_string())
+            .or_default()
+            .clone()
+    }
+
+    async fn send<T, R>(&self, body: &JsonRpcRequest<T>) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        T: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let counters = self.counters(&body.method);
+        let req = serde_json::to_vec(body)?;
+        let sent = req.len();
+        let (received, result) = self.exchange(req).await;
+        counters.calls.fetch_add(1, Ordering::Relaxed);
+        counters
+            .bytes_sent
+            .fetch_add(sent as u64, Ordering::Relaxed);
+        counters
+            .bytes_received
+            .fetch_add(received as u64, Ordering::Relaxed);
+        let result = result.and_then(|text| Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?));
+        if !matches!(&result, Ok(resp) if resp.error.is_none()) {
+            counters.errors.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Posts `req` and returns the number of response bytes read with the body text.
+    async fn exchange(&self, req: Vec<u8>) -> (usize, Result<String, RpcError>) {
+        tracing::debug!("jsonrpc request body: {}", String::from_utf8_lossy(&req));
+        let resp = self
+            .client
+            .client()
+            .post(self.client.url())
+            .header("Content-Type", self.client.content_type())
+            .headers(trace_context_headers())
+            .body(req)
+            .send()
+            .await;
+        let text = match resp {
+            Ok(resp) => resp.text().await,
+            Err(e) => return (0, Err(e.into())),
+        };
+        match text {
+            Ok(text) => {
+                tracing::debug!("jsonrpc response body: {}", text);
+                (text.len(), Ok(text))
+            }
+            Err(e) => (0, Err(e.into())),
+        }
+    }
+}
+
+/// How a [`JsonRpcClientPool`] picks the session of each call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LoadBalancing {
+    /// Cycle through the sessions in order.
+    #[default]
+    RoundRobin,
+    /// Pick the session with the fewest calls in flight, cycling through ties.
+    LeastConnections,
+}
+
+/// Distributes calls across several [`JsonRpcClientSession`]s, e.g. one per server.
+///
+/// Each session keeps its own metrics, available through
+/// [`JsonRpcClientPool::sessions`]. Calls always go to the endpoints of the sessions,
+/// even through [`JsonRpcClient`] methods that take a `url`.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{JsonRpcClientPool, JsonRpcClientSession, JsonRpcResponse, LoadBalancing, RpcError, SharedClient};
+/// # const APP_JSON: &str = "application/json";
+/// # async fn example(client: reqwest::Client, urls: &[&str]) -> Result<(), RpcError> {
+/// let pool = JsonRpcClientPool::new(
+///     urls.iter().map(|url| JsonRpcClientSession::new(SharedClient::new(client.clone(), url, APP_JSON))).collect(),
+///     LoadBalancing::LeastConnections,
+/// );
+/// let resp: JsonRpcResponse<i32> = pool.call_v2("addArray", (10, 20)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct JsonRpcClientPool {
+    sessions: Vec<JsonRpcClientSession>,
+    active: Vec<AtomicUsize>,
+    next: AtomicUsize,
+    strategy: LoadBalancing,
+}
+
+/// Counts a call in flight on a session until dropped.
+struct ActiveCall<'a>(&'a AtomicUsize);
+
+impl Drop for ActiveCall<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl JsonRpcClientPool {
+    /// Creates a pool distributing calls across `sessions` with `strategy`.
+    ///
+    /// # Panics
+    /// Panics if `sessions` is empty.
+    pub fn new(sessions: Vec<JsonRpcClientSession>, strategy: LoadBalancing) -> Self {
+        assert!(
+            !sessions.is_empty(),
+            "JsonRpcClientPool requires at least one session"
+        );
+        JsonRpcClientPool {
+            active: sessions.iter().map(|_| AtomicUsize::new(0)).collect(),
+            sessions,
+            next: AtomicUsize::new(0),
+            strategy,
+        }
+    }
+
+    /// Returns the sessions of the pool, in the order they were given.
+    pub fn sessions(&self) -> &[JsonRpcClientSession] {
+        &self.sessions
+    }
+
+    /// Sends a JSON-RPC 1.0 request through the next session.
+    pub async fn call_v1<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let (session, _active) = self.acquire();
+        session.call_v1(method, params).await
+    }
+
+    /// Sends a JSON-RPC 2.0 request through the next session.
+    pub async fn call_v2<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let (session, _active) = self.acquire();
+        session.call_v2(method, params).await
+    }
+
+    /// Picks the session of the next call and counts the call as in flight on it.
+    fn acquire(&self) -> (&JsonRpcClientSession, ActiveCall<'_>) {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let len = self.sessions.len();
+        let index = match self.strategy {
+            LoadBalancing::RoundRobin => start % len,
+            LoadBalancing::LeastConnections => (0..len)
+                .map(|i| (start + i) % len)
+                .min_by_key(|&i| self.active[i].load(Ordering::Relaxed))
+                .unwrap_or(0),
+        };
+        self.active[index].fetch_add(1, Ordering::Relaxed);
+        (&self.sessions[index], ActiveCall(&self.active[index]))
+    }
+}
+
+/// Sends a request without parameters through the next session of the pool.
+///
+/// Unlike other [`JsonRpcClient`] implementations, this one does not post to the
+/// given endpoint: the `url` and `content_type` arguments are ignored, and every
+/// session posts to the endpoint and content type it is bound to. Prefer
+/// [`JsonRpcClientPool::call_v1`] and [`JsonRpcClientPool::call_v2`], which take no
+/// endpoint, unless the pool has to stand in for a generic client.
+#[async_trait::async_trait]
+impl JsonRpcClient for JsonRpcClientPool {
+    async fn send_v1_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        self.call_v1(method, Vec::<serde_json::Value>::new()).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        self.call_v2(method, Vec::<serde_json::Value>::new()).await
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        self.call_v1(method, serde_json::json!({})).await
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        self.call_v2(method, serde_json::json!({})).await
+    }
+}
+
+/// The time a [`CachingClient`] keeps a response unless [`CachingClient::with_ttl`]
+/// changes it.
+#[cfg(feature = "caching")]
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Wraps a [`JsonRpcClient`], answering repeated JSON-RPC 2.0 calls from a cache.
+///
+/// [`JsonRpcClient::send_v2_request`] looks up the response by endpoint `url`, method
+/// and a hash of the content type and serialized parameters, and only calls the inner client on a miss or once the
+/// cached response is older than the TTL. Only responses without an `error` object
+/// are cached, and a cached response keeps the `id` of the call that fetched it. The
+/// other methods are passed through uncached. Requires the `caching` feature.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{CachingClient, JsonRpcClient, RpcError};
+/// # use std::time::Duration;
+/// # const URL: &str = "http://localhost:3000/";
+/// # const APP_JSON: &str = "application/json";
+/// # async fn example() -> Result<(), RpcError> {
+/// let balance = CachingClient::new(("0xabc",)).with_ttl(Duration::from_secs(5));
+/// let resp = balance.send_v2_request::<u64>(URL, APP_JSON, "getBalance").await?;
+/// // Served from the cache for the next 5 seconds.
+/// let resp = balance.send_v2_request::<u64>(URL, APP_JSON, "getBalance").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "caching")]
+#[derive(Debug)]
+pub struct CachingClient<C> {
+    inner: C,
+    ttl: Duration,
+    cache: dashmap::DashMap<(String, String, String), (String, std::time::Instant)>,
+}
+
+#[cfg(feature = "caching")]
+impl<C: JsonRpcClient + Serialize> CachingClient<C> {
+    /// Wraps `inner`, caching responses for [`DEFAULT_CACHE_TTL`].
+    pub fn new(inner: C) -> Self {
+        CachingClient {
+            inner,
+            ttl: DEFAULT_CACHE_TTL,
+            cache: dashmap::DashMap::new(),
+        }
+    }
+
+    /// Sets how long a response is served from the cache.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Returns the wrapped client.
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Drops every cached response of `method` at `url`.
+    pub fn invalidate(&self, url: &str, method: &str) {
+        self.cache.retain(|(cached_url, cached_method, _), _| {
+            cached_url != url || cached_method != method
+        });
+    }
+
+    /// Returns the cache key of a call to `method` at `url`, with the inner client as
+    /// parameters.
+    fn key(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<(String, String, String), RpcError> {
+        use std::hash::{DefaultHasher, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        hasher.write(content_type.as_bytes());
+        hasher.write(&serde_json::to_vec(&self.inner)?);
+        Ok((
+            url.to_string(),
+            method.to_string(),
+            format!("{:016x}", hasher.finish()),
+        ))
+    }
+}
+
+#[cfg(feature = "caching")]
+#[async_trait::async_trait]
+impl<C> JsonRpcClient for CachingClient<C>
+where
+    C: JsonRpcClient + Serialize + Send + Sync,
+{
+    async fn send_v1_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        self.inner.send_v1_request(url, content_type, method).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let key = self.key(url, content_type, method)?;
+        let cached = self
+            .cache
+            .get(&key)
+            .filter(|entry| entry.1.elapsed() < self.ttl)
+            .map(|entry| entry.0.clone());
+        if let Some(text) = cached {
+            tracing::debug!("jsonrpc cached response body: {}", text);
+            return Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?);
+        }
+        let resp = self
+            .inner
+            .send_v2_request::<serde_json::Value>(url, content_type, method)
+            .await?;
+        let text = serde_json::to_string(&resp)?;
+        if resp.error.is_none() {
+            self.cache
+                .insert(key, (text.clone(), std::time::Instant::now()));
+        }
+        Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        self.inner
+            .send_v1_request_obj(url, content_type, method)
+            .await
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        self.inner
+            .send_v2_request_obj(url, content_type, method)
+            .await
+    }
+}
+
+/// Sends a request without parameters through the given `reqwest::Client`.
+///
+/// Create one client at startup and reuse it, so every call shares its connection
+/// pool. All HTTP configuration (timeouts, proxies, default headers) comes from the
+/// client itself.
+#[async_trait::async_trait]
+impl JsonRpcClient for reqwest::Client {
+    async fn send_v1_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(vec![]);
+        post_request(self, url, content_type, &body).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(vec![]);
+        post_request(self, url, content_type, &body).await
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(serde_json::json!({}));
+        post_request(self, url, content_type, &body).await
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(serde_json::json!({}));
+        post_request(self, url, content_type, &body).await
+    }
+}
+
+/// Extension methods for sending parameters through a shared `reqwest::Client`.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{JsonRpcClientExt, JsonRpcResponse, RpcError};
+/// # async fn example() -> Result<(), RpcError> {
+/// let client = reqwest::Client::new();
+/// let resp: JsonRpcResponse<i32> = client
+///     .send_v2_request_typed(&(10, 20), "http://localhost:3000/", "application/json", "addArray")
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait::async_trait]
+pub trait JsonRpcClientExt {
+    /// Sends a JSON-RPC 2.0 request with `params` as its parameters.
+    ///
+    /// Parameters follow the positional rules of the [`JsonRpcClient`] impls: arrays
+    /// and objects are sent as-is, `()` and `None` as empty parameters, and any other
+    /// value as a single-element array.
+    async fn send_v2_request_typed<P, R>(
+        &self,
+        params: &P,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: JsonRpcClient + Serialize + Sync,
+        R: serde::de::DeserializeOwned;
+}
+
+#[async_trait::async_trait]
+impl JsonRpcClientExt for reqwest::Client {
+    async fn send_v2_request_typed<P, R>(
+        &self,
+        params: &P,
+        url: &str,
+        content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: JsonRpcClient + Serialize + Sync,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+        body.set_params(positional_params(serde_json::to_value(params)?));
+        post_request(self, url, content_type, &body).await
+    }
+}
+
+/// Normalizes a serialized parameter value to an array or object.
+fn positional_params(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => value,
+        serde_json::Value::Null => serde_json::Value::Array(vec![]),
+        scalar => serde_json::Value::Array(vec![scalar]),
+    }
+}
@@ -4,7 +4,12 @@
 //! the library for both client and server operations. It also provides automatic
 //! conversion into the standard JSON-RPC error format.
 
-use crate::response::JsonRpcError;
+use crate::{
+    JsonRpcId,
+    request::JsonRpcVersion,
+    response::{JsonRpcError, JsonRpcResponse},
+};
+use std::borrow::Cow;
 use thiserror::Error;
 
 /// The primary error type for JSON-RPC operations.
@@ -26,24 +31,164 @@ pub enum RpcError {
     ReqwestError(#[from] reqwest::Error),
 
     /// Errors occurring during JSON serialization or deserialization.
+    #[cfg(not(feature = "path-errors"))]
     #[error("serialize/deserialize error: {0}")]
     SerdeError(#[from] serde_json::Error),
 
+    /// Errors occurring during JSON serialization or deserialization.
+    ///
+    /// With the `path-errors` feature, request deserialization in service handlers
+    /// records the path of the offending value, e.g. `params.b`.
+    #[cfg(feature = "path-errors")]
+    #[error("serialize/deserialize error{}: {error}", at_path(.path))]
+    SerdeError {
+        /// The underlying error.
+        #[source]
+        error: serde_json::Error,
+        /// The path of the value that failed to deserialize, if known.
+        path: Option<String>,
+    },
+
+    /// Errors occurring during MessagePack encoding or decoding. Requires the
+    /// `msgpack` feature.
+    #[cfg(feature = "msgpack")]
+    #[error("msgpack error: {0}")]
+    MsgpackError(String),
+
+    /// Standard JSON-RPC error (-32700) indicating the payload is not valid JSON.
+    #[error("parse error: {0}")]
+    ParseError(String),
+
     /// Triggered when the `jsonrpc` field in a request does not match the expected version.
     #[error("invalid json rpc version: {0}")]
     InvalidJsonRpcVersion(String),
 
+    /// Standard JSON-RPC error (-32600) indicating the request object is not valid.
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
     /// Standard JSON-RPC error (-32601) indicating the requested method does not exist.
     #[error("json rpc method not found")]
     MethodNotFound,
 
+    /// Standard JSON-RPC error (-32603) indicating the server failed internally, e.g.
+    /// a handler panicked while [`crate::ServiceConfig::catch_panics`] was set.
+    #[error("internal error: {0}")]
+    InternalError(String),
+
+    /// Triggered when a request did not complete within its time limit.
+    #[error("json rpc request timed out")]
+    Timeout,
+
     /// General-purpose error for custom business logic failures.
+    ///
+    /// Deprecated: it has no code of its own and is always sent as `-32003`. Use
+    /// [`RpcError::ApplicationError`] instead.
+    #[deprecated(note = "use `RpcError::ApplicationError`, which carries a code and data")]
     #[error("custom error: {0}")]
-    CustomError(String),
+    CustomError(Cow<'static, str>),
+
+    /// An application-specific error, passed through to the JSON-RPC `error` object
+    /// unchanged.
+    ///
+    /// Codes from -32000 to -32099 are reserved for the library's own server errors,
+    /// and -32700 to -32600 for the protocol; application codes should be positive or
+    /// lie outside those ranges.
+    #[error("application error {code}: {message}")]
+    ApplicationError {
+        /// The error code sent to the client.
+        code: i64,
+        /// A short description of the error.
+        message: String,
+        /// Additional structured information about the error.
+        data: Option<serde_json::Value>,
+    },
 
     /// Standard JSON-RPC error (-32602) indicating invalid or malformed arguments.
     #[error("invalid parameters: {0}")]
-    InvalidParams(String),
+    InvalidParams(Cow<'static, str>),
+}
+
+impl RpcError {
+    /// Creates a [`RpcError::CustomError`] from a static message, usable in `const`
+    /// contexts.
+    ///
+    /// The variant is deprecated in favour of [`RpcError::ApplicationError`]; this
+    /// constructor is kept for code that still defines its errors as constants.
+    #[allow(deprecated)]
+    pub const fn custom_error_static(msg: &'static str) -> RpcError {
+        RpcError::CustomError(Cow::Borrowed(msg))
+    }
+
+    /// Creates a [`RpcError::InvalidParams`] from a static message, usable in `const`
+    /// contexts.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use a_rs_jsonrpc::RpcError;
+    /// const MISSING_NAME: RpcError = RpcError::invalid_params_static("name is required");
+    /// ```
+    pub const fn invalid_params_static(msg: &'static str) -> RpcError {
+        RpcError::InvalidParams(Cow::Borrowed(msg))
+    }
+
+    /// Returns `true` for failures that may succeed when the request is repeated.
+    ///
+    /// These are timeouts, transient I/O errors (a refused, reset or aborted
+    /// connection, a timed out or interrupted operation, or an unexpected end of
+    /// file) and `reqwest` errors raised while connecting, sending the request or
+    /// waiting for the response. Other I/O errors, e.g. a missing file or a denied
+    /// permission, are not retryable. Protocol errors such as
+    /// [`RpcError::MethodNotFound`], [`RpcError::InvalidParams`],
+    /// [`RpcError::InvalidJsonRpcVersion`] or [`RpcError::ParseError`] would fail
+    /// again and are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RpcError::Timeout => true,
+            RpcError::IoError(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::TimedOut
+                    | std::io::ErrorKind::Interrupted
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            RpcError::ReqwestError(e) => e.is_connect() || e.is_timeout() || e.is_request(),
+            _ => false,
+        }
+    }
+
+    /// Returns the HTTP status of the response the error was raised for, if it came
+    /// from `reqwest` with a status, e.g. through `Response::error_for_status`.
+    pub fn http_status_code(&self) -> Option<u16> {
+        match self {
+            RpcError::ReqwestError(e) => e.status().map(|status| status.as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Serializes the error as a complete JSON-RPC error response.
+    ///
+    /// The error object is produced by the [`JsonRpcError`] conversion, and `id` and
+    /// `version` should be taken from the failed request so the client can correlate
+    /// the response.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use a_rs_jsonrpc::{JsonRpcId, RpcError, request::JsonRpcVersion};
+    /// let body = RpcError::MethodNotFound.into_jsonrpc_error_response(JsonRpcId::Number(1), JsonRpcVersion::V2_0);
+    /// // {"jsonrpc":"2.0","error":{"code":-32601,"message":"method not found"},"id":1}
+    /// ```
+    pub fn into_jsonrpc_error_response(self, id: JsonRpcId, version: JsonRpcVersion) -> String {
+        let resp: JsonRpcResponse<()> = JsonRpcResponse {
+            jsonrpc: version,
+            result: None,
+            error: Some(self.into()),
+            id,
+        };
+        serde_json::to_string(&resp).unwrap_or_default()
+    }
 }
 
 impl From<RpcError> for JsonRpcError {
@@ -51,10 +196,15 @@ impl From<RpcError> for JsonRpcError {
     /// transmission over the wire.
     ///
     /// The conversion maps specific variants to standard JSON-RPC codes:
-    /// - `InvalidJsonRpcVersion` -> `-32600` (Invalid Request)
+    /// - `ParseError` -> `-32700`
+    /// - `InvalidJsonRpcVersion`, `InvalidRequest` -> `-32600` (Invalid Request)
     /// - `MethodNotFound` -> `-32601`
     /// - `InvalidParams` -> `-32602`
-    /// - Internal errors (IO/Reqwest/Serde) -> `-32000` to `-32002` (Server Error range)
+    /// - `InternalError` -> `-32603`
+    /// - Internal errors (IO/Reqwest/Serde/MessagePack) -> `-32000` to `-32002` (Server Error range)
+    /// - `Timeout` -> `-32004`
+    /// - `ApplicationError` -> its own code, message and data
+    #[allow(deprecated)]
     fn from(err: RpcError) -> Self {
         match err {
             RpcError::IoError(e) => JsonRpcError {
@@ -67,24 +217,57 @@ impl From<RpcError> for JsonRpcError {
                 message: e.to_string(),
                 data: None,
             },
+            #[cfg(not(feature = "path-errors"))]
             RpcError::SerdeError(e) => JsonRpcError {
                 code: -32002,
                 message: e.to_string(),
                 data: None,
             },
+            #[cfg(feature = "path-errors")]
+            RpcError::SerdeError { error, path } => JsonRpcError {
+                code: -32002,
+                message: format!("{}{}", error, at_path(&path)),
+                data: None,
+            },
+            #[cfg(feature = "msgpack")]
+            RpcError::MsgpackError(msg) => JsonRpcError {
+                code: -32002,
+                message: msg,
+                data: None,
+            },
+            RpcError::ParseError(msg) => JsonRpcError {
+                code: -32700,
+                message: format!("Parse error: {}", msg),
+                data: None,
+            },
             RpcError::InvalidJsonRpcVersion(v) => JsonRpcError {
                 code: -32600,
                 message: format!("Invalid JSON-RPC version: {}", v),
                 data: None,
             },
+            RpcError::InvalidRequest(msg) => JsonRpcError {
+                code: -32600,
+                message: format!("Invalid request: {}", msg),
+                data: None,
+            },
             RpcError::MethodNotFound => JsonRpcError {
                 code: -32601,
                 message: "method not found".to_string(),
                 data: None,
             },
+            RpcError::InternalError(msg) => JsonRpcError {
+                code: -32603,
+                message: format!("Internal error: {}", msg),
+                data: None,
+            },
+            RpcError::Timeout => JsonRpcError {
+                code: -32004,
+                message: "request timed out".to_string(),
+                data: None,
+            },
             RpcError::CustomError(msg) => JsonRpcError {
                 code: -32003,
-                message: msg,
+                message: msg.into_owned(),
                 data: None,
             },
             RpcError::InvalidParams(msg) => JsonRpcError {
@@ -92,6 +275,81 @@ impl From<RpcError> for JsonRpcError {
                 message: format!("Invalid parameters: {}", msg),
                 data: None,
             },
+            RpcError::ApplicationError {
+                code,
+                message,
+                data,
+            } => JsonRpcError {
+                code,
+                message,
+                data,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "path-errors")]
+impl From<serde_json::Error> for RpcError {
+    /// Wraps a JSON error without path information.
+    fn from(error: serde_json::Error) -> Self {
+        RpcError::SerdeError { error, path: None }
+    }
+}
+
+/// Formats a known error path as ` at <path>`, or nothing if it is unknown.
+#[cfg(feature = "path-errors")]
+fn at_path(path: &Option<String>) -> String {
+    path.as_ref()
+        .map(|path| format!(" at {}", path))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "msgpack")]
+impl From<rmp_serde::encode::Error> for RpcError {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        RpcError::MsgpackError(err.to_string())
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl From<rmp_serde::decode::Error> for RpcError {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        RpcError::MsgpackError(err.to_string())
+    }
+}
+
+impl From<tokio::time::error::Elapsed> for RpcError {
+    /// Maps an elapsed `tokio::time::timeout` into [`RpcError::Timeout`], so
+    /// timeout-guarded futures can be propagated with `?`.
+    fn from(_: tokio::time::error::Elapsed) -> Self {
+        RpcError::Timeout
+    }
+}
+
+impl From<JsonRpcError> for RpcError {
+    /// Converts a [`JsonRpcError`] received from a server back into an [`RpcError`].
+    ///
+    /// Standard codes are mapped to their matching variants:
+    /// - `-32700` -> `ParseError`
+    /// - `-32600` -> `InvalidRequest`
+    /// - `-32601` -> `MethodNotFound`
+    /// - `-32602` -> `InvalidParams`
+    /// - `-32603` -> `InternalError`
+    /// - `-32004` -> `Timeout`
+    /// - Any other code -> `ApplicationError` carrying the code, message and data
+    fn from(err: JsonRpcError) -> Self {
+        match err.code {
+            -32700 => RpcError::ParseError(err.message),
+            -32600 => RpcError::InvalidRequest(err.message),
+            -32601 => RpcError::MethodNotFound,
+            -32602 => RpcError::InvalidParams(err.message.into()),
+            -32603 => RpcError::InternalError(err.message),
+            -32004 => RpcError::Timeout,
+            code => RpcError::ApplicationError {
+                code,
+                message: err.message,
+                data: err.data,
+            },
         }
     }
 }
@@ -1,8 +1,8 @@
 //! # JSON-RPC Identifiers
 //!
 //! This module defines the [`Id`] enum, which represents the `id` field in JSON-RPC
-//! requests and responses. It supports both numeric and string identifiers as
-//! per the JSON-RPC 2.0 specification.
+//! requests and responses. It supports numeric and string identifiers as well as
+//! the `null` id of notifications, as per the JSON-RPC 2.0 specification.
 
 use serde::{Deserialize, Serialize};
 
@@ -16,7 +16,7 @@ static ATOMIC_U64_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU6
 ///
 /// ### Usage
 /// ```rust
-/// use a_rs_jsonrpc::Id;
+/// use a_rs_jsonrpc::id::Id;
 ///
 /// // Create from literal
 /// let id = Id::from(42);
@@ -24,14 +24,20 @@ static ATOMIC_U64_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU6
 /// // Generate automatic IDs
 /// let next = Id::next_number();
 /// ```
+///
+/// Identifiers are ordered numbers first, then strings, then `null`; numbers sort
+/// numerically and strings lexicographically.
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Id {
     /// A numeric identifier (integer).
     Number(u64),
     /// A string identifier.
     String(String),
+    /// The `null` identifier, carried by notifications and by error responses to
+    /// requests whose id could not be read.
+    Null,
 }
 
 impl From<u64> for Id {
@@ -40,6 +46,30 @@ impl From<u64> for Id {
     }
 }
 
+impl From<u32> for Id {
+    fn from(value: u32) -> Self {
+        Id::Number(value.into())
+    }
+}
+
+impl From<i64> for Id {
+    /// Converts a non-negative value into [`Id::Number`]. A negative value cannot be
+    /// held by it and becomes its decimal [`Id::String`] instead.
+    fn from(value: i64) -> Self {
+        match u64::try_from(value) {
+            Ok(value) => Id::Number(value),
+            Err(_) => Id::String(value.to_string()),
+        }
+    }
+}
+
+impl From<i32> for Id {
+    /// Converts like the `i64` conversion.
+    fn from(value: i32) -> Self {
+        Id::from(i64::from(value))
+    }
+}
+
 impl From<String> for Id {
     fn from(value: String) -> Self {
         Id::String(value)
@@ -52,7 +82,34 @@ impl From<&str> for Id {
     }
 }
 
+impl std::fmt::Display for Id {
+    /// Writes the number or the string value, unquoted, or `null`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Id::Number(n) => write!(f, "{}", n),
+            Id::String(s) => f.write_str(s),
+            Id::Null => f.write_str("null"),
+        }
+    }
+}
+
 impl Id {
+    /// Returns the numeric identifier, or `None` for a string or `null` id.
+    pub fn as_number(&self) -> Option<u64> {
+        match self {
+            Id::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the string identifier, or `None` for a numeric or `null` id.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Id::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
     /// Generates a unique numeric ID by incrementing a global atomic counter.
     ///
     /// This is the preferred method for generating IDs for new client requests
@@ -69,4 +126,34 @@ impl Id {
         let id = ATOMIC_U64_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         Id::String(format!("id-{}", id))
     }
+
+    /// Generates a time-ordered string ID from a UUID v7.
+    ///
+    /// UUID v7 values sort by creation time, which makes correlating requests
+    /// in logs easier than with random identifiers. Requires the `uuid` feature.
+    #[cfg(feature = "uuid")]
+    pub fn next_uuid_v7() -> Self {
+        Id::String(uuid::Uuid::now_v7().to_string())
+    }
+
+    /// Compares two identifiers by value, treating a numeric string as equal to
+    /// the matching number.
+    ///
+    /// The derived `PartialEq` is strict: `Id::Number(42) != Id::String("42")`, as the
+    /// specification treats them as distinct identifiers. Use this method instead when
+    /// a peer may encode the same id either way.
+    ///
+    /// ```rust
+    /// # use a_rs_jsonrpc::id::Id;
+    /// assert!(Id::Number(42).eq_value(&Id::from("42")));
+    /// assert!(!Id::Number(42).eq_value(&Id::from("id-42")));
+    /// ```
+    pub fn eq_value(&self, other: &Id) -> bool {
+        match (self, other) {
+            (Id::Number(n), Id::String(s)) | (Id::String(s), Id::Number(n)) => {
+                s.parse::<u64>().is_ok_and(|v| v == *n)
+            }
+            _ => self == other,
+        }
+    }
 }
@@ -42,7 +42,7 @@
 //!
 //! #### Defining an Interface with `rpc_method`
 //! ```rust
-//! use a_rs_jsonrpc::macros::rpc_method;
+//! use a_rs_jsonrpc::{JsonRpcResponse, RpcError, rpc_method};
 //!
 //! #[rpc_method(
 //!     url = "http://localhost:3000/",
@@ -54,14 +54,22 @@
 //! ```
 //!
 //! #### Using Trait Extensions
-//! ```rust
+//! ```rust,no_run
+//! # use a_rs_jsonrpc::{JsonRpcClient, JsonRpcResponse, RpcError};
+//! # use serde::Serialize;
+//! # const URL: &str = "http://localhost:3000/";
+//! # const APP_JSON: &str = "application/json";
+//! # async fn example() -> Result<(), RpcError> {
 //! // Call using a tuple (serialized as a JSON array)
-//! let resp = (10, 20).send_v2_request(URL, APP_JSON, "addArray").await?;
+//! let resp: JsonRpcResponse<i32> = (10, 20).send_v2_request(URL, APP_JSON, "addArray").await?;
 //!
 //! // Call using a struct (serialized as a JSON object)
-//! #[derive(Serialize)]
+//! #[derive(Clone, Serialize, JsonRpcClient)]
+//! #[jsonrpc(url = "http://localhost:3000/", content_type = "application/json", method = "addObj")]
 //! struct MyParams { lhs: i32, rhs: i32 }
-//! let resp = MyParams { lhs: 10, rhs: 20 }.send_v2_request_obj(URL, APP_JSON, "addObj").await?;
+//! let resp: JsonRpcResponse<i32> = MyParams { lhs: 10, rhs: 20 }.send_v2_request_obj(URL, APP_JSON, "addObj").await?;
+//! # Ok(())
+//! # }
 //! ```
 //!
 //! ---
@@ -93,29 +101,131 @@
 //!     .init();
 //! ```
 //!
+//! With the `opentelemetry` feature, clients send the current span's context as a
+//! `traceparent` header, and servers continue the trace by dispatching through
+//! [`dispatch_rpc_request_with_headers`]. Every handler also runs in a
+//! `jsonrpc.handler` span, continuing the trace a request carries in its
+//! `x-trace-context` field (see [`JsonRpcRequest::with_trace_context`]).
+//!
+//! With the `axum` feature, [`JsonRpcBody`] serves the registered methods from an Axum
+//! router: `Router::new().route("/", post(JsonRpcBody::handler))`.
+//!
+//! With the `caching` feature, [`CachingClient`] answers repeated calls from a
+//! cache of responses that expire after a TTL.
+//!
+//! With the `msgpack` feature, clients can send requests encoded as MessagePack through
+//! [`JsonRpcClient::send_v2_request_msgpack`] or `#[rpc_method(encoding = "msgpack")]`.
+//!
+//! With the `path-errors` feature, a request whose parameters fail to deserialize is
+//! answered with an error naming the offending value, e.g. `... at params.b`.
+//!
+//! With the `schema` feature, services declared with `schema = true` publish a JSON
+//! Schema of their parameters through [`get_rpc_schema`] and `rpc.getSchema`.
+//!
 
+#[cfg(feature = "axum")]
+pub mod axum;
 pub mod client;
 pub mod error;
 pub mod id;
+pub mod metrics;
+pub mod middleware;
+#[cfg(feature = "ws")]
+pub mod pending;
 pub mod request;
 pub mod response;
 pub mod service;
+#[cfg(feature = "stdio")]
+pub mod stdio;
+pub mod testing;
+#[cfg(feature = "tower")]
+pub mod tower;
+#[cfg(feature = "ws")]
+pub mod ws;
 
+#[cfg(feature = "axum")]
+pub use crate::axum::JsonRpcBody;
+#[cfg(feature = "tower")]
+pub use crate::tower::JsonRpcDispatcher;
+pub use a_rs_jsonrpc_macros::rpc_interface;
 pub use a_rs_jsonrpc_macros::rpc_method;
 pub use async_trait;
+pub use bytes;
+#[cfg(feature = "caching")]
+pub use client::CachingClient;
 pub use client::JsonRpcClient;
 pub use client::JsonRpcClientCall;
+pub use client::JsonRpcClientExt;
+pub use client::JsonRpcClientMetrics;
+pub use client::JsonRpcClientPool;
+pub use client::JsonRpcClientSession;
+pub use client::LoadBalancing;
+pub use client::RetryPolicy;
+pub use client::SharedClient;
 pub use error::RpcError;
+pub use futures;
 pub use id::Id as JsonRpcId;
 pub use linkme;
+pub use metrics::Metrics;
+pub use metrics::dispatch_with_metrics as dispatch_rpc_request_with_metrics;
+pub use middleware::AuthMiddleware;
+pub use middleware::LoggingMiddleware;
+pub use middleware::Middleware;
+pub use middleware::RateLimiter;
+pub use middleware::ValidationMiddleware;
+pub use middleware::dispatch_with_rate_limit as dispatch_rpc_request_with_rate_limit;
+#[cfg(feature = "ws")]
+pub use pending::PendingRequests;
 pub use request::JsonRpcRequest;
+pub use request::JsonRpcRequestBuilder;
+pub use request::Params as JsonRpcParams;
+pub use response::JsonRpcErrorKind;
+pub use response::JsonRpcRawResponse;
 pub use response::JsonRpcResponse;
+#[cfg(feature = "schema")]
+pub use schemars;
 pub use serde;
 pub use serde_json;
 pub use service::JsonRpcServiceFn;
+pub use service::ParamMode;
 pub use service::RPC_SERVICES;
+pub use service::RpcContext;
+pub use service::RpcMethodInfo;
 pub use service::RpcServiceEntry;
+pub use service::ServiceConfig;
+pub use service::describe as describe_rpc_method;
 pub use service::dispatch as dispatch_rpc_request;
+pub use service::dispatch_batch as dispatch_rpc_batch_request;
+pub use service::dispatch_bytes as dispatch_rpc_bytes;
+pub use service::dispatch_bytes_with_context as dispatch_rpc_bytes_with_context;
+pub use service::dispatch_from_method as dispatch_rpc_request_from_method;
+#[cfg(feature = "http")]
+pub use service::dispatch_http as dispatch_rpc_http;
+pub use service::dispatch_json_str as dispatch_rpc_json_str;
+pub use service::dispatch_raw as dispatch_rpc_request_raw;
+pub use service::dispatch_streaming as dispatch_rpc_request_streaming;
+pub use service::dispatch_to_writer as dispatch_rpc_request_to_writer;
+pub use service::dispatch_with_context as dispatch_rpc_request_with_context;
+#[cfg(feature = "opentelemetry")]
+pub use service::dispatch_with_headers as dispatch_rpc_request_with_headers;
+pub use service::dispatch_with_middleware as dispatch_rpc_request_with_middleware;
+pub use service::dispatch_with_middleware_and_headers as dispatch_rpc_request_with_middleware_and_headers;
+pub use service::dispatch_with_selector as dispatch_rpc_request_with_selector;
+pub use service::dispatch_with_timeout as dispatch_rpc_request_with_timeout;
+pub use service::generate_openrpc_document;
+pub use service::generate_openrpc_to_file;
+#[cfg(feature = "schema")]
+pub use service::get_schema as get_rpc_schema;
+pub use service::hot_reload_methods as hot_reload_rpc_methods;
 pub use service::init as init_rpc_service;
+pub use service::jsonrpc_service_fn;
 pub use service::jsonrpc_service_fn_array;
+pub use service::jsonrpc_service_fn_both;
 pub use service::jsonrpc_service_fn_obj;
+pub use service::list_methods as list_rpc_methods;
+pub use service::register_additional_handler as register_additional_rpc_handler;
+pub use service::set_fallback_handler as set_rpc_fallback_handler;
+pub use service::try_dispatch as try_dispatch_rpc_request;
+pub use tokio;
+#[cfg(feature = "ws")]
+pub use ws::WsJsonRpcClient;
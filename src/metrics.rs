@@ -0,0 +1,179 @@
+//! # Metrics Module
+//!
+//! This module provides [`Metrics`], a collector of per-method call counts, error
+//! counts and handler latencies, and [`dispatch_with_metrics()`], which records them
+//! around [`dispatch()`].
+//!
+//! With the `prometheus` feature, the collected values can be rendered in the
+//! Prometheus text exposition format.
+
+use crate::{
+    RpcError,
+    service::{MethodEnvelope, dispatch},
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+/// A Prometheus metric family: its name, help text and the counter it exports.
+#[cfg(feature = "prometheus")]
+type CounterFamily = (&'static str, &'static str, fn(&MethodMetrics) -> &AtomicU64);
+
+/// Counters of a single method.
+#[derive(Debug, Default)]
+pub struct MethodMetrics {
+    /// The number of dispatched calls.
+    pub calls: AtomicU64,
+    /// The number of calls that returned an error.
+    pub errors: AtomicU64,
+    /// The accumulated handler latency in microseconds.
+    pub total_latency_us: AtomicU64,
+}
+
+impl MethodMetrics {
+    /// Copies the current counter values.
+    fn copy(&self) -> Self {
+        MethodMetrics {
+            calls: AtomicU64::new(self.calls.load(Ordering::Relaxed)),
+            errors: AtomicU64::new(self.errors.load(Ordering::Relaxed)),
+            total_latency_us: AtomicU64::new(self.total_latency_us.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// A collector of per-method metrics, shared by all dispatches it is passed to.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::metrics::{Metrics, dispatch_with_metrics};
+/// # use std::sync::LazyLock;
+/// # use std::sync::atomic::Ordering;
+/// static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+///
+/// # async fn example(body: &[u8]) -> Result<(), a_rs_jsonrpc::RpcError> {
+/// let resp = dispatch_with_metrics(&body, &METRICS).await;
+/// let add_calls = METRICS.snapshot()["add"].calls.load(Ordering::Relaxed);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Metrics {
+    methods: Mutex<HashMap<String, Arc<MethodMetrics>>>,
+}
+
+impl Metrics {
+    /// Creates an empty collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the counters of `method`, registering them on first use.
+    fn method(&self, method: &str) -> Arc<MethodMetrics> {
+        let mut methods = self.methods.lock().unwrap();
+        if let Some(metrics) = methods.get(method) {
+            return metrics.clone();
+        }
+        let metrics = Arc::new(MethodMetrics::default());
+        methods.insert(method.to_string(), metrics.clone());
+        metrics
+    }
+
+    /// Returns a copy of the counters of every method called so far.
+    pub fn snapshot(&self) -> HashMap<String, MethodMetrics> {
+        self.methods
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(method, metrics)| (method.clone(), metrics.copy()))
+            .collect()
+    }
+
+    /// Discards all collected counters.
+    pub fn reset(&self) {
+        self.methods.lock().unwrap().clear();
+    }
+
+    /// Renders the counters in the Prometheus text exposition format.
+    ///
+    /// Three counters labelled by `method` are exported: `jsonrpc_calls_total`,
+    /// `jsonrpc_errors_total` and `jsonrpc_latency_microseconds_total`.
+    #[cfg(feature = "prometheus")]
+    pub fn to_prometheus_text(&self) -> String {
+        use std::fmt::Write;
+
+        let mut snapshot: Vec<(String, MethodMetrics)> = self.snapshot().into_iter().collect();
+        snapshot.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let families: [CounterFamily; 3] = [
+            (
+                "jsonrpc_calls_total",
+                "Total number of JSON-RPC calls.",
+                |m| &m.calls,
+            ),
+            (
+                "jsonrpc_errors_total",
+                "Total number of JSON-RPC calls that returned an error.",
+                |m| &m.errors,
+            ),
+            (
+                "jsonrpc_latency_microseconds_total",
+                "Accumulated JSON-RPC handler latency in microseconds.",
+                |m| &m.total_latency_us,
+            ),
+        ];
+        let mut out = String::new();
+        for (name, help, value) in families {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            for (method, metrics) in &snapshot {
+                let _ = writeln!(
+                    out,
+                    "{}{{method=\"{}\"}} {}",
+                    name,
+                    escape_label_value(method),
+                    value(metrics).load(Ordering::Relaxed)
+                );
+            }
+        }
+        out
+    }
+}
+
+/// Escapes a Prometheus label value.
+#[cfg(feature = "prometheus")]
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Dispatches a request like [`dispatch()`], recording its call count, error count
+/// and latency under its method name in `metrics`.
+///
+/// A body whose method cannot be read is not recorded.
+///
+/// # Errors
+/// Same as [`dispatch()`].
+pub async fn dispatch_with_metrics(body: &[u8], metrics: &Metrics) -> Result<String, RpcError> {
+    let method = match serde_json::from_slice::<MethodEnvelope>(body) {
+        Ok(MethodEnvelope { method, .. }) => metrics.method(method),
+        Err(_) => return dispatch(body).await,
+    };
+    let start = Instant::now();
+    let result = dispatch(body).await;
+    let elapsed = u64::try_from(start.elapsed().as_micros()).unwrap_or(u64::MAX);
+    method.calls.fetch_add(1, Ordering::Relaxed);
+    method
+        .total_latency_us
+        .fetch_add(elapsed, Ordering::Relaxed);
+    if result.is_err() {
+        method.errors.fetch_add(1, Ordering::Relaxed);
+    }
+    result
+}
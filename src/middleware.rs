@@ -0,0 +1,319 @@
+//! # Middleware Module
+//!
+//! This module provides building blocks that wrap [`dispatch()`] with cross-cutting
+//! policies:
+//! - [`RateLimiter`], a token-bucket rate limiter, and [`dispatch_with_rate_limit()`],
+//!   which applies it before routing.
+//! - The [`Middleware`] trait, run around the dispatcher by
+//!   [`crate::service::dispatch_with_middleware()`], with the built-in
+//!   [`LoggingMiddleware`], [`AuthMiddleware`] and [`ValidationMiddleware`].
+
+use crate::{
+    RpcError,
+    request::{JsonRpcRequest, JsonRpcVersion},
+    response::{JsonRpcError, JsonRpcResponse},
+    service::{MethodEnvelope, dispatch},
+};
+use serde::Deserialize;
+use std::{collections::HashMap, str::FromStr, sync::Mutex, time::Instant};
+
+/// The JSON-RPC error code returned for requests rejected by a [`RateLimiter`].
+pub const RATE_LIMIT_EXCEEDED_CODE: i64 = -32029;
+
+/// A token bucket holding up to `capacity` tokens, refilled continuously.
+#[derive(Debug)]
+struct TokenBucket {
+    capacity: f64,
+    refill_per_second: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: u32, refill_per_second: u32) -> Self {
+        TokenBucket {
+            capacity: f64::from(capacity),
+            refill_per_second: f64::from(refill_per_second),
+            tokens: f64::from(capacity),
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes one token, returning `false` if the bucket is empty.
+    fn try_acquire(&mut self) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_second).min(self.capacity);
+        self.last_refill = now;
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+/// A token-bucket rate limiter for [`dispatch_with_rate_limit()`].
+///
+/// A limiter built with [`RateLimiter::new`] shares one bucket between all methods;
+/// one built with [`RateLimiter::per_method`] keeps a bucket per listed method and
+/// leaves other methods unlimited. Bucket state is guarded by a [`Mutex`], so one
+/// limiter can be shared by all request handlers.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::RpcError;
+/// # use a_rs_jsonrpc::middleware::{RateLimiter, dispatch_with_rate_limit};
+/// # use std::sync::LazyLock;
+/// static LIMITER: LazyLock<RateLimiter> = LazyLock::new(|| RateLimiter::new(100, 20));
+///
+/// # async fn example(body: &[u8]) -> Result<(), RpcError> {
+/// let resp = dispatch_with_rate_limit(&body, &LIMITER).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RateLimiter {
+    global: Option<Mutex<TokenBucket>>,
+    per_method: HashMap<String, Mutex<TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing bursts of `capacity` requests, refilled by
+    /// `refill_per_second` requests per second.
+    pub fn new(capacity: u32, refill_per_second: u32) -> Self {
+        RateLimiter {
+            global: Some(Mutex::new(TokenBucket::new(capacity, refill_per_second))),
+            per_method: HashMap::new(),
+        }
+    }
+
+    /// Creates a limiter with a separate `(capacity, refill_per_second)` bucket for
+    /// each listed method. Methods missing from `map` are not limited.
+    pub fn per_method(map: HashMap<&str, (u32, u32)>) -> Self {
+        RateLimiter {
+            global: None,
+            per_method: map
+                .into_iter()
+                .map(|(method, (capacity, refill_per_second))| {
+                    (
+                        method.to_string(),
+                        Mutex::new(TokenBucket::new(capacity, refill_per_second)),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Takes a token for a request to `method`, returning `false` if the request
+    /// must be rejected.
+    pub fn try_acquire(&self, method: Option<&str>) -> bool {
+        if let Some(global) = &self.global {
+            return global.lock().unwrap().try_acquire();
+        }
+        match method.and_then(|method| self.per_method.get(method)) {
+            Some(bucket) => bucket.lock().unwrap().try_acquire(),
+            None => true,
+        }
+    }
+}
+
+/// Dispatches a request like [`dispatch()`] if `limiter` has a token for it.
+///
+/// A rejected request never reaches its handler; instead a JSON-RPC error response
+/// with code [`RATE_LIMIT_EXCEEDED_CODE`] and message `"rate limit exceeded"` is
+/// returned.
+///
+/// # Errors
+/// Same as [`dispatch()`].
+pub async fn dispatch_with_rate_limit(
+    body: &[u8],
+    limiter: &RateLimiter,
+) -> Result<String, RpcError> {
+    let method = serde_json::from_slice::<MethodEnvelope>(body)
+        .ok()
+        .map(|envelope| envelope.method);
+    if limiter.try_acquire(method) {
+        return dispatch(body).await;
+    }
+    tracing::warn!("rate limit exceeded for method {:?}", method);
+    let error = JsonRpcError {
+        code: RATE_LIMIT_EXCEEDED_CODE,
+        message: "rate limit exceeded".to_string(),
+        data: None,
+    };
+    let response = match serde_json::from_slice::<JsonRpcRequest<serde_json::Value>>(body) {
+        Ok(request) => serde_json::to_string(&JsonRpcResponse::<()> {
+            jsonrpc: request.jsonrpc,
+            result: None,
+            error: Some(error),
+            id: request.id,
+        })?,
+        Err(_) => serde_json::to_string(&serde_json::json!({
+            "jsonrpc": "2.0",
+            "error": error,
+            "id": null,
+        }))?,
+    };
+    Ok(response)
+}
+
+/// A hook run around the dispatcher by [`crate::service::dispatch_with_middleware()`].
+///
+/// `before` hooks run in order before routing and may reject the request by
+/// returning an error. `after` hooks run in reverse order and may replace the
+/// response text.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::service::dispatch_with_middleware;
+/// # use a_rs_jsonrpc::{LoggingMiddleware, Middleware, RpcError, ValidationMiddleware};
+/// # use std::sync::Arc;
+/// # async fn example(body: &[u8]) -> Result<(), RpcError> {
+/// let middlewares: Vec<Arc<dyn Middleware>> = vec![
+///     Arc::new(LoggingMiddleware::new()),
+///     Arc::new(ValidationMiddleware),
+/// ];
+/// let resp = dispatch_with_middleware(&body, &middlewares).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait::async_trait]
+pub trait Middleware: Send + Sync {
+    /// Inspects the raw request before it is routed.
+    async fn before(&self, req: &[u8]) -> Result<(), RpcError>;
+
+    /// Inspects the request together with the transport headers supplied to
+    /// [`crate::service::dispatch_with_middleware_and_headers()`].
+    ///
+    /// The default implementation ignores the headers and calls [`Middleware::before`].
+    async fn before_with_headers(
+        &self,
+        req: &[u8],
+        _headers: &HashMap<String, String>,
+    ) -> Result<(), RpcError> {
+        self.before(req).await
+    }
+
+    /// Inspects the response text, returning the text handed to the next hook.
+    async fn after(&self, req: &[u8], resp: &str) -> Result<String, RpcError>;
+}
+
+/// Logs the method and latency of every request at `info` level.
+///
+/// Requests are matched between `before` and `after` by the address of the
+/// request buffer, which stays borrowed for the whole dispatch.
+#[derive(Debug, Default)]
+pub struct LoggingMiddleware {
+    started: Mutex<Vec<(usize, Instant)>>,
+}
+
+impl LoggingMiddleware {
+    /// Creates a logging middleware.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for LoggingMiddleware {
+    async fn before(&self, req: &[u8]) -> Result<(), RpcError> {
+        self.started
+            .lock()
+            .unwrap()
+            .push((req.as_ptr() as usize, Instant::now()));
+        Ok(())
+    }
+
+    async fn after(&self, req: &[u8], resp: &str) -> Result<String, RpcError> {
+        let started = {
+            let mut started = self.started.lock().unwrap();
+            started
+                .iter()
+                .rposition(|(addr, _)| *addr == req.as_ptr() as usize)
+                .map(|i| started.remove(i).1)
+        };
+        let method = serde_json::from_slice::<MethodEnvelope>(req)
+            .map(|envelope| envelope.method)
+            .unwrap_or_default();
+        match started {
+            Some(started) => tracing::info!("jsonrpc {} took {:?}", method, started.elapsed()),
+            None => tracing::info!("jsonrpc {} finished", method),
+        }
+        Ok(resp.to_string())
+    }
+}
+
+/// Rejects requests whose `Authorization` header does not carry the given token.
+///
+/// The header may hold the token as is or as `Bearer <token>`; its name is matched
+/// case-insensitively. Headers are only available through
+/// [`crate::service::dispatch_with_middleware_and_headers()`], so every request
+/// dispatched without them is rejected.
+#[derive(Debug, Clone)]
+pub struct AuthMiddleware(pub String);
+
+#[async_trait::async_trait]
+impl Middleware for AuthMiddleware {
+    async fn before(&self, _req: &[u8]) -> Result<(), RpcError> {
+        Err(RpcError::InvalidRequest(
+            "missing Authorization header".to_string(),
+        ))
+    }
+
+    async fn before_with_headers(
+        &self,
+        _req: &[u8],
+        headers: &HashMap<String, String>,
+    ) -> Result<(), RpcError> {
+        let Some(value) = headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("authorization"))
+            .map(|(_, value)| value.trim())
+        else {
+            return Err(RpcError::InvalidRequest(
+                "missing Authorization header".to_string(),
+            ));
+        };
+        let token = value.strip_prefix("Bearer ").unwrap_or(value).trim();
+        if token != self.0 {
+            return Err(RpcError::InvalidRequest("unauthorized".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn after(&self, _req: &[u8], resp: &str) -> Result<String, RpcError> {
+        Ok(resp.to_string())
+    }
+}
+
+/// Envelope used to peek at the `jsonrpc` field of a request.
+#[derive(Deserialize)]
+struct VersionEnvelope<'a> {
+    #[serde(borrow)]
+    jsonrpc: Option<&'a str>,
+}
+
+/// Rejects requests whose `jsonrpc` field is missing or not `"1.0"` / `"2.0"`
+/// before they are routed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ValidationMiddleware;
+
+#[async_trait::async_trait]
+impl Middleware for ValidationMiddleware {
+    async fn before(&self, req: &[u8]) -> Result<(), RpcError> {
+        let VersionEnvelope { jsonrpc } = serde_json::from_slice(req)
+            .map_err(|e| RpcError::InvalidRequest(format!("malformed request: {}", e)))?;
+        let Some(jsonrpc) = jsonrpc else {
+            return Err(RpcError::InvalidJsonRpcVersion(
+                "missing jsonrpc field".to_string(),
+            ));
+        };
+        JsonRpcVersion::from_str(jsonrpc)?;
+        Ok(())
+    }
+
+    async fn after(&self, _req: &[u8], resp: &str) -> Result<String, RpcError> {
+        Ok(resp.to_string())
+    }
+}
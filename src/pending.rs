@@ -0,0 +1,109 @@
+//! # Pending Requests
+//!
+//! This module provides [`PendingRequests`], the table a multiplexing client keeps of
+//! the calls still waiting for a response. Each call registers a oneshot channel under
+//! its request id; the task reading responses resolves the channel whose id matches.
+//!
+//! Requires the `ws` feature.
+
+use crate::JsonRpcId;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+
+/// A waiting call: the channel receiving the raw response text and when it was sent.
+#[derive(Debug)]
+struct Pending {
+    tx: oneshot::Sender<String>,
+    sent_at: Instant,
+}
+
+/// The calls of a multiplexed connection that are waiting for their response.
+///
+/// Dropping an entry without resolving it closes its channel, so the waiting call
+/// observes an error instead of hanging.
+///
+/// ### Example
+/// ```rust
+/// # use a_rs_jsonrpc::{JsonRpcId, JsonRpcRequest, PendingRequests};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let request: JsonRpcRequest<()> = JsonRpcRequest::new_v2(JsonRpcId::from(1), "ping");
+/// # let (response_id, text) = (JsonRpcId::from(1), String::from(r#"{"jsonrpc":"2.0","result":"pong","id":1}"#));
+/// let mut pending = PendingRequests::new();
+/// let rx = pending.insert(request.id.clone());
+/// // ... later, in the reader task
+/// pending.resolve(&response_id, text);
+/// let text = rx.await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct PendingRequests {
+    calls: HashMap<JsonRpcId, Pending>,
+}
+
+impl PendingRequests {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a call waiting for the response with `id`, returning the receiver
+    /// of its raw response text.
+    ///
+    /// A call already registered under the same id is replaced and its receiver closed.
+    pub fn insert(&mut self, id: JsonRpcId) -> oneshot::Receiver<String> {
+        let (tx, rx) = oneshot::channel();
+        self.calls.insert(
+            id,
+            Pending {
+                tx,
+                sent_at: Instant::now(),
+            },
+        );
+        rx
+    }
+
+    /// Hands `response` to the call waiting for `id`.
+    ///
+    /// Returns `false` if no call is waiting for `id` or the caller has gone away.
+    pub fn resolve(&mut self, id: &JsonRpcId, response: String) -> bool {
+        match self.calls.remove(id) {
+            Some(pending) => pending.tx.send(response).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Forgets the call waiting for `id`, closing its receiver.
+    pub fn remove(&mut self, id: &JsonRpcId) {
+        self.calls.remove(id);
+    }
+
+    /// Drops every call registered more than `timeout` ago, closing their receivers.
+    ///
+    /// Returns the number of calls dropped.
+    pub fn cleanup_timed_out(&mut self, timeout: Duration) -> usize {
+        let before = self.calls.len();
+        self.calls
+            .retain(|_, pending| pending.sent_at.elapsed() < timeout);
+        before - self.calls.len()
+    }
+
+    /// Drops every waiting call, closing all receivers.
+    pub fn clear(&mut self) {
+        self.calls.clear();
+    }
+
+    /// Returns the number of waiting calls.
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Returns `true` if no call is waiting.
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+}
@@ -13,7 +13,7 @@ use std::str::FromStr;
 /// This enum ensures that the `jsonrpc` field is serialized correctly as `"1.0"` or `"2.0"`.
 /// It also provides validation during deserialization and string parsing.
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum JsonRpcVersion {
     /// Version 1.0 of the JSON-RPC specification.
     V1_0,
@@ -21,15 +21,28 @@ pub enum JsonRpcVersion {
     V2_0,
 }
 
+impl JsonRpcVersion {
+    /// Returns the version as it appears in the `jsonrpc` member: `"1.0"` or `"2.0"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JsonRpcVersion::V1_0 => "1.0",
+            JsonRpcVersion::V2_0 => "2.0",
+        }
+    }
+}
+
+impl std::fmt::Display for JsonRpcVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl Serialize for JsonRpcVersion {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        match self {
-            JsonRpcVersion::V1_0 => serializer.serialize_str("1.0"),
-            JsonRpcVersion::V2_0 => serializer.serialize_str("2.0"),
-        }
+        serializer.serialize_str(self.as_str())
     }
 }
 
@@ -59,48 +72,230 @@ impl FromStr for JsonRpcVersion {
     }
 }
 
+impl TryFrom<&str> for JsonRpcVersion {
+    type Error = RpcError;
+
+    /// Parses the version like [`FromStr`].
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// The `params` member of a request, distinguishing a missing key from an explicit `null`.
+///
+/// Some servers treat `{"params": null}` differently from a request without `params`,
+/// so the two are kept apart instead of collapsing into `Option::None`.
+///
+/// | Variant | Serialized as |
+/// | :--- | :--- |
+/// | `Absent` | no `params` key |
+/// | `Null` | `"params": null` |
+/// | `Present(v)` | `"params": v` |
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Params<T> {
+    /// The request has no `params` key.
+    #[default]
+    Absent,
+    /// The request carries `"params": null`.
+    Null,
+    /// The request carries the given parameters.
+    Present(T),
+}
+
+impl<T> Params<T> {
+    /// Returns `true` if the `params` key is missing.
+    pub fn is_absent(&self) -> bool {
+        matches!(self, Params::Absent)
+    }
+
+    /// Returns a reference to the parameters, if present.
+    pub fn as_option(&self) -> Option<&T> {
+        match self {
+            Params::Present(params) => Some(params),
+            _ => None,
+        }
+    }
+
+    /// Returns the parameters, if present, discarding the absent/null distinction.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Params::Present(params) => Some(params),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<Option<T>> for Params<T> {
+    /// Maps `None` to [`Params::Absent`].
+    fn from(params: Option<T>) -> Self {
+        params.map_or(Params::Absent, Params::Present)
+    }
+}
+
+impl<T: Serialize> Serialize for Params<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            Params::Present(params) => params.serialize(serializer),
+            // `Absent` is skipped by `JsonRpcRequest`; standalone it serializes as null.
+            Params::Absent | Params::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Params<T> {
+    /// A missing key is handled by `#[serde(default)]`; a present `null` becomes
+    /// [`Params::Null`].
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(Option::<T>::deserialize(deserializer)?.map_or(Params::Null, Params::Present))
+    }
+}
+
 /// A standard JSON-RPC request object.
 ///
 /// `T` represents the type of the `params` field, which is typically a collection
 /// (like a `Vec` or a `Tuple`) or a named object (struct).
-#[derive(Debug, Serialize)]
+///
+/// The request can also be deserialized, which is useful for inspecting raw
+/// request bytes on the server side or in proxy code. A missing `params` key
+/// is deserialized as [`Params::Absent`] and an explicit `null` as [`Params::Null`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(deserialize = "T: serde::de::DeserializeOwned"))]
 pub struct JsonRpcRequest<T> {
     /// The version of the JSON-RPC protocol.
     pub jsonrpc: JsonRpcVersion,
     /// A string containing the name of the method to be invoked.
     pub method: String,
     /// A structured value that holds the parameter values to be used during the
-    /// invocation of the method. This field is omitted if `None`.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub params: Option<T>,
+    /// invocation of the method. This field is omitted if [`Params::Absent`].
+    #[serde(default, skip_serializing_if = "Params::is_absent")]
+    pub params: Params<T>,
     /// An identifier established by the client.
     pub id: JsonRpcId,
+    /// Extension fields, written at the top level of the request next to the
+    /// standard members.
+    ///
+    /// They are not read back when a request is deserialized: collecting unknown
+    /// keys would buffer the whole request, which `RawValue` params cannot survive.
+    #[serde(flatten, skip_deserializing)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
 }
 
+/// The members defined by the specification, which extension fields must not shadow.
+const RESERVED_MEMBERS: [&str; 4] = ["jsonrpc", "method", "params", "id"];
+
+/// The extension member carrying the caller's trace context, as an object of
+/// propagation fields such as `traceparent`. Requires the `opentelemetry` feature.
+#[cfg(feature = "opentelemetry")]
+pub const TRACE_CONTEXT_MEMBER: &str = "x-trace-context";
+
 impl<T> JsonRpcRequest<T> {
     /// Creates a new JSON-RPC 1.0 request with no parameters.
+    ///
+    /// Deprecated in favour of [`JsonRpcRequestBuilder::v1`], which validates the
+    /// method name. Kept for compatibility with existing call sites and macros.
     pub fn new_v1(id: JsonRpcId, method: &str) -> Self {
         JsonRpcRequest {
             jsonrpc: JsonRpcVersion::V1_0,
             method: method.to_string(),
-            params: None,
+            params: Params::Absent,
             id,
+            extensions: serde_json::Map::new(),
         }
     }
 
     /// Creates a new JSON-RPC 2.0 request with no parameters.
+    ///
+    /// Deprecated in favour of [`JsonRpcRequestBuilder::v2`], which validates the
+    /// method name. Kept for compatibility with existing call sites and macros.
     pub fn new_v2(id: JsonRpcId, method: &str) -> Self {
         JsonRpcRequest {
             jsonrpc: JsonRpcVersion::V2_0,
             method: method.to_string(),
-            params: None,
+            params: Params::Absent,
             id,
+            extensions: serde_json::Map::new(),
         }
     }
 
     /// Attaches parameters to the request.
     pub fn set_params(&mut self, params: T) {
-        self.params = Some(params);
+        self.params = Params::Present(params);
+    }
+
+    /// Removes the `params` key from the request.
+    pub fn no_params(mut self) -> Self {
+        self.params = Params::Absent;
+        self
+    }
+
+    /// Sends `"params": null` with the request.
+    pub fn null_params(mut self) -> Self {
+        self.params = Params::Null;
+        self
+    }
+
+    /// Attaches parameters to the request, returning it for chaining.
+    pub fn with_params(mut self, params: T) -> Self {
+        self.params = Params::Present(params);
+        self
+    }
+
+    /// Adds an extension field, returning the request for chaining.
+    ///
+    /// A key naming one of the standard members, or a value that fails to serialize,
+    /// is skipped with a warning.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use a_rs_jsonrpc::{JsonRpcId as Id, JsonRpcRequest};
+    /// let req = JsonRpcRequest::<()>::new_v2(Id::from(1), "ping").with_ext("x-trace-id", "abc");
+    /// // {"jsonrpc":"2.0","method":"ping","id":1,"x-trace-id":"abc"}
+    /// ```
+    pub fn with_ext<E: Serialize>(mut self, key: &str, value: E) -> Self {
+        if RESERVED_MEMBERS.contains(&key) {
+            tracing::warn!("extension field {} shadows a standard member, skipped", key);
+            return self;
+        }
+        match serde_json::to_value(value) {
+            Ok(value) => {
+                self.extensions.insert(key.to_string(), value);
+            }
+            Err(e) => tracing::warn!("extension field {} failed to serialize: {}", key, e),
+        }
+        self
+    }
+
+    /// Attaches the context of the current `tracing` span as the
+    /// [`TRACE_CONTEXT_MEMBER`] extension field, for transports without headers.
+    ///
+    /// The context is injected with the globally configured OpenTelemetry
+    /// propagator; nothing is attached if it produces no fields. Requires the
+    /// `opentelemetry` feature.
+    #[cfg(feature = "opentelemetry")]
+    pub fn with_trace_context(self) -> Self {
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let cx = tracing::Span::current().context();
+        let mut fields = std::collections::HashMap::<String, String>::new();
+        opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(&cx, &mut fields)
+        });
+        if fields.is_empty() {
+            return self;
+        }
+        self.with_ext(TRACE_CONTEXT_MEMBER, fields)
+    }
+
+    /// Returns `true` if the request is a notification, i.e. its id is `null` and
+    /// the server sends no response.
+    pub fn is_notification(&self) -> bool {
+        self.id == JsonRpcId::Null
     }
 }
 
@@ -111,6 +306,7 @@ impl JsonRpcRequest<Vec<serde_json::Value>> {
     ///
     /// # Example
     /// ```rust
+    /// # use a_rs_jsonrpc::{JsonRpcId as Id, JsonRpcRequest};
     /// let mut req = JsonRpcRequest::new_v2(Id::from(1), "add");
     /// req.add_param(10);
     /// req.add_param(20);
@@ -120,13 +316,214 @@ impl JsonRpcRequest<Vec<serde_json::Value>> {
         P: Serialize,
         serde_json::Value: From<P>,
     {
-        if self.params.is_none() {
-            self.params = Some(vec![serde_json::Value::from(param)]);
-            return;
-        }
-        self.params
-            .as_mut()
-            .unwrap()
-            .push(serde_json::Value::from(param));
+        match &mut self.params {
+            Params::Present(params) => params.push(serde_json::Value::from(param)),
+            _ => self.params = Params::Present(vec![serde_json::Value::from(param)]),
+        }
+    }
+}
+
+impl JsonRpcRequest<serde_json::Map<String, serde_json::Value>> {
+    /// Creates a new JSON-RPC 2.0 request with named parameters, starting from an
+    /// empty `params` object.
+    pub fn new_v2_obj(id: JsonRpcId, method: &str) -> Self {
+        JsonRpcRequest {
+            params: Params::Present(serde_json::Map::new()),
+            ..JsonRpcRequest::new_v2(id, method)
+        }
+    }
+
+    /// Dynamically adds a named parameter to a request that uses an object of values.
+    ///
+    /// The object-mode counterpart of `add_param`. A value that fails to serialize
+    /// is skipped with a warning.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use a_rs_jsonrpc::{JsonRpcId as Id, JsonRpcRequest};
+    /// let mut req = JsonRpcRequest::new_v2_obj(Id::from(1), "addObj");
+    /// req.add_named_param("lhs", 10);
+    /// req.add_named_param("rhs", 20);
+    /// ```
+    pub fn add_named_param<P: Serialize>(&mut self, key: &str, value: P) {
+        let value = match serde_json::to_value(value) {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::warn!("named param {} failed to serialize: {}", key, e);
+                return;
+            }
+        };
+        match &mut self.params {
+            Params::Present(params) => {
+                params.insert(key.to_string(), value);
+            }
+            _ => {
+                self.params =
+                    Params::Present(serde_json::Map::from_iter([(key.to_string(), value)]))
+            }
+        }
+    }
+}
+
+impl JsonRpcRequest<serde_json::Value> {
+    /// Serializes the request into a `reqwest::Body`.
+    ///
+    /// This avoids the generic `Serialize` bound of `RequestBuilder::json`, e.g. when
+    /// the request was built dynamically. The `Content-Type` header must be set by
+    /// the caller.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// # use a_rs_jsonrpc::JsonRpcRequest;
+    /// # async fn example(client: reqwest::Client, url: &str, request: JsonRpcRequest<serde_json::Value>) -> Result<(), reqwest::Error> {
+    /// let resp = client
+    ///     .post(url)
+    ///     .header("Content-Type", "application/json")
+    ///     .body(request.into_reqwest_body())
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_reqwest_body(self) -> reqwest::Body {
+        // a request holding only `serde_json::Value`s always serializes
+        reqwest::Body::from(serde_json::to_vec(&self).unwrap_or_default())
+    }
+}
+
+impl JsonRpcRequest<Box<serde_json::value::RawValue>> {
+    /// Creates a JSON-RPC 2.0 request whose params are kept as unparsed JSON text.
+    ///
+    /// Requests of this type can also be deserialized, leaving `params` uninterpreted,
+    /// so proxies and logging middleware can forward them without a round-trip
+    /// through concrete types.
+    ///
+    /// # Errors
+    /// Returns [`RpcError::SerdeError`] if `raw_params` is not valid JSON.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use a_rs_jsonrpc::{JsonRpcId as Id, JsonRpcRequest, RpcError};
+    /// # fn main() -> Result<(), RpcError> {
+    /// let req = JsonRpcRequest::new_v2_raw(Id::from(1), "add", "[10, 20]")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new_v2_raw(id: JsonRpcId, method: &str, raw_params: &str) -> Result<Self, RpcError> {
+        let params = serde_json::value::RawValue::from_string(raw_params.to_string())?;
+        Ok(JsonRpcRequest::new_v2(id, method).with_params(params))
+    }
+}
+
+/// Lets a request be deserialized into another type, e.g. the request struct of a
+/// proxied service, through its JSON representation.
+///
+/// A request that fails to serialize yields `null` with a warning, so the
+/// deserialization fails with an "invalid type" error.
+///
+/// # Example
+/// ```rust
+/// # use a_rs_jsonrpc::{JsonRpcId as Id, JsonRpcRequest};
+/// use serde::{Deserialize, de::IntoDeserializer};
+///
+/// # fn main() -> Result<(), serde_json::Error> {
+/// let req: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v2(Id::from(1), "add");
+/// let value = serde_json::Value::deserialize(req.into_deserializer())?;
+/// # Ok(())
+/// # }
+/// ```
+impl<'de, T: Serialize> serde::de::IntoDeserializer<'de, serde_json::Error> for JsonRpcRequest<T> {
+    type Deserializer = serde_json::Value;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        serde_json::to_value(&self).unwrap_or_else(|e| {
+            tracing::warn!("request {} failed to serialize: {}", self.method, e);
+            serde_json::Value::Null
+        })
+    }
+}
+
+/// A fluent builder for [`JsonRpcRequest`].
+///
+/// This is the recommended way to construct requests outside of the procedural
+/// macros. If no `id` is supplied, one is generated with [`JsonRpcId::next_number`].
+///
+/// # Example
+/// ```rust
+/// # use a_rs_jsonrpc::{JsonRpcRequestBuilder, RpcError};
+/// # fn main() -> Result<(), RpcError> {
+/// let req = JsonRpcRequestBuilder::v2("add")
+///     .add_param(serde_json::json!(10))
+///     .add_param(serde_json::json!(20))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct JsonRpcRequestBuilder<T> {
+    jsonrpc: JsonRpcVersion,
+    method: String,
+    params: Option<T>,
+    id: Option<JsonRpcId>,
+}
+
+impl<T> JsonRpcRequestBuilder<T> {
+    /// Starts building a JSON-RPC 1.0 request for `method`.
+    pub fn v1(method: &str) -> Self {
+        JsonRpcRequestBuilder {
+            jsonrpc: JsonRpcVersion::V1_0,
+            method: method.to_string(),
+            params: None,
+            id: None,
+        }
+    }
+
+    /// Starts building a JSON-RPC 2.0 request for `method`.
+    pub fn v2(method: &str) -> Self {
+        JsonRpcRequestBuilder {
+            jsonrpc: JsonRpcVersion::V2_0,
+            method: method.to_string(),
+            params: None,
+            id: None,
+        }
+    }
+
+    /// Sets an explicit request identifier.
+    pub fn id(mut self, id: JsonRpcId) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets the request parameters, replacing any previously set value.
+    pub fn params(mut self, params: T) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// Finishes the request.
+    ///
+    /// # Errors
+    /// Returns [`RpcError::InvalidRequest`] if the method name is empty.
+    pub fn build(self) -> Result<JsonRpcRequest<T>, RpcError> {
+        if self.method.is_empty() {
+            return Err(RpcError::InvalidRequest(
+                "method cannot be empty".to_string(),
+            ));
+        }
+        Ok(JsonRpcRequest {
+            jsonrpc: self.jsonrpc,
+            method: self.method,
+            params: Params::from(self.params),
+            id: self.id.unwrap_or_else(JsonRpcId::next_number),
+            extensions: serde_json::Map::new(),
+        })
+    }
+}
+
+impl JsonRpcRequestBuilder<Vec<serde_json::Value>> {
+    /// Appends a positional parameter to the request.
+    pub fn add_param(mut self, param: serde_json::Value) -> Self {
+        self.params.get_or_insert_with(Vec::new).push(param);
+        self
     }
 }
@@ -4,7 +4,10 @@
 //! A response is returned by the server upon receiving a request (except for notifications)
 //! and contains either the successful result of the invocation or an error object.
 
-use crate::{JsonRpcId, request::JsonRpcVersion};
+use crate::{
+    JsonRpcId, RpcError,
+    request::{JsonRpcRequest, JsonRpcVersion},
+};
 use serde::{Deserialize, Serialize};
 
 /// A standard JSON-RPC response object.
@@ -32,7 +35,7 @@ use serde::{Deserialize, Serialize};
 /// }
 /// ```
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonRpcResponse<T> {
     /// The version of the JSON-RPC protocol.
     pub jsonrpc: JsonRpcVersion,
@@ -51,11 +54,128 @@ pub struct JsonRpcResponse<T> {
     pub id: JsonRpcId,
 }
 
+impl<T> JsonRpcResponse<T> {
+    /// Converts the response into a `Result`, surfacing a server-side error
+    /// object as an [`RpcError`].
+    ///
+    /// # Errors
+    /// Returns the converted `error` object if present, or
+    /// [`RpcError::CustomError`] if the response carries neither a result nor an error.
+    #[allow(deprecated)]
+    pub fn into_result(self) -> Result<T, RpcError> {
+        if let Some(err) = self.error {
+            return Err(err.into());
+        }
+        self.result.ok_or_else(|| {
+            RpcError::CustomError("response contains neither result nor error".into())
+        })
+    }
+
+    /// Returns `true` if this is the response to `req`, i.e. both carry the same id.
+    ///
+    /// The comparison is strict, like the derived `PartialEq` of [`JsonRpcId`]; use
+    /// [`JsonRpcId::eq_value`] for peers that re-encode numeric ids as strings.
+    pub fn correlates_to<U>(&self, req: &JsonRpcRequest<U>) -> bool {
+        self.id == req.id
+    }
+
+    /// Splits a list result into one response per item, for consumers that expect
+    /// per-item responses.
+    ///
+    /// `f` turns the result into its items. Every page inherits `jsonrpc`, `id` and
+    /// `error` from this response. A response without a result yields a single page
+    /// with no result, so the error is not lost.
+    pub fn page<U, F: FnOnce(T) -> Vec<U>>(self, f: F) -> Vec<JsonRpcResponse<U>> {
+        let Some(result) = self.result else {
+            return vec![JsonRpcResponse {
+                jsonrpc: self.jsonrpc,
+                result: None,
+                error: self.error,
+                id: self.id,
+            }];
+        };
+        f(result)
+            .into_iter()
+            .map(|item| JsonRpcResponse {
+                jsonrpc: self.jsonrpc,
+                result: Some(item),
+                error: self.error.clone(),
+                id: self.id.clone(),
+            })
+            .collect()
+    }
+}
+
+impl<T> JsonRpcResponse<Vec<T>> {
+    /// Turns a list result into an iterator over its items, each wrapped in `Ok`.
+    ///
+    /// A response carrying an `error` object yields that error as its only item, and
+    /// a response with neither a `result` nor an `error` yields nothing.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use a_rs_jsonrpc::JsonRpcResponse;
+    /// # use a_rs_jsonrpc::response::JsonRpcError;
+    /// # fn main() -> Result<(), JsonRpcError> {
+    /// # let resp: JsonRpcResponse<Vec<String>> = serde_json::from_str(r#"{"jsonrpc":"2.0","result":["write docs"],"id":1}"#).unwrap();
+    /// for todo in resp.into_items() {
+    ///     println!("{}", todo?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_items(self) -> impl Iterator<Item = Result<T, JsonRpcError>> {
+        let (error, items) = match self.error {
+            Some(error) => (Some(error), Vec::new()),
+            None => (None, self.result.unwrap_or_default()),
+        };
+        error.map(Err).into_iter().chain(items.into_iter().map(Ok))
+    }
+}
+
+/// A response whose `result` is kept as raw JSON, for logging or forwarding it
+/// before, or instead of, converting it with [`JsonRpcResponse::from_raw`].
+pub type JsonRpcRawResponse = JsonRpcResponse<serde_json::Value>;
+
+impl JsonRpcResponse<serde_json::Value> {
+    /// Returns the raw JSON of the `result`, or `None` if the response has none.
+    pub fn raw_result(&self) -> Option<&serde_json::Value> {
+        self.result.as_ref()
+    }
+}
+
+impl<T: serde::de::DeserializeOwned> JsonRpcResponse<T> {
+    /// Converts a raw response into a typed one by deserializing its `result`.
+    ///
+    /// # Errors
+    /// Returns [`RpcError::SerdeError`] if the `result` does not deserialize into `T`.
+    ///
+    /// ### Example
+    /// ```rust
+    /// # use a_rs_jsonrpc::{JsonRpcRawResponse, JsonRpcResponse, RpcError};
+    /// # fn main() -> Result<(), RpcError> {
+    /// # let text = r#"{"jsonrpc":"2.0","result":30,"id":1}"#;
+    /// let raw: JsonRpcRawResponse = serde_json::from_str(&text)?;
+    /// tracing::debug!("result: {:?}", raw.raw_result());
+    /// let resp = JsonRpcResponse::<i32>::from_raw(raw)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_raw(raw: JsonRpcRawResponse) -> Result<Self, RpcError> {
+        Ok(JsonRpcResponse {
+            jsonrpc: raw.jsonrpc,
+            result: raw.result.map(serde_json::from_value).transpose()?,
+            error: raw.error,
+            id: raw.id,
+        })
+    }
+}
+
 /// A structure representing a JSON-RPC error.
 ///
 /// This object is included in the [`JsonRpcResponse`] when a method
 /// call fails. It includes a numeric code and a descriptive message.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JsonRpcError {
     /// A Number that indicates the error type that occurred.
     pub code: i64,
@@ -68,3 +188,73 @@ pub struct JsonRpcError {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<serde_json::Value>,
 }
+
+impl JsonRpcError {
+    /// Converts a borrowed error object into an [`RpcError`].
+    ///
+    /// This is a convenience alias for `RpcError::from(err.clone())`.
+    pub fn to_rpc_error(&self) -> RpcError {
+        RpcError::from(self.clone())
+    }
+
+    /// Returns `true` if the code lies in the range reserved by the specification,
+    /// -32700 through -32600.
+    pub fn is_protocol_error(&self) -> bool {
+        (-32700..=-32600).contains(&self.code)
+    }
+
+    /// Returns `true` if the code lies outside the reserved protocol range.
+    pub fn is_application_error(&self) -> bool {
+        !self.is_protocol_error()
+    }
+
+    /// Returns `true` if the code lies in the range for implementation-defined
+    /// server errors, -32099 through -32000.
+    pub fn is_server_error(&self) -> bool {
+        (-32099..=-32000).contains(&self.code)
+    }
+
+    /// Classifies the error by its code.
+    ///
+    /// Reserved protocol codes without a meaning of their own are reported as
+    /// [`JsonRpcErrorKind::InternalError`].
+    pub fn error_kind(&self) -> JsonRpcErrorKind {
+        match self.code {
+            -32700 => JsonRpcErrorKind::ParseError,
+            -32600 => JsonRpcErrorKind::InvalidRequest,
+            -32601 => JsonRpcErrorKind::MethodNotFound,
+            -32602 => JsonRpcErrorKind::InvalidParams,
+            _ if self.is_protocol_error() => JsonRpcErrorKind::InternalError,
+            _ if self.is_server_error() => JsonRpcErrorKind::ServerError,
+            _ => JsonRpcErrorKind::ApplicationError,
+        }
+    }
+}
+
+/// The class of a [`JsonRpcError`], as returned by [`JsonRpcError::error_kind()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonRpcErrorKind {
+    /// `-32700`: the payload is not valid JSON.
+    ParseError,
+    /// `-32600`: the payload is not a valid request object.
+    InvalidRequest,
+    /// `-32601`: the method does not exist.
+    MethodNotFound,
+    /// `-32602`: the parameters are invalid.
+    InvalidParams,
+    /// `-32603` and the other reserved protocol codes.
+    InternalError,
+    /// `-32099` through `-32000`: implementation-defined server errors.
+    ServerError,
+    /// Any code outside the reserved ranges.
+    ApplicationError,
+}
+
+#[cfg(feature = "axum")]
+impl<T: Serialize> axum::response::IntoResponse for JsonRpcResponse<T> {
+    /// Serializes the response as the JSON body with `Content-Type: application/json`,
+    /// so handlers can return a `JsonRpcResponse` directly. Requires the `axum` feature.
+    fn into_response(self) -> axum::response::Response {
+        axum::Json(self).into_response()
+    }
+}
@@ -16,12 +16,36 @@
 //! 3. Call [`init()`] at application startup to build the [`ROUTE_TABLE`].
 //! 4. Use [`dispatch()`] to process raw request bytes.
 
-use crate::RpcError;
-pub use a_rs_jsonrpc_macros::{jsonrpc_service_fn_array, jsonrpc_service_fn_obj};
-use futures::future::BoxFuture;
+use crate::{
+    JsonRpcId, JsonRpcResponse, RpcError,
+    middleware::Middleware,
+    request::{JsonRpcRequest, JsonRpcVersion, Params},
+    response::JsonRpcError,
+};
+pub use a_rs_jsonrpc_macros::{
+    jsonrpc_service_fn, jsonrpc_service_fn_array, jsonrpc_service_fn_both, jsonrpc_service_fn_obj,
+};
+use arc_swap::ArcSwap;
+use bytes::Bytes;
+use futures::{
+    FutureExt, Stream, StreamExt,
+    future::BoxFuture,
+    stream::{self, BoxStream},
+};
 use linkme::distributed_slice;
-use serde::Deserialize;
-use std::{collections::HashMap, sync::LazyLock};
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    cell::Cell,
+    collections::HashMap,
+    panic::AssertUnwindSafe,
+    sync::{
+        Arc, LazyLock, Once, OnceLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
 /// A trait for types that can handle JSON-RPC requests.
 ///
@@ -38,14 +62,190 @@ pub trait JsonRpcServiceFn {
     ) -> Result<crate::response::JsonRpcResponse<Self::Result>, crate::error::RpcError>
     where
         Self::Result: serde::Serialize;
+
+    /// Processes a raw byte request with user data supplied by the dispatcher.
+    ///
+    /// Handlers declared with `has_context = true` receive the user data through
+    /// an [`RpcContext`]. The default implementation ignores it.
+    async fn handle_with_context(
+        req: &[u8],
+        _user_data: RpcUserData,
+    ) -> Result<crate::response::JsonRpcResponse<Self::Result>, crate::error::RpcError>
+    where
+        Self::Result: serde::Serialize,
+    {
+        Self::handle(req).await
+    }
+}
+
+/// Type-erased, shareable user data handed to handlers by [`dispatch_with_context()`].
+pub type RpcUserData = Arc<dyn Any + Send + Sync>;
+
+/// Per-request context passed to handlers declared with `has_context = true`.
+///
+/// The annotated function takes `&RpcContext` as its first argument, which is not
+/// part of the JSON-RPC parameters:
+///
+/// ```rust
+/// # use a_rs_jsonrpc::{RpcContext, RpcError, jsonrpc_service_fn_array};
+/// #[jsonrpc_service_fn_array(method = "whoami", version = "v2", has_context = true)]
+/// async fn whoami(ctx: &RpcContext) -> Result<String, RpcError> {
+///     Ok(format!("{} called with id {:?}", ctx.method, ctx.id))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RpcContext {
+    /// The identifier of the request being handled.
+    pub id: JsonRpcId,
+    /// The method name of the request being handled.
+    pub method: String,
+    /// The user data passed to [`dispatch_with_context()`], or `()` when the request
+    /// was dispatched without one.
+    pub user_data: RpcUserData,
+}
+
+impl RpcContext {
+    /// Returns the user data downcast to `T`, or `None` if it has a different type.
+    pub fn user_data<T: Any>(&self) -> Option<&T> {
+        self.user_data.downcast_ref::<T>()
+    }
 }
 
 /// Internal envelope used to peek at the `method` field of a JSON-RPC request
 /// without deserializing the entire payload.
 #[derive(Deserialize)]
-struct MethodEnvelope<'a> {
+pub(crate) struct MethodEnvelope<'a> {
     #[serde(borrow)]
-    method: &'a str,
+    pub(crate) method: &'a str,
+    /// The JSON type of `params`; the value itself is skipped.
+    #[serde(default)]
+    pub(crate) params: ParamsShape,
+}
+
+/// Reads the [`MethodEnvelope`] of a request body.
+///
+/// # Errors
+/// Returns [`RpcError::ParseError`] if the body is not valid JSON, and
+/// [`RpcError::InvalidRequest`] if it is valid JSON but not a request object.
+fn read_envelope(body: &[u8]) -> Result<MethodEnvelope<'_>, RpcError> {
+    serde_json::from_slice(body).map_err(|e| request_error(body, e))
+}
+
+/// Classifies the failure to read a request from `body`, as the specification
+/// tells a body that is not JSON apart from a malformed request object.
+fn request_error(body: &[u8], err: serde_json::Error) -> RpcError {
+    if serde_json::from_slice::<serde::de::IgnoredAny>(body).is_err() {
+        RpcError::ParseError(err.to_string())
+    } else {
+        RpcError::InvalidRequest(err.to_string())
+    }
+}
+
+/// The JSON type of a request's `params` member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ParamsShape {
+    /// `params` is missing or `null`.
+    #[default]
+    Absent,
+    /// `params` is an array.
+    Array,
+    /// `params` is an object.
+    Object,
+    /// `params` is a scalar, which no handler accepts.
+    Other,
+}
+
+impl<'de> Deserialize<'de> for ParamsShape {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct ShapeVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ShapeVisitor {
+            type Value = ParamsShape;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("JSON-RPC params")
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(ParamsShape::Absent)
+            }
+
+            fn visit_none<E>(self) -> Result<Self::Value, E> {
+                Ok(ParamsShape::Absent)
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
+                Ok(ParamsShape::Array)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                while map
+                    .next_entry::<serde::de::IgnoredAny, serde::de::IgnoredAny>()?
+                    .is_some()
+                {}
+                Ok(ParamsShape::Object)
+            }
+
+            fn visit_bool<E>(self, _: bool) -> Result<Self::Value, E> {
+                Ok(ParamsShape::Other)
+            }
+
+            fn visit_i64<E>(self, _: i64) -> Result<Self::Value, E> {
+                Ok(ParamsShape::Other)
+            }
+
+            fn visit_u64<E>(self, _: u64) -> Result<Self::Value, E> {
+                Ok(ParamsShape::Other)
+            }
+
+            fn visit_f64<E>(self, _: f64) -> Result<Self::Value, E> {
+                Ok(ParamsShape::Other)
+            }
+
+            fn visit_str<E>(self, _: &str) -> Result<Self::Value, E> {
+                Ok(ParamsShape::Other)
+            }
+        }
+
+        deserializer.deserialize_any(ShapeVisitor)
+    }
+}
+
+/// How a registered method expects its `params` to be passed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamMode {
+    /// Positional parameters in a JSON array, as registered by `#[jsonrpc_service_fn_array]`.
+    Array,
+    /// Named parameters in a JSON object, as registered by `#[jsonrpc_service_fn_obj]`.
+    Object,
+    /// Either form, as registered by `#[jsonrpc_service_fn_both]`.
+    Both,
+}
+
+impl ParamMode {
+    /// Rejects `shape` if it contradicts this mode. Missing params and scalars are
+    /// left for the handler to judge.
+    fn check(self, method: &str, shape: ParamsShape) -> Result<(), RpcError> {
+        let expected = match (self, shape) {
+            (ParamMode::Array, ParamsShape::Object) => "array",
+            (ParamMode::Object, ParamsShape::Array) => "object",
+            _ => return Ok(()),
+        };
+        Err(RpcError::InvalidParams(
+            format!("method '{}' expects params as an {}", method, expected).into(),
+        ))
+    }
 }
 
 /// A registration entry for an RPC method.
@@ -56,8 +256,17 @@ struct MethodEnvelope<'a> {
 pub struct RpcServiceEntry {
     /// The string name of the RPC method.
     pub method: &'static str,
+    /// A human-readable description, taken from the `desc` key or the doc comment
+    /// of the annotated function.
+    pub description: Option<&'static str>,
+    /// Whether the method takes positional or named parameters.
+    pub param_mode: ParamMode,
+    /// Builds the JSON Schema of the parameters, set by `schema = true`.
+    pub schema: Option<RpcSchemaFn>,
     /// The handler function pointer that returns a boxed future.
     pub handler: RpcHandlerFn,
+    /// The handler streaming one response per result item, set by `streaming = true`.
+    pub stream_handler: Option<RpcStreamHandlerFn>,
 }
 
 /// A distributed slice containing all registered RPC services.
@@ -70,47 +279,1443 @@ pub static RPC_SERVICES: [RpcServiceEntry];
 
 /// A type alias for the internal handler function signature.
 ///
-/// It takes raw request bytes and returns a [`BoxFuture`] resolving to a
-/// JSON-serialized response string.
-pub type RpcHandlerFn =
-    fn(req: &[u8]) -> BoxFuture<'static, Result<String, crate::error::RpcError>>;
+/// It takes raw request bytes and the user data supplied to the dispatcher, and
+/// returns a [`BoxFuture`] resolving to a JSON-serialized response string. The
+/// request is passed as [`Bytes`], which the `'static` handler future can hold
+/// without copying it.
+pub type RpcHandlerFn = fn(
+    req: Bytes,
+    user_data: RpcUserData,
+) -> BoxFuture<'static, Result<String, crate::error::RpcError>>;
+
+/// A stream of newline-terminated JSON-RPC responses, as returned by
+/// [`dispatch_streaming()`].
+pub type RpcResponseStream = BoxStream<'static, String>;
+
+/// A type alias for the handler of a method declared with `streaming = true`.
+///
+/// It resolves to a stream carrying one serialized response per item produced by
+/// the method.
+pub type RpcStreamHandlerFn =
+    fn(
+        req: Bytes,
+        user_data: RpcUserData,
+    ) -> BoxFuture<'static, Result<RpcResponseStream, crate::error::RpcError>>;
+
+/// Builds the JSON Schema of a method's parameters.
+#[cfg(feature = "schema")]
+pub type RpcSchemaFn = fn() -> schemars::schema::RootSchema;
+
+/// Builds the JSON Schema of a method's parameters.
+///
+/// Without the `schema` feature no schema is ever generated, so entries always
+/// carry `None`.
+#[cfg(not(feature = "schema"))]
+pub type RpcSchemaFn = fn() -> serde_json::Value;
+
+/// The method-to-entry map held by [`ROUTE_TABLE`].
+type RouteMap = HashMap<&'static str, &'static RpcServiceEntry>;
 
 /// A global, lazily-initialized routing table.
 ///
 /// On first access, it collects all entries from [`RPC_SERVICES`] into a [`HashMap`].
+/// The map sits behind an [`ArcSwap`], so [`hot_reload_methods()`] can replace it
+/// while requests are being dispatched; readers never block.
+static ROUTE_TABLE: LazyLock<ArcSwap<RouteMap>> =
+    LazyLock::new(|| ArcSwap::from_pointee(build_route_map()));
+
+/// The method under which `fallback = true` services are registered.
+///
+/// Entries with this method are kept out of the routing table and consulted only
+/// when no registered or built-in method matches.
+#[doc(hidden)]
+pub const FALLBACK_METHOD: &str = "rpc.fallback";
+
+/// The entry registered with `fallback = true`, if any.
+///
+/// It will panic if more than one fallback is registered.
+static REGISTERED_FALLBACK: LazyLock<Option<&'static RpcServiceEntry>> = LazyLock::new(|| {
+    let mut fallbacks = RPC_SERVICES
+        .iter()
+        .filter(|entry| entry.method == FALLBACK_METHOD);
+    let fallback = fallbacks.next();
+    if fallbacks.next().is_some() {
+        panic!("More than one fallback handler registered");
+    }
+    fallback
+});
+
+/// Collects all entries from [`RPC_SERVICES`] into a routing map.
+///
 /// It will panic if duplicate method names are detected.
-static ROUTE_TABLE: LazyLock<HashMap<&'static str, RpcHandlerFn>> = LazyLock::new(|| {
+fn build_route_map() -> RouteMap {
     let mut m = HashMap::new();
     for entry in RPC_SERVICES {
-        if m.insert(entry.method, entry.handler).is_some() {
+        if entry.method == FALLBACK_METHOD {
+            continue;
+        }
+        if m.insert(entry.method, entry).is_some() {
             panic!("Duplicate method registered: {}", entry.method);
         }
     }
     m
-});
+}
+
+/// Builds a routing table from `entries` and atomically installs it in place of the
+/// current one.
+///
+/// An entry replaces an earlier one with the same method, so passing the registered
+/// entries followed by a new entry swaps the handler of that method, and leaving an
+/// entry out removes its method. Passing [`RPC_SERVICES`] alone restores the
+/// registered methods. Fallback entries are skipped; the fallback handler is not
+/// reloadable.
+///
+/// Requests already in flight finish with the handler they resolved; every later
+/// request sees the new table. The swap is O(1) and never blocks dispatchers.
+///
+/// ### Example
+/// ```rust
+/// # use a_rs_jsonrpc::service::{ParamMode, RPC_SERVICES, RpcServiceEntry, RpcUserData, hot_reload_methods};
+/// # use a_rs_jsonrpc::{RpcError, bytes::Bytes, futures::future::BoxFuture};
+/// # fn add_v2(_req: Bytes, _user_data: RpcUserData) -> BoxFuture<'static, Result<String, RpcError>> {
+/// #     Box::pin(async { Ok(r#"{"jsonrpc":"2.0","result":3,"id":1}"#.to_string()) })
+/// # }
+/// static ADD_V2: RpcServiceEntry = RpcServiceEntry {
+///     method: "add",
+///     description: None,
+///     param_mode: ParamMode::Array,
+///     schema: None,
+///     handler: add_v2,
+///     stream_handler: None,
+/// };
+///
+/// hot_reload_methods(RPC_SERVICES.iter().chain([&ADD_V2]));
+/// ```
+pub fn hot_reload_methods<I>(entries: I)
+where
+    I: IntoIterator<Item = &'static RpcServiceEntry>,
+{
+    let routes: RouteMap = entries
+        .into_iter()
+        .filter(|entry| entry.method != FALLBACK_METHOD)
+        .map(|entry| (entry.method, entry))
+        .collect();
+    tracing::info!("RPC routing table reloaded with {} methods", routes.len());
+    ROUTE_TABLE.store(Arc::new(routes));
+}
+
+/// Options controlling how requests are validated before dispatch, passed to [`init()`].
+///
+/// [`ServiceConfig::default()`] keeps the behaviour of a service without a
+/// configuration, and [`ServiceConfig::strict()`] enables every validation. The
+/// validations apply to every dispatch entry point, including [`try_dispatch()`],
+/// [`dispatch_from_method()`] and [`dispatch_streaming()`]. The options can be set
+/// directly or chained:
+///
+/// ```rust
+/// # use a_rs_jsonrpc::service::{ServiceConfig, init};
+/// init(ServiceConfig::default().max_request_bytes(64 * 1024).enable_batch(false));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServiceConfig {
+    /// Reject requests whose `jsonrpc` version differs from the one the method was
+    /// registered with. When disabled, any supported version is accepted.
+    pub strict_version: bool,
+    /// Ignore top-level request members other than `jsonrpc`, `method`, `params`
+    /// and `id`. When disabled, such members are an [`RpcError::InvalidRequest`].
+    pub allow_unknown_fields: bool,
+    /// The largest accepted request body, in bytes.
+    pub max_request_bytes: usize,
+    /// Serve batch requests through [`dispatch_batch()`].
+    pub enable_batch: bool,
+    /// The version of error responses to requests whose own version cannot be read.
+    pub default_version: JsonRpcVersion,
+    /// Answer a request whose handler panics with an [`RpcError::InternalError`]
+    /// instead of propagating the panic. The panic and its backtrace are logged.
+    pub catch_panics: bool,
+}
+
+impl ServiceConfig {
+    /// The configuration used until [`init()`] installs another one.
+    const DEFAULT: ServiceConfig = ServiceConfig {
+        strict_version: true,
+        allow_unknown_fields: true,
+        max_request_bytes: usize::MAX,
+        enable_batch: true,
+        default_version: JsonRpcVersion::V2_0,
+        catch_panics: false,
+    };
+
+    /// A configuration enabling every validation: unknown request members are
+    /// rejected and request bodies are limited to 1 MiB.
+    pub fn strict() -> Self {
+        ServiceConfig {
+            allow_unknown_fields: false,
+            max_request_bytes: 1024 * 1024,
+            ..ServiceConfig::DEFAULT
+        }
+    }
+
+    /// Sets [`ServiceConfig::strict_version`].
+    pub fn strict_version(mut self, strict_version: bool) -> Self {
+        self.strict_version = strict_version;
+        self
+    }
+
+    /// Sets [`ServiceConfig::allow_unknown_fields`].
+    pub fn allow_unknown_fields(mut self, allow_unknown_fields: bool) -> Self {
+        self.allow_unknown_fields = allow_unknown_fields;
+        self
+    }
+
+    /// Sets [`ServiceConfig::max_request_bytes`].
+    pub fn max_request_bytes(mut self, max_request_bytes: usize) -> Self {
+        self.max_request_bytes = max_request_bytes;
+        self
+    }
+
+    /// Sets [`ServiceConfig::enable_batch`].
+    pub fn enable_batch(mut self, enable_batch: bool) -> Self {
+        self.enable_batch = enable_batch;
+        self
+    }
+
+    /// Sets [`ServiceConfig::default_version`].
+    pub fn default_version(mut self, default_version: JsonRpcVersion) -> Self {
+        self.default_version = default_version;
+        self
+    }
+
+    /// Sets [`ServiceConfig::catch_panics`].
+    pub fn catch_panics(mut self, catch_panics: bool) -> Self {
+        self.catch_panics = catch_panics;
+        self
+    }
+}
+
+impl Default for ServiceConfig {
+    /// Versions are checked, unknown members are ignored, batches are served,
+    /// request bodies are not limited and handler panics are not caught.
+    fn default() -> Self {
+        ServiceConfig::DEFAULT
+    }
+}
+
+/// The configuration installed by [`init()`].
+static SERVICE_CONFIG: OnceLock<ServiceConfig> = OnceLock::new();
+
+/// Returns the configuration installed by [`init()`], or the default one.
+fn service_config() -> &'static ServiceConfig {
+    SERVICE_CONFIG.get().unwrap_or(&ServiceConfig::DEFAULT)
+}
+
+/// Applies the size and member checks of the [`ServiceConfig`] to a request body.
+fn validate_request(body: &[u8]) -> Result<(), RpcError> {
+    check_request_size(body)?;
+    check_request_members(body)
+}
+
+/// Applies [`ServiceConfig::max_request_bytes`] to a request body, before it is parsed.
+fn check_request_size(body: &[u8]) -> Result<(), RpcError> {
+    let config = service_config();
+    if body.len() > config.max_request_bytes {
+        return Err(RpcError::InvalidRequest(format!(
+            "request body exceeds {} bytes",
+            config.max_request_bytes
+        )));
+    }
+    Ok(())
+}
+
+/// Applies [`ServiceConfig::allow_unknown_fields`] to a request body.
+fn check_request_members(body: &[u8]) -> Result<(), RpcError> {
+    let config = service_config();
+    if !config.allow_unknown_fields {
+        let members: HashMap<String, serde::de::IgnoredAny> =
+            serde_json::from_slice(body).map_err(|e| request_error(body, e))?;
+        if let Some(member) = members
+            .keys()
+            .find(|member| !matches!(member.as_str(), "jsonrpc" | "method" | "params" | "id"))
+        {
+            return Err(RpcError::InvalidRequest(format!(
+                "unknown request member '{}'",
+                member
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks the `jsonrpc` version of a request in a generated service handler.
+///
+/// With [`ServiceConfig::strict_version`] disabled, any supported version is accepted.
+#[doc(hidden)]
+pub fn check_version(version: &str, expected: &str) -> Result<(), RpcError> {
+    if version == expected
+        || (!service_config().strict_version && version.parse::<JsonRpcVersion>().is_ok())
+    {
+        return Ok(());
+    }
+    Err(RpcError::InvalidJsonRpcVersion(format!(
+        "Expected JSON-RPC version {}, got {}",
+        expected, version
+    )))
+}
+
+/// The handler invoked when no registered or built-in method matches.
+///
+/// Installed once via [`set_fallback_handler()`].
+static FALLBACK_HANDLER: OnceLock<RpcHandlerFn> = OnceLock::new();
+
+/// Registers a handler that receives every request whose method is not routed.
+///
+/// The fallback gets the full raw request bytes and the dispatcher's user data, so
+/// it can proxy the request, produce an error in a custom format, or delegate to
+/// another service. Only the first call takes effect; later calls are ignored with
+/// a warning. Without a fallback, unknown methods produce [`RpcError::MethodNotFound`].
+///
+/// A service registered with `fallback = true` takes precedence over this handler.
+pub fn set_fallback_handler(handler: RpcHandlerFn) {
+    if FALLBACK_HANDLER.set(handler).is_err() {
+        tracing::warn!("RPC fallback handler is already set, ignoring");
+    }
+}
+
+/// Chooses which of the handlers registered for `method` serves a request, as
+/// passed to [`dispatch_with_selector()`].
+///
+/// `handlers` holds at least two handlers: the one registered through
+/// [`RPC_SERVICES`], if any, followed by those added with
+/// [`register_additional_handler()`] in registration order.
+pub type RpcHandlerSelector = fn(method: &str, handlers: &[RpcHandlerFn]) -> RpcHandlerFn;
+
+/// Handlers added to methods with [`register_additional_handler()`], keyed by method.
+static ADDITIONAL_HANDLERS: LazyLock<ArcSwap<HashMap<String, Vec<RpcHandlerFn>>>> =
+    LazyLock::new(|| ArcSwap::from_pointee(HashMap::new()));
+
+/// Registers another handler for `method`, e.g. a canary version of it.
+///
+/// Additional handlers are only considered by [`dispatch_with_selector()`]; every
+/// other dispatcher keeps routing `method` to its handler in [`RPC_SERVICES`].
+/// They survive [`hot_reload_methods()`].
+pub fn register_additional_handler(method: &str, handler: RpcHandlerFn) {
+    ADDITIONAL_HANDLERS.rcu(|handlers| {
+        let mut handlers = HashMap::clone(handlers);
+        handlers
+            .entry(method.to_string())
+            .or_default()
+            .push(handler);
+        handlers
+    });
+}
+
+/// Resolves the handler for `method`: registered methods first, then the
+/// built-in `rpc.*` methods, then the registered and the installed fallback handlers.
+fn resolve_handler(method: &str) -> Option<RpcHandlerFn> {
+    ROUTE_TABLE
+        .load()
+        .get(method)
+        .map(|entry| entry.handler)
+        .or_else(|| builtin_handler(method))
+        .or_else(|| REGISTERED_FALLBACK.map(|entry| entry.handler))
+        .or_else(|| FALLBACK_HANDLER.get().copied())
+}
+
+/// Returns `true` if the `params` of a request body are a JSON object.
+///
+/// Used by handlers generated with `#[jsonrpc_service_fn_both]` to pick the
+/// object-mode decoding; anything else, including missing params, is decoded as
+/// positional parameters.
+#[doc(hidden)]
+pub fn has_named_params(body: &[u8]) -> bool {
+    serde_json::from_slice::<MethodEnvelope>(body)
+        .is_ok_and(|envelope| envelope.params == ParamsShape::Object)
+}
+
+/// Deserializes a request body in a generated service handler.
+///
+/// With the `path-errors` feature, a failure is reported as [`RpcError::SerdeError`]
+/// carrying the path of the offending value, e.g. `params.b`.
+#[doc(hidden)]
+pub fn deserialize_request<'de, T: Deserialize<'de>>(body: &'de [u8]) -> Result<T, RpcError> {
+    #[cfg(feature = "path-errors")]
+    {
+        let mut deserializer = serde_json::Deserializer::from_slice(body);
+        let request = serde_path_to_error::deserialize(&mut deserializer).map_err(|e| {
+            let path = e.path().to_string();
+            RpcError::SerdeError {
+                error: e.into_inner(),
+                path: Some(path),
+            }
+        })?;
+        deserializer.end()?;
+        Ok(request)
+    }
+    #[cfg(not(feature = "path-errors"))]
+    {
+        Ok(serde_json::from_slice(body)?)
+    }
+}
 
 /// Dispatches a raw JSON-RPC request to the appropriate registered handler.
 ///
 /// This is the main entry point for integrating the library with a web server
 /// (e.g., Axum or Actix). It extracts the method name and executes the mapped future.
 ///
+/// The handler runs in an `rpc_dispatch` span carrying the `method` and request
+/// `id`, which records the handler's `latency_us` and, on failure, its `error`.
+///
 /// # Errors
-/// Returns [`RpcError::MethodNotFound`] if the method name is not in the routing table.
+/// Returns [`RpcError::ParseError`] if the body is not valid JSON,
+/// [`RpcError::InvalidRequest`] if it is not a request object,
+/// [`RpcError::MethodNotFound`] if the method name is not in the routing table
+/// and no fallback handler is set, and [`RpcError::InvalidParams`] if `params` is an
+/// object for an array-mode method or an array for an object-mode method.
 pub async fn dispatch(body: &[u8]) -> Result<String, RpcError> {
-    let MethodEnvelope { method } = serde_json::from_slice(body)?;
-    if let Some(handler) = ROUTE_TABLE.get(method) {
-        return handler(body).await;
+    dispatch_with_context(body, Arc::new(())).await
+}
+
+/// Dispatches a raw JSON-RPC request, returning the response as [`Bytes`].
+///
+/// The response buffer produced by the handler is handed over as-is, without a copy,
+/// so it can go straight into a socket or HTTP body.
+///
+/// # Errors
+/// Same as [`dispatch()`].
+pub async fn dispatch_raw(body: &[u8]) -> Result<Bytes, RpcError> {
+    dispatch(body).await.map(Bytes::from)
+}
+
+/// Dispatches a raw JSON-RPC request and writes the response to `writer`.
+///
+/// The writer is flushed afterwards. No framing is added, so transports that
+/// delimit messages (e.g. with a trailing newline) write the delimiter themselves.
+/// Returns the number of response bytes written.
+///
+/// # Errors
+/// Same as [`dispatch()`], plus [`RpcError::IoError`] if writing fails. Nothing is
+/// written if dispatching fails.
+pub async fn dispatch_to_writer<W>(body: &[u8], writer: &mut W) -> Result<usize, RpcError>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    use tokio::io::AsyncWriteExt;
+
+    let resp = dispatch_raw(body).await?;
+    writer.write_all(&resp).await?;
+    writer.flush().await?;
+    Ok(resp.len())
+}
+
+/// Maps a dispatch error to the HTTP status of its response.
+///
+/// - `MethodNotFound` -> `404 Not Found`
+/// - `ParseError`, `SerdeError`, `InvalidRequest`, `InvalidJsonRpcVersion`,
+///   `InvalidParams` -> `400 Bad Request`
+/// - anything else -> `500 Internal Server Error`
+#[cfg(feature = "http")]
+pub fn status_code_of(err: &RpcError) -> http::StatusCode {
+    match err {
+        RpcError::MethodNotFound => http::StatusCode::NOT_FOUND,
+        RpcError::ParseError(_)
+        | RpcError::SerdeError { .. }
+        | RpcError::InvalidRequest(_)
+        | RpcError::InvalidJsonRpcVersion(_)
+        | RpcError::InvalidParams(_) => http::StatusCode::BAD_REQUEST,
+        _ => http::StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+/// Dispatches a JSON-RPC request received over HTTP into a complete HTTP response.
+///
+/// A successful dispatch is answered with `200 OK` and `Content-Type: application/json`.
+/// A dispatch error becomes a JSON-RPC error response with the status chosen by
+/// [`status_code_of()`]. A request whose `Content-Type` is set to something other
+/// than JSON is rejected with `415 Unsupported Media Type` without being dispatched;
+/// a missing `Content-Type` is accepted. Requires the `http` feature.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::bytes::Bytes;
+/// # use a_rs_jsonrpc::service::dispatch_http;
+/// # use axum::http;
+/// # async fn example(req: http::Request<Bytes>) {
+/// let resp = dispatch_http(req).await;
+/// # }
+/// ```
+#[cfg(feature = "http")]
+pub async fn dispatch_http(req: http::Request<Bytes>) -> http::Response<String> {
+    let json_content = accepts_content_type(req.headers());
+    let body = req.into_body();
+    let (status, text) = if !json_content {
+        let err = RpcError::InvalidRequest("content type must be application/json".to_string());
+        (
+            http::StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            error_response(&body, err),
+        )
+    } else {
+        match dispatch_bytes(body.clone()).await {
+            Ok(text) => (http::StatusCode::OK, text),
+            Err(err) => (status_code_of(&err), error_response(&body, err)),
+        }
+    };
+    http::Response::builder()
+        .status(status)
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(text)
+        .unwrap_or_default()
+}
+
+/// Returns `false` if the `Content-Type` header is set to something other than JSON.
+/// A missing header is accepted.
+#[cfg(feature = "http")]
+pub(crate) fn accepts_content_type(headers: &http::HeaderMap) -> bool {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_none_or(|value| {
+            let mime = value.split(';').next().unwrap_or_default().trim();
+            mime.eq_ignore_ascii_case("application/json") || mime.ends_with("+json")
+        })
+}
+
+/// Dispatches a raw JSON-RPC request, streaming its responses as newline-delimited JSON.
+///
+/// A method declared with `streaming = true` yields one response per item of its
+/// stream, each carrying the request `id` and terminated by `\n`, so the items can
+/// be written out as an `application/x-ndjson` body. Any other method yields the
+/// single response produced by [`dispatch()`]. Either way the handler runs in the
+/// `rpc_dispatch` span of [`dispatch()`].
+///
+/// # Errors
+/// Same as [`dispatch()`], for errors raised before the first item. An item that
+/// fails to serialize is sent as an error response line instead.
+pub async fn dispatch_streaming(body: &[u8]) -> Result<RpcResponseStream, RpcError> {
+    validate_request(body)?;
+    let MethodEnvelope { method, params } = read_envelope(body)?;
+    let entry = ROUTE_TABLE.load().get(method).copied();
+    let Some((entry, stream_handler)) =
+        entry.and_then(|entry| Some((entry, entry.stream_handler?)))
+    else {
+        let mut text = dispatch(body).await?;
+        text.push('\n');
+        return Ok(Box::pin(stream::iter([text])));
+    };
+    entry.param_mode.check(method, params)?;
+    let body = Bytes::copy_from_slice(body);
+    let stream = run_handler(
+        |body| stream_handler(body, Arc::new(())),
+        body.clone(),
+        method,
+    )
+    .await?;
+    Ok(catch_stream_panics(stream, body, method))
+}
+
+/// Serializes each item of a streaming service as a newline-terminated response.
+#[doc(hidden)]
+pub fn stream_responses<T: Serialize>(
+    items: impl Stream<Item = T> + Send + 'static,
+    jsonrpc: JsonRpcVersion,
+    id: JsonRpcId,
+) -> RpcResponseStream {
+    Box::pin(items.map(move |item| {
+        let response = JsonRpcResponse {
+            jsonrpc,
+            result: Some(item),
+            error: None,
+            id: id.clone(),
+        };
+        let mut line = serde_json::to_string(&response).unwrap_or_else(|err| {
+            RpcError::from(err).into_jsonrpc_error_response(id.clone(), jsonrpc)
+        });
+        line.push('\n');
+        line
+    }))
+}
+
+/// Dispatches a raw JSON-RPC request, giving up once `timeout` has elapsed.
+///
+/// The handler future is dropped when the timeout fires, so handlers need no
+/// timeout logic of their own. Requires a Tokio runtime with the time driver enabled.
+///
+/// # Errors
+/// Returns [`RpcError::Timeout`] if the request did not complete in time, otherwise
+/// the same errors as [`dispatch()`].
+pub async fn dispatch_with_timeout(body: &[u8], timeout: Duration) -> Result<String, RpcError> {
+    tokio::time::timeout(timeout, dispatch(body)).await?
+}
+
+/// Dispatches a JSON-RPC request held in a string.
+///
+/// A convenience wrapper around [`dispatch()`] for callers that already have the
+/// request as `&str`.
+///
+/// # Errors
+/// Same as [`dispatch()`].
+pub async fn dispatch_json_str(s: &str) -> Result<String, RpcError> {
+    dispatch(s.as_bytes()).await
+}
+
+/// Dispatches a raw JSON-RPC request received over HTTP, continuing the caller's trace.
+///
+/// The W3C `traceparent` / `tracestate` headers are extracted with the globally
+/// configured OpenTelemetry propagator and become the parent of the
+/// `jsonrpc.dispatch` span the request is handled in. Requires the
+/// `opentelemetry` feature.
+///
+/// # Errors
+/// Same as [`dispatch()`].
+#[cfg(feature = "opentelemetry")]
+pub async fn dispatch_with_headers(
+    body: &[u8],
+    headers: &http::HeaderMap,
+) -> Result<String, RpcError> {
+    use tracing::Instrument;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&opentelemetry_http::HeaderExtractor(headers))
+    });
+    let method = serde_json::from_slice::<MethodEnvelope>(body)
+        .map(|envelope| envelope.method)
+        .unwrap_or_default();
+    let span = tracing::info_span!("jsonrpc.dispatch", rpc.method = method);
+    let _ = span.set_parent(parent);
+    dispatch(body).instrument(span).await
+}
+
+/// The trace context a request carries in its extension fields.
+#[cfg(feature = "opentelemetry")]
+#[derive(Deserialize)]
+struct TraceContextEnvelope {
+    #[serde(rename = "x-trace-context", default)]
+    trace_context: HashMap<String, String>,
+}
+
+/// Creates the `jsonrpc.handler` span a handler runs in.
+///
+/// If the request carries a [`crate::request::TRACE_CONTEXT_MEMBER`] field, the
+/// context it holds is extracted with the globally configured propagator and
+/// becomes the parent of the span; otherwise the span is a child of the current
+/// one. The span is exported by whatever `tracing-opentelemetry` layer, e.g. an
+/// OTLP exporter, the application installed.
+#[cfg(feature = "opentelemetry")]
+fn handler_span(body: &[u8], method: &str) -> tracing::Span {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let span = tracing::info_span!("jsonrpc.handler", rpc.method = method);
+    let fields = serde_json::from_slice::<TraceContextEnvelope>(body)
+        .map(|envelope| envelope.trace_context)
+        .unwrap_or_default();
+    if !fields.is_empty() {
+        let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+            propagator.extract(&fields)
+        });
+        let _ = span.set_parent(parent);
+    }
+    span
+}
+
+thread_local! {
+    /// The backtrace of the last panic on this thread, recorded by the hook
+    /// installed for [`ServiceConfig::catch_panics`].
+    static PANIC_BACKTRACE: Cell<Option<Backtrace>> = const { Cell::new(None) };
+}
+
+/// Guards the installation of the panic hook recording backtraces.
+static PANIC_HOOK: Once = Once::new();
+
+/// Chains a panic hook recording the backtrace of each panic for [`call_handler()`].
+///
+/// A handler future panics and is caught within the same poll, so the backtrace is
+/// still in the thread-local when the panic is logged.
+fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            PANIC_BACKTRACE.set(Some(Backtrace::force_capture()));
+            previous(info);
+        }));
+    });
+}
+
+/// Returns the message a panic was raised with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "handler panicked".to_string()
+    }
+}
+
+/// The request id peeked from a body to label the `rpc_dispatch` span.
+#[derive(Deserialize)]
+struct IdEnvelope {
+    id: Option<JsonRpcId>,
+}
+
+/// Runs `handler` on a request to `method` in an `rpc_dispatch` span.
+///
+/// The span carries the `method` and the request `id`, and records `latency_us`
+/// and, on failure, `error` once the handler completes. Every dispatch entry point
+/// calls its handler through this function.
+async fn run_handler<T>(
+    handler: impl FnOnce(Bytes) -> BoxFuture<'static, Result<T, RpcError>>,
+    body: Bytes,
+    method: &str,
+) -> Result<T, RpcError> {
+    let span = tracing::info_span!(
+        "rpc_dispatch",
+        method = method,
+        id = tracing::field::Empty,
+        latency_us = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+    if let Ok(IdEnvelope { id: Some(id) }) = serde_json::from_slice(&body) {
+        span.record("id", tracing::field::display(id));
+    }
+    let started = Instant::now();
+    let result =
+        tracing::Instrument::instrument(call_handler(handler, body, method), span.clone()).await;
+    span.record("latency_us", started.elapsed().as_micros() as u64);
+    if let Err(err) = &result {
+        span.record("error", tracing::field::display(err));
+    }
+    result
+}
+
+/// Calls `handler` on a request to `method`.
+///
+/// With [`ServiceConfig::catch_panics`], a panic of the handler is logged with its
+/// backtrace and becomes an [`RpcError::InternalError`] carrying the panic message.
+async fn call_handler<T>(
+    handler: impl FnOnce(Bytes) -> BoxFuture<'static, Result<T, RpcError>>,
+    body: Bytes,
+    method: &str,
+) -> Result<T, RpcError> {
+    #[cfg(feature = "opentelemetry")]
+    let span = handler_span(&body, method);
+    let response = handler(body);
+    #[cfg(feature = "opentelemetry")]
+    let response = tracing::Instrument::instrument(response, span);
+    if !service_config().catch_panics {
+        return response.await;
+    }
+    match AssertUnwindSafe(response).catch_unwind().await {
+        Ok(result) => result,
+        Err(payload) => Err(panic_error(method, payload.as_ref())),
+    }
+}
+
+/// Logs a caught panic of the handler of `method` with its backtrace and returns
+/// the [`RpcError::InternalError`] answering it.
+fn panic_error(method: &str, payload: &(dyn Any + Send)) -> RpcError {
+    let message = panic_message(payload);
+    let backtrace = PANIC_BACKTRACE
+        .take()
+        .map_or_else(|| "backtrace unavailable".to_string(), |b| b.to_string());
+    tracing::error!("handler of {} panicked: {}\n{}", method, message, backtrace);
+    RpcError::InternalError(message)
+}
+
+/// With [`ServiceConfig::catch_panics`], ends the response stream of a request to
+/// `method` with an error response if producing an item panics.
+fn catch_stream_panics(stream: RpcResponseStream, body: Bytes, method: &str) -> RpcResponseStream {
+    if !service_config().catch_panics {
+        return stream;
+    }
+    let method = method.to_string();
+    Box::pin(AssertUnwindSafe(stream).catch_unwind().map(move |line| {
+        line.unwrap_or_else(|payload| {
+            let mut line = error_response(&body, panic_error(&method, payload.as_ref()));
+            line.push('\n');
+            line
+        })
+    }))
+}
+
+/// Dispatches a raw JSON-RPC request, making `user_data` available to the handler.
+///
+/// Handlers declared with `has_context = true` can read the data through
+/// [`RpcContext::user_data()`]; other handlers ignore it.
+///
+/// # Errors
+/// Same as [`dispatch()`].
+pub async fn dispatch_with_context(
+    body: &[u8],
+    user_data: RpcUserData,
+) -> Result<String, RpcError> {
+    dispatch_bytes_with_context(Bytes::copy_from_slice(body), user_data).await
+}
+
+/// Dispatches a raw JSON-RPC request held in [`Bytes`].
+///
+/// Unlike [`dispatch()`], the body is handed to the handler without being copied,
+/// which suits servers that already receive `Bytes`, such as Axum's `body::Bytes`.
+///
+/// # Errors
+/// Same as [`dispatch()`].
+pub async fn dispatch_bytes(body: Bytes) -> Result<String, RpcError> {
+    dispatch_bytes_with_context(body, Arc::new(())).await
+}
+
+/// Dispatches a raw JSON-RPC request held in [`Bytes`], making `user_data`
+/// available to the handler.
+///
+/// The zero-copy counterpart of [`dispatch_with_context()`].
+///
+/// # Errors
+/// Same as [`dispatch()`].
+pub async fn dispatch_bytes_with_context(
+    body: Bytes,
+    user_data: RpcUserData,
+) -> Result<String, RpcError> {
+    validate_request(&body)?;
+    let MethodEnvelope { method, params } = read_envelope(&body)?;
+    if let Some(entry) = ROUTE_TABLE.load().get(method) {
+        entry.param_mode.check(method, params)?;
+    }
+    let handler = resolve_handler(method).ok_or(RpcError::MethodNotFound)?;
+    run_handler(|body| handler(body, user_data), body.clone(), method).await
+}
+
+/// Dispatches a raw JSON-RPC request, letting `selector` choose between the handlers
+/// of a method that has several, for A/B testing or canary routing.
+///
+/// A method with a single handler is routed to it without consulting `selector`,
+/// and a method with none falls back to the built-in and fallback handlers like
+/// [`dispatch()`].
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::RpcError;
+/// # use a_rs_jsonrpc::service::{RpcHandlerFn, RpcUserData, dispatch_with_selector, register_additional_handler};
+/// # use a_rs_jsonrpc::{bytes::Bytes, futures::future::BoxFuture};
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// # fn sum_canary(_req: Bytes, _user_data: RpcUserData) -> BoxFuture<'static, Result<String, RpcError>> {
+/// #     Box::pin(async { Ok(r#"{"jsonrpc":"2.0","result":3,"id":1}"#.to_string()) })
+/// # }
+/// static CALLS: AtomicUsize = AtomicUsize::new(0);
+///
+/// // Sends every twentieth call to the canary.
+/// fn pick_canary(_method: &str, handlers: &[RpcHandlerFn]) -> RpcHandlerFn {
+///     if CALLS.fetch_add(1, Ordering::Relaxed) % 20 == 0 { handlers[1] } else { handlers[0] }
+/// }
+///
+/// # async fn example(body: &[u8]) -> Result<(), RpcError> {
+/// register_additional_handler("sum", sum_canary);
+/// let resp = dispatch_with_selector(body, pick_canary).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Same as [`dispatch()`].
+pub async fn dispatch_with_selector(
+    body: &[u8],
+    selector: RpcHandlerSelector,
+) -> Result<String, RpcError> {
+    validate_request(body)?;
+    let MethodEnvelope { method, params } = read_envelope(body)?;
+    let entry = ROUTE_TABLE.load().get(method).copied();
+    if let Some(entry) = entry {
+        entry.param_mode.check(method, params)?;
+    }
+    let mut handlers: Vec<RpcHandlerFn> = entry.map(|entry| entry.handler).into_iter().collect();
+    if let Some(additional) = ADDITIONAL_HANDLERS.load().get(method) {
+        handlers.extend(additional.iter().copied());
+    }
+    let handler = match handlers.as_slice() {
+        [] => resolve_handler(method).ok_or(RpcError::MethodNotFound)?,
+        [handler] => *handler,
+        handlers => selector(method, handlers),
+    };
+    run_handler(
+        |body| handler(body, Arc::new(())),
+        Bytes::copy_from_slice(body),
+        method,
+    )
+    .await
+}
+
+/// Returns the handler future for a request to a registered method, without awaiting it.
+///
+/// Only the methods in [`ROUTE_TABLE`] are considered: the built-in `rpc.*` methods
+/// and the fallback handler are not. `None` is returned immediately if the body has
+/// no readable `method` or the method is not registered, so proxy pipelines can try
+/// another registry. The handler runs in the `rpc_dispatch` span of [`dispatch()`].
+///
+/// The [`ServiceConfig`] checks apply as in [`dispatch()`]. A body over
+/// [`ServiceConfig::max_request_bytes`] is rejected before it is parsed, so its error
+/// is returned even if the method is not registered.
+pub fn try_dispatch(body: &[u8]) -> Option<BoxFuture<'static, Result<String, RpcError>>> {
+    if let Err(err) = check_request_size(body) {
+        return Some(Box::pin(futures::future::ready(Err(err))));
+    }
+    let MethodEnvelope { method, params } = serde_json::from_slice(body).ok()?;
+    let entry = *ROUTE_TABLE.load().get(method)?;
+    if let Err(err) =
+        check_request_members(body).and_then(|()| entry.param_mode.check(method, params))
+    {
+        return Some(Box::pin(futures::future::ready(Err(err))));
+    }
+    let handler = entry.handler;
+    Some(Box::pin(run_handler(
+        move |body| handler(body, Arc::new(())),
+        Bytes::copy_from_slice(body),
+        entry.method,
+    )))
+}
+
+/// Builds the error response for a request that failed to dispatch, echoing its
+/// `id` and version when the request can be parsed.
+pub(crate) fn error_response(body: &[u8], err: RpcError) -> String {
+    match serde_json::from_slice::<JsonRpcRequest<serde_json::Value>>(body) {
+        Ok(req) => err.into_jsonrpc_error_response(req.id, req.jsonrpc),
+        Err(_) => serde_json::to_string(&serde_json::json!({
+            "jsonrpc": service_config().default_version,
+            "error": JsonRpcError::from(err),
+            "id": null,
+        }))
+        .unwrap_or_default(),
+    }
+}
+
+/// Dispatches a request through a chain of [`Middleware`], onion style.
+///
+/// Each `before` hook runs in order, then [`dispatch()`], then the `after` hooks of
+/// the middlewares whose `before` ran, in reverse order. A hook or dispatch error
+/// does not abort the chain: it becomes an error response echoing the request `id`,
+/// which is what the remaining `after` hooks see. Every middleware that saw a
+/// request therefore also sees its response.
+///
+/// # Errors
+/// Never fails; the `Result` matches the other dispatchers.
+pub async fn dispatch_with_middleware(
+    body: &[u8],
+    middlewares: &[Arc<dyn Middleware>],
+) -> Result<String, RpcError> {
+    dispatch_with_middleware_and_headers(body, &HashMap::new(), middlewares).await
+}
+
+/// Dispatches a request through a chain of [`Middleware`], handing the transport
+/// headers to [`Middleware::before_with_headers`].
+///
+/// This is how an [`crate::middleware::AuthMiddleware`] sees the `Authorization`
+/// header of an HTTP request. Otherwise the same as [`dispatch_with_middleware()`].
+///
+/// # Errors
+/// Same as [`dispatch_with_middleware()`].
+pub async fn dispatch_with_middleware_and_headers(
+    body: &[u8],
+    headers: &HashMap<String, String>,
+    middlewares: &[Arc<dyn Middleware>],
+) -> Result<String, RpcError> {
+    let mut entered = 0;
+    let mut rejected = None;
+    for middleware in middlewares {
+        entered += 1;
+        if let Err(err) = middleware.before_with_headers(body, headers).await {
+            rejected = Some(err);
+            break;
+        }
+    }
+    let mut resp = match rejected {
+        Some(err) => error_response(body, err),
+        None => match dispatch(body).await {
+            Ok(resp) => resp,
+            Err(err) => error_response(body, err),
+        },
+    };
+    for middleware in middlewares[..entered].iter().rev() {
+        resp = match middleware.after(body, &resp).await {
+            Ok(resp) => resp,
+            Err(err) => error_response(body, err),
+        };
+    }
+    Ok(resp)
+}
+
+/// Dispatches a JSON-RPC batch request.
+///
+/// The body is first parsed as an array of request objects; a body that is not an
+/// array is handed to [`dispatch()`] unchanged. All entries are dispatched
+/// concurrently and their responses are serialized as a JSON array in request
+/// order. A failing entry contributes an error response object instead of failing
+/// the whole batch. Notifications (entries with a missing or `null` id) are
+/// processed but contribute no element; if the batch consists only of
+/// notifications, an empty string is returned.
+///
+/// # Errors
+/// Returns [`RpcError::InvalidParams`] if the batch array is empty.
+pub async fn dispatch_batch(body: &[u8]) -> Result<String, RpcError> {
+    // Checked before parsing, so an oversized batch is never deserialized.
+    check_request_size(body)?;
+    let config = service_config();
+    let Ok(batch) = serde_json::from_slice::<Vec<serde_json::Value>>(body) else {
+        return dispatch(body).await;
+    };
+    if !config.enable_batch {
+        return Err(RpcError::InvalidRequest(
+            "batch requests are disabled".to_string(),
+        ));
+    }
+    if batch.is_empty() {
+        return Err(RpcError::invalid_params_static(
+            "batch request must not be empty",
+        ));
+    }
+    let responses = futures::future::join_all(batch.into_iter().map(dispatch_batch_item)).await;
+    let responses: Vec<serde_json::Value> = responses.into_iter().flatten().collect();
+    if responses.is_empty() {
+        return Ok(String::new());
+    }
+    Ok(serde_json::to_string(&responses)?)
+}
+
+/// Dispatches one entry of a batch, returning `None` for notifications.
+async fn dispatch_batch_item(item: serde_json::Value) -> Option<serde_json::Value> {
+    let id = item.get("id").cloned().unwrap_or(serde_json::Value::Null);
+    let version = item
+        .get("jsonrpc")
+        .cloned()
+        .unwrap_or(serde_json::Value::Null);
+    let result = match serde_json::to_vec(&item) {
+        Ok(body) => dispatch(&body)
+            .await
+            .and_then(|text| Ok(serde_json::from_str::<serde_json::Value>(&text)?)),
+        Err(e) => Err(RpcError::from(e)),
+    };
+    if id.is_null() {
+        return None;
+    }
+    Some(match result {
+        Ok(response) => response,
+        Err(err) => serde_json::json!({
+            "jsonrpc": version,
+            "error": JsonRpcError::from(err),
+            "id": id,
+        }),
+    })
+}
+
+/// Dispatches a request whose method name is supplied out-of-band.
+///
+/// This supports URL-based routing such as `POST /rpc/<method>`, where the JSON
+/// body carries `jsonrpc`, `params` and `id` but no `method` field. The method name
+/// is injected into a synthetic request envelope before the handler runs, so any
+/// `method` field already present in the body is overwritten. The handler runs in
+/// the `rpc_dispatch` span of [`dispatch()`], and the [`ServiceConfig`] checks apply
+/// as in [`dispatch()`].
+///
+/// # Errors
+/// Returns [`RpcError::InvalidRequest`] if the body is not a JSON object or fails
+/// the [`ServiceConfig`] checks, [`RpcError::MethodNotFound`] if the method name is
+/// not in the routing table and no fallback handler is set, and
+/// [`RpcError::InvalidParams`] if the shape of `params` does not match the method.
+pub async fn dispatch_from_method(method: &str, body: &[u8]) -> Result<String, RpcError> {
+    check_request_size(body)?;
+    let Some(handler) = resolve_handler(method) else {
+        return Err(RpcError::MethodNotFound);
+    };
+    let mut envelope = match serde_json::from_slice::<serde_json::Value>(body)? {
+        serde_json::Value::Object(map) => map,
+        _ => {
+            return Err(RpcError::InvalidRequest(
+                "request body must be a JSON object".to_string(),
+            ));
+        }
+    };
+    envelope.insert(
+        "method".to_string(),
+        serde_json::Value::String(method.to_string()),
+    );
+    let body = serde_json::to_vec(&envelope)?;
+    check_request_members(&body)?;
+    let MethodEnvelope { params, .. } = read_envelope(&body)?;
+    if let Some(entry) = ROUTE_TABLE.load().get(method) {
+        entry.param_mode.check(method, params)?;
+    }
+    run_handler(
+        |body| handler(body, Arc::new(())),
+        Bytes::from(body),
+        method,
+    )
+    .await
+}
+
+/// Returns the names of all registered methods in sorted order.
+///
+/// Only methods collected from [`RPC_SERVICES`] are listed; the built-in
+/// `rpc.*` methods enabled by [`init()`] are not included.
+pub fn list_methods() -> Vec<&'static str> {
+    let mut methods: Vec<&'static str> = ROUTE_TABLE.load().keys().copied().collect();
+    methods.sort_unstable();
+    methods
+}
+
+/// Whether the built-in `rpc.*` methods are served. Set by [`init()`].
+static BUILTINS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Looks up a built-in method handler.
+///
+/// Built-ins are consulted only after [`ROUTE_TABLE`], so a user-registered
+/// method with the same name takes precedence.
+fn builtin_handler(method: &str) -> Option<RpcHandlerFn> {
+    if !BUILTINS_ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    match method {
+        "rpc.listMethods" => Some(list_methods_handler as RpcHandlerFn),
+        "rpc.describe" => Some(describe_handler as RpcHandlerFn),
+        "rpc.openrpc" => Some(openrpc_handler as RpcHandlerFn),
+        #[cfg(feature = "schema")]
+        "rpc.getSchema" => Some(get_schema_handler as RpcHandlerFn),
+        _ => None,
+    }
+}
+
+/// Serializes a successful response echoing the version and id of `request`.
+fn builtin_response(
+    request: JsonRpcRequest<serde_json::Value>,
+    result: serde_json::Value,
+) -> Result<String, RpcError> {
+    let response = JsonRpcResponse {
+        jsonrpc: request.jsonrpc,
+        result: Some(result),
+        error: None,
+        id: request.id,
+    };
+    Ok(serde_json::to_string(&response)?)
+}
+
+/// Handler for `rpc.listMethods`, returning the result of [`list_methods()`].
+fn list_methods_handler(
+    req: Bytes,
+    _user_data: RpcUserData,
+) -> BoxFuture<'static, Result<String, RpcError>> {
+    let result = serde_json::from_slice::<JsonRpcRequest<serde_json::Value>>(&req)
+        .map_err(RpcError::from)
+        .and_then(|request| builtin_response(request, serde_json::json!(list_methods())));
+    Box::pin(futures::future::ready(result))
+}
+
+/// Handler for `rpc.describe`, returning the registration metadata of one method.
+///
+/// The method name is accepted either positionally (`["add"]`) or by name
+/// (`{"method": "add"}`).
+fn describe_handler(
+    req: Bytes,
+    _user_data: RpcUserData,
+) -> BoxFuture<'static, Result<String, RpcError>> {
+    let result = serde_json::from_slice::<JsonRpcRequest<serde_json::Value>>(&req)
+        .map_err(RpcError::from)
+        .and_then(|request| {
+            let name = requested_method("rpc.describe", &request.params)?;
+            let entry = *ROUTE_TABLE
+                .load()
+                .get(name)
+                .ok_or(RpcError::MethodNotFound)?;
+            builtin_response(request, describe_entry(entry))
+        });
+    Box::pin(futures::future::ready(result))
+}
+
+/// Reads the method name a built-in was asked about, given either positionally
+/// (`["add"]`) or by name (`{"method": "add"}`).
+fn requested_method<'a>(
+    builtin: &str,
+    params: &'a Params<serde_json::Value>,
+) -> Result<&'a str, RpcError> {
+    match params.as_option() {
+        Some(serde_json::Value::Array(params)) => params.first(),
+        Some(serde_json::Value::Object(params)) => params.get("method"),
+        _ => None,
+    }
+    .and_then(serde_json::Value::as_str)
+    .ok_or_else(|| RpcError::InvalidParams(format!("{} requires a method name", builtin).into()))
+}
+
+/// Handler for `rpc.getSchema`, returning the parameter schema of one method.
+///
+/// A method registered without `schema = true` yields `null`.
+#[cfg(feature = "schema")]
+fn get_schema_handler(
+    req: Bytes,
+    _user_data: RpcUserData,
+) -> BoxFuture<'static, Result<String, RpcError>> {
+    let result = serde_json::from_slice::<JsonRpcRequest<serde_json::Value>>(&req)
+        .map_err(RpcError::from)
+        .and_then(|request| {
+            let name = requested_method("rpc.getSchema", &request.params)?;
+            if !RPC_SERVICES.iter().any(|entry| entry.method == name) {
+                return Err(RpcError::MethodNotFound);
+            }
+            let schema = serde_json::to_value(get_schema(name))?;
+            builtin_response(request, schema)
+        });
+    Box::pin(futures::future::ready(result))
+}
+
+/// The parameter schemas of every method registered with `schema = true`, built
+/// once on first access.
+#[cfg(feature = "schema")]
+static SCHEMAS: LazyLock<HashMap<&'static str, schemars::schema::RootSchema>> =
+    LazyLock::new(|| {
+        RPC_SERVICES
+            .iter()
+            .filter_map(|entry| entry.schema.map(|schema| (entry.method, schema())))
+            .collect()
+    });
+
+/// Returns the JSON Schema of the parameters of `method`, or `None` if it is not
+/// registered or was registered without `schema = true`.
+#[cfg(feature = "schema")]
+pub fn get_schema(method: &str) -> Option<&'static schemars::schema::RootSchema> {
+    SCHEMAS.get(method)
+}
+
+/// Registration metadata of one method, as reported by `rpc.describe`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RpcMethodInfo {
+    /// The string name of the RPC method.
+    pub method: &'static str,
+    /// The description of the method, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<&'static str>,
+    /// Whether the method takes positional or named parameters.
+    pub param_mode: ParamMode,
+}
+
+impl From<&RpcServiceEntry> for RpcMethodInfo {
+    fn from(entry: &RpcServiceEntry) -> Self {
+        RpcMethodInfo {
+            method: entry.method,
+            description: entry.description,
+            param_mode: entry.param_mode,
+        }
+    }
+}
+
+/// Returns the registration metadata of `method`, or `None` if it is not in the
+/// routing table.
+pub fn describe(method: &str) -> Option<RpcMethodInfo> {
+    ROUTE_TABLE
+        .load()
+        .get(method)
+        .map(|entry| RpcMethodInfo::from(*entry))
+}
+
+/// Builds the `rpc.describe` metadata object for a registration entry.
+fn describe_entry(entry: &RpcServiceEntry) -> serde_json::Value {
+    serde_json::to_value(RpcMethodInfo::from(entry)).unwrap_or_default()
+}
+
+/// Handler for `rpc.openrpc`, returning the result of [`generate_openrpc_document()`].
+fn openrpc_handler(
+    req: Bytes,
+    _user_data: RpcUserData,
+) -> BoxFuture<'static, Result<String, RpcError>> {
+    let result = serde_json::from_slice::<JsonRpcRequest<serde_json::Value>>(&req)
+        .map_err(RpcError::from)
+        .and_then(|request| builtin_response(request, generate_openrpc_document()));
+    Box::pin(futures::future::ready(result))
+}
+
+/// Generates an [OpenRPC 1.2](https://spec.open-rpc.org) document describing every
+/// method in [`ROUTE_TABLE`].
+///
+/// Methods are listed in sorted order with their description and parameter
+/// structure. With the `schema` feature, the parameters of methods registered with
+/// `schema = true` are described by their JSON Schema; positional parameters are
+/// named `arg0`, `arg1`, ... and shared type definitions are moved to
+/// `components.schemas`. Results are not typed, so every method reports an
+/// unconstrained `result` schema.
+pub fn generate_openrpc_document() -> serde_json::Value {
+    let routes = ROUTE_TABLE.load();
+    let mut entries: Vec<&RpcServiceEntry> = routes.values().copied().collect();
+    entries.sort_unstable_by_key(|entry| entry.method);
+
+    let mut components = serde_json::Map::new();
+    let methods: Vec<serde_json::Value> = entries
+        .into_iter()
+        .map(|entry| openrpc_method(entry, &mut components))
+        .collect();
+
+    let mut document = serde_json::json!({
+        "openrpc": "1.2.6",
+        "info": {
+            "title": "JSON-RPC API",
+            "version": "1.0.0",
+        },
+        "methods": methods,
+    });
+    if !components.is_empty() {
+        document["components"] = serde_json::json!({ "schemas": components });
+    }
+    document
+}
+
+/// Writes the document of [`generate_openrpc_document()`] to `path` as pretty-printed JSON.
+///
+/// Services are collected when the final binary is linked, so a build script of the
+/// serving crate cannot see them yet. Call this from a small binary or test of that
+/// crate instead, e.g. a `gen-openrpc` target run as part of the build pipeline.
+///
+/// # Errors
+/// Returns the I/O error if the file cannot be written.
+pub fn generate_openrpc_to_file(path: &std::path::Path) -> std::io::Result<()> {
+    let document = serde_json::to_string_pretty(&generate_openrpc_document())?;
+    std::fs::write(path, document)
+}
+
+/// Builds the OpenRPC method object of one entry, moving the definitions of its
+/// parameter schema into `components`.
+fn openrpc_method(
+    entry: &RpcServiceEntry,
+    components: &mut serde_json::Map<String, serde_json::Value>,
+) -> serde_json::Value {
+    let params = params_schema(entry)
+        .map(|mut schema| {
+            if let Some(serde_json::Value::Object(definitions)) = schema
+                .as_object_mut()
+                .and_then(|schema| schema.remove("definitions"))
+            {
+                components.extend(definitions);
+            }
+            rewrite_definition_refs(&mut schema);
+            openrpc_params(entry.param_mode, &schema)
+        })
+        .unwrap_or_default();
+
+    let mut method = serde_json::json!({
+        "name": entry.method,
+        "paramStructure": match entry.param_mode {
+            ParamMode::Array => "by-position",
+            ParamMode::Object => "by-name",
+            ParamMode::Both => "either",
+        },
+        "params": params,
+        "result": { "name": "result", "schema": {} },
+    });
+    if let Some(description) = entry.description {
+        method["description"] = serde_json::Value::from(description);
+    }
+    method
+}
+
+/// The JSON Schema of the parameters of `entry`, if one was generated.
+#[cfg(feature = "schema")]
+fn params_schema(entry: &RpcServiceEntry) -> Option<serde_json::Value> {
+    get_schema(entry.method).and_then(|schema| serde_json::to_value(schema).ok())
+}
+
+/// The JSON Schema of the parameters of `entry`; never available without the
+/// `schema` feature.
+#[cfg(not(feature = "schema"))]
+fn params_schema(_entry: &RpcServiceEntry) -> Option<serde_json::Value> {
+    None
+}
+
+/// Splits a parameter schema into OpenRPC content descriptors: the properties of
+/// an object schema, or the items of a tuple schema.
+fn openrpc_params(mode: ParamMode, schema: &serde_json::Value) -> Vec<serde_json::Value> {
+    match mode {
+        // The schema of a `Both` method describes its named form.
+        ParamMode::Object | ParamMode::Both => {
+            let required: Vec<&str> = schema["required"]
+                .as_array()
+                .map(|names| names.iter().filter_map(serde_json::Value::as_str).collect())
+                .unwrap_or_default();
+            schema["properties"]
+                .as_object()
+                .map(|properties| {
+                    properties
+                        .iter()
+                        .map(|(name, schema)| {
+                            serde_json::json!({
+                                "name": name,
+                                "schema": schema,
+                                "required": required.contains(&name.as_str()),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+        ParamMode::Array => schema["items"]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, schema)| {
+                        serde_json::json!({
+                            "name": format!("arg{}", i),
+                            "schema": schema,
+                            "required": true,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Points every `$ref` to `#/definitions/...` at `#/components/schemas/...`.
+fn rewrite_definition_refs(schema: &mut serde_json::Value) {
+    match schema {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    serde_json::Value::String(target) if key == "$ref" => {
+                        if let Some(name) = target.strip_prefix("#/definitions/") {
+                            *target = format!("#/components/schemas/{}", name);
+                        }
+                    }
+                    _ => rewrite_definition_refs(value),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(rewrite_definition_refs),
+        _ => {}
     }
-    Err(RpcError::MethodNotFound)
 }
 
 /// Initializes the RPC service and logs all registered methods.
 ///
 /// It is recommended to call this during application startup to ensure the
-/// [`ROUTE_TABLE`] is valid and to verify registered services.
-pub fn init() {
-    tracing::info!("RPC Service initialized with {} methods", ROUTE_TABLE.len());
-    for method in ROUTE_TABLE.keys() {
+/// [`ROUTE_TABLE`] is valid and to verify registered services. It also enables
+/// the built-in `rpc.listMethods`, `rpc.describe` and `rpc.openrpc` methods, plus
+/// `rpc.getSchema` with the `schema` feature. An empty routing table is almost
+/// certainly a misconfiguration and is logged as a warning.
+///
+/// `config` controls how requests are validated; pass [`ServiceConfig::default()`]
+/// for the standard behaviour. Only the first configuration takes effect; a later
+/// call with a different one keeps it and logs a warning.
+pub fn init(config: ServiceConfig) {
+    if *SERVICE_CONFIG.get_or_init(|| config) != config {
+        tracing::warn!("RPC Service already initialized, keeping the existing configuration");
+    }
+    if service_config().catch_panics {
+        install_panic_hook();
+    }
+    BUILTINS_ENABLED.store(true, Ordering::Relaxed);
+    if REGISTERED_FALLBACK.is_some() {
+        tracing::info!("RPC Service has a registered fallback handler");
+    }
+    let routes = ROUTE_TABLE.load();
+    if routes.is_empty() {
+        tracing::warn!(
+            "RPC Service initialized with 0 methods, check that the service functions are linked into the binary"
+        );
+        return;
+    }
+    tracing::info!("RPC Service initialized with {} methods", routes.len());
+    for method in routes.keys() {
         tracing::info!("  - {}", method);
     }
 }
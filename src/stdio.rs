@@ -0,0 +1,317 @@
+//! # Stdio Transport
+//!
+//! This module speaks JSON-RPC over standard input and output, as language servers,
+//! build servers and other developer tools do. It provides:
+//! - [`StdioServer`], which serves the registered methods on the process' own stdio.
+//! - [`StdioClient`], which talks to a child process over its stdin/stdout.
+//!
+//! Messages are framed either one per line or with LSP-style `Content-Length`
+//! headers, see [`FramingMode`]. Requires the `stdio` feature.
+
+use crate::{
+    JsonRpcId,
+    client::JsonRpcClient,
+    error::RpcError,
+    request::JsonRpcRequest,
+    response::JsonRpcResponse,
+    service::{dispatch, error_response},
+};
+use serde::Serialize;
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    process::{Child, ChildStdin, ChildStdout, Command},
+    sync::Mutex,
+};
+
+/// How messages are delimited on the byte stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FramingMode {
+    /// One JSON message per line, terminated by `\n`.
+    #[default]
+    Newline,
+    /// Each message is preceded by a `Content-Length: N\r\n\r\n` header, as in the
+    /// Language Server Protocol.
+    ContentLength,
+}
+
+/// A JSON-RPC server reading requests from stdin and writing responses to stdout.
+///
+/// Every request is passed to [`dispatch()`]; a dispatch error is written back as an
+/// error response. Stdout carries the protocol, so logs must go to stderr.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::RpcError;
+/// # use a_rs_jsonrpc::stdio::{FramingMode, StdioServer};
+/// # async fn example() -> Result<(), RpcError> {
+/// a_rs_jsonrpc::init_rpc_service(a_rs_jsonrpc::ServiceConfig::default());
+/// StdioServer::run_with_framing(FramingMode::ContentLength).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StdioServer;
+
+impl StdioServer {
+    /// Serves newline-delimited requests until stdin is closed.
+    pub async fn run() -> Result<(), RpcError> {
+        Self::run_with_framing(FramingMode::Newline).await
+    }
+
+    /// Serves requests framed according to `framing` until stdin is closed.
+    ///
+    /// # Errors
+    /// Returns [`RpcError::IoError`] if stdio fails and [`RpcError::ParseError`] if a
+    /// `Content-Length` header is malformed.
+    pub async fn run_with_framing(framing: FramingMode) -> Result<(), RpcError> {
+        let mut reader = BufReader::new(tokio::io::stdin());
+        let mut writer = tokio::io::stdout();
+        while let Some(body) = read_frame(&mut reader, framing).await? {
+            if body.trim_ascii().is_empty() {
+                continue;
+            }
+            tracing::debug!("jsonrpc request body: {}", String::from_utf8_lossy(&body));
+            let resp = match dispatch(&body).await {
+                Ok(resp) => resp,
+                Err(err) => error_response(&body, err),
+            };
+            tracing::debug!("jsonrpc response body: {}", resp);
+            write_frame(&mut writer, framing, resp.as_bytes()).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads the next message, returning `None` once the stream is exhausted.
+async fn read_frame<S>(reader: &mut S, framing: FramingMode) -> Result<Option<Vec<u8>>, RpcError>
+where
+    S: AsyncBufRead + Unpin,
+{
+    match framing {
+        FramingMode::Newline => {
+            let mut line = Vec::new();
+            if reader.read_until(b'\n', &mut line).await? == 0 {
+                return Ok(None);
+            }
+            Ok(Some(line))
+        }
+        FramingMode::ContentLength => {
+            let mut length = None;
+            loop {
+                let mut header = String::new();
+                if reader.read_line(&mut header).await? == 0 {
+                    return Ok(None);
+                }
+                let header = header.trim();
+                if header.is_empty() {
+                    if length.is_some() {
+                        break;
+                    }
+                    continue;
+                }
+                let Some((name, value)) = header.split_once(':') else {
+                    continue;
+                };
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    length = Some(value.trim().parse::<usize>().map_err(|e| {
+                        RpcError::ParseError(format!("invalid Content-Length header: {}", e))
+                    })?);
+                }
+            }
+            let mut body = vec![0; length.unwrap_or_default()];
+            reader.read_exact(&mut body).await?;
+            Ok(Some(body))
+        }
+    }
+}
+
+/// Writes one message with the framing expected by the peer and flushes it.
+async fn write_frame<W>(writer: &mut W, framing: FramingMode, body: &[u8]) -> Result<(), RpcError>
+where
+    W: AsyncWrite + Unpin,
+{
+    match framing {
+        FramingMode::Newline => {
+            writer.write_all(body).await?;
+            writer.write_all(b"\n").await?;
+        }
+        FramingMode::ContentLength => {
+            writer
+                .write_all(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes())
+                .await?;
+            writer.write_all(body).await?;
+        }
+    }
+    writer.flush().await?;
+    Ok(())
+}
+
+/// The pipes connected to the child process.
+#[derive(Debug)]
+struct ChildPipes {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+/// A JSON-RPC client talking to a child process over its stdin/stdout.
+///
+/// Intended for testing stdio servers: calls are sent one at a time and each waits
+/// for the next response frame. The child is killed when the client is dropped.
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{JsonRpcResponse, RpcError};
+/// # use a_rs_jsonrpc::stdio::{FramingMode, StdioClient};
+/// # async fn example() -> Result<(), RpcError> {
+/// let mut command = tokio::process::Command::new("./target/debug/examples/stdio_server");
+/// let client = StdioClient::spawn(&mut command, FramingMode::Newline)?;
+/// let resp: JsonRpcResponse<i32> = client.call_v2("addArray", (10, 20)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct StdioClient {
+    framing: FramingMode,
+    pipes: Mutex<ChildPipes>,
+    _child: Child,
+}
+
+impl StdioClient {
+    /// Spawns `command` with piped stdin/stdout and connects to it.
+    pub fn spawn(command: &mut Command, framing: FramingMode) -> Result<Self, RpcError> {
+        let mut child = command
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            return Err(std::io::Error::other("child process stdio is not piped").into());
+        };
+        Ok(StdioClient {
+            framing,
+            pipes: Mutex::new(ChildPipes {
+                stdin,
+                stdout: BufReader::new(stdout),
+            }),
+            _child: child,
+        })
+    }
+
+    /// Sends a JSON-RPC 1.0 request to the child process.
+    pub async fn call_v1<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v1(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.send(&body).await
+    }
+
+    /// Sends a JSON-RPC 2.0 request to the child process.
+    pub async fn call_v2<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.send(&body).await
+    }
+
+    async fn send<T, R>(&self, body: &JsonRpcRequest<T>) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        T: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let req = serde_json::to_vec(body)?;
+        tracing::debug!("jsonrpc request body: {}", String::from_utf8_lossy(&req));
+        let mut pipes = self.pipes.lock().await;
+        write_frame(&mut pipes.stdin, self.framing, &req).await?;
+        let Some(text) = read_frame(&mut pipes.stdout, self.framing).await? else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "child process closed stdout",
+            )
+            .into());
+        };
+        tracing::debug!("jsonrpc response body: {}", String::from_utf8_lossy(&text));
+        Ok(serde_json::from_slice::<JsonRpcResponse<R>>(&text)?)
+    }
+}
+
+/// Sends a request without parameters to the child process.
+///
+/// The child process is the endpoint, so the `url` and `content_type` arguments are ignored.
+#[async_trait::async_trait]
+impl JsonRpcClient for StdioClient {
+    async fn send_v1_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(vec![]);
+        self.send(&body).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(vec![]);
+        self.send(&body).await
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(serde_json::json!({}));
+        self.send(&body).await
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(serde_json::json!({}));
+        self.send(&body).await
+    }
+}
@@ -0,0 +1,384 @@
+//! # Testing Utilities
+//!
+//! This module provides client transports for testing without a running server:
+//! - [`InProcessTransport`] hands requests straight to the registered service
+//!   handlers instead of sending them over the network.
+//! - [`MockTransport`] answers requests from a list of expected calls.
+
+use crate::{
+    JsonRpcId,
+    client::JsonRpcClient,
+    error::RpcError,
+    request::JsonRpcRequest,
+    response::{JsonRpcError, JsonRpcResponse},
+    service::{RpcUserData, dispatch_with_context},
+};
+use serde::Serialize;
+use std::{
+    any::Any,
+    sync::{Arc, Mutex},
+};
+
+/// A transport that dispatches requests to the in-process routing table.
+///
+/// Requests are serialized exactly as they would be for HTTP and passed to
+/// [`dispatch_with_context()`]. An error raised by the dispatcher is returned as an
+/// error response object, just like a server would send it, so clients observe the
+/// same responses as over the network.
+///
+/// ### Example
+/// ```rust,standalone_crate
+/// # use a_rs_jsonrpc::testing::InProcessTransport;
+/// # use a_rs_jsonrpc::{JsonRpcResponse, RpcError, jsonrpc_service_fn_array};
+/// #[jsonrpc_service_fn_array(method = "addArray", version = "v2")]
+/// async fn add(a: i32, b: i32) -> Result<i32, RpcError> {
+///     Ok(a + b)
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), RpcError> {
+/// let transport = InProcessTransport::new();
+/// let resp: JsonRpcResponse<i32> = transport.call_v2("addArray", (10, 20)).await?;
+/// assert_eq!(resp.result, Some(30));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct InProcessTransport {
+    user_data: RpcUserData,
+}
+
+impl std::fmt::Debug for InProcessTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InProcessTransport").finish_non_exhaustive()
+    }
+}
+
+impl Default for InProcessTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InProcessTransport {
+    /// Creates a transport dispatching without user data, like [`crate::dispatch_rpc_request`].
+    pub fn new() -> Self {
+        Self::with_state(())
+    }
+
+    /// Creates a transport that hands `state` to handlers declared with `has_context = true`.
+    pub fn with_state<S: Any + Send + Sync>(state: S) -> Self {
+        InProcessTransport {
+            user_data: Arc::new(state),
+        }
+    }
+
+    /// Sends a JSON-RPC 1.0 request to the in-process handlers.
+    pub async fn call_v1<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v1(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.send(&body).await
+    }
+
+    /// Sends a JSON-RPC 2.0 request to the in-process handlers.
+    pub async fn call_v2<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.send(&body).await
+    }
+
+    async fn send<T, R>(&self, body: &JsonRpcRequest<T>) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        T: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let req = serde_json::to_vec(body)?;
+        match dispatch_with_context(&req, self.user_data.clone()).await {
+            Ok(text) => Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?),
+            Err(err) => Ok(JsonRpcResponse {
+                jsonrpc: body.jsonrpc,
+                result: None,
+                error: Some(JsonRpcError::from(err)),
+                id: body.id.clone(),
+            }),
+        }
+    }
+}
+
+/// Sends a request without parameters to the in-process handlers.
+///
+/// There is no network involved, so the `url` and `content_type` arguments are ignored.
+#[async_trait::async_trait]
+impl JsonRpcClient for InProcessTransport {
+    async fn send_v1_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(vec![]);
+        self.send(&body).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(vec![]);
+        self.send(&body).await
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(serde_json::json!({}));
+        self.send(&body).await
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(serde_json::json!({}));
+        self.send(&body).await
+    }
+}
+
+/// One expected call registered with [`MockTransport::expect`].
+#[derive(Debug)]
+struct Expectation {
+    method: String,
+    params: serde_json::Value,
+    result: serde_json::Value,
+    called: bool,
+}
+
+/// A transport answering requests from a list of expected calls.
+///
+/// Each expectation matches one call with the same method and params and is
+/// consumed by it. Cloning the transport shares the expectations.
+///
+/// ### Example
+/// ```rust
+/// # use a_rs_jsonrpc::testing::MockTransport;
+/// # use a_rs_jsonrpc::{JsonRpcResponse, RpcError};
+/// # use serde_json::json;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), RpcError> {
+/// let mock = MockTransport::new();
+/// mock.expect("addArray", json!([10, 20]), json!(30));
+///
+/// let resp: JsonRpcResponse<i32> = mock.call_v2("addArray", (10, 20)).await?;
+/// assert_eq!(resp.result, Some(30));
+/// mock.assert_all_called();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    expectations: Arc<Mutex<Vec<Expectation>>>,
+}
+
+impl MockTransport {
+    /// Creates a transport without expectations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expects one call of `method` with `params`, answered with `result`.
+    ///
+    /// Requests sent through the [`JsonRpcClient`] impl carry no parameters, which
+    /// is matched by `json!([])` or `json!({})` depending on the mode.
+    pub fn expect(&self, method: &str, params: serde_json::Value, result: serde_json::Value) {
+        self.expectations.lock().unwrap().push(Expectation {
+            method: method.to_string(),
+            params,
+            result,
+            called: false,
+        });
+    }
+
+    /// Panics if any expectation has not been consumed by a call.
+    pub fn assert_all_called(&self) {
+        let expectations = self.expectations.lock().unwrap();
+        let pending: Vec<String> = expectations
+            .iter()
+            .filter(|e| !e.called)
+            .map(|e| format!("{}({})", e.method, e.params))
+            .collect();
+        assert!(
+            pending.is_empty(),
+            "expected calls not made: {}",
+            pending.join(", ")
+        );
+    }
+
+    /// Sends a JSON-RPC 1.0 request to the mock.
+    pub async fn call_v1<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v1(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.respond(&body)
+    }
+
+    /// Sends a JSON-RPC 2.0 request to the mock.
+    pub async fn call_v2<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.respond(&body)
+    }
+
+    /// Consumes the first pending expectation matching the request.
+    #[allow(deprecated)]
+    fn respond<T, R>(&self, body: &JsonRpcRequest<T>) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        T: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let params = serde_json::to_value(&body.params)?;
+        let mut expectations = self.expectations.lock().unwrap();
+        let Some(expectation) = expectations
+            .iter_mut()
+            .find(|e| !e.called && e.method == body.method && e.params == params)
+        else {
+            return Err(RpcError::CustomError(
+                format!("unexpected call: {}({})", body.method, params).into(),
+            ));
+        };
+        expectation.called = true;
+        Ok(JsonRpcResponse {
+            jsonrpc: body.jsonrpc,
+            result: Some(serde_json::from_value(expectation.result.clone())?),
+            error: None,
+            id: body.id.clone(),
+        })
+    }
+}
+
+/// Sends a request without parameters to the mock.
+///
+/// There is no network involved, so the `url` and `content_type` arguments are ignored.
+#[async_trait::async_trait]
+impl JsonRpcClient for MockTransport {
+    async fn send_v1_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(vec![]);
+        self.respond(&body)
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(vec![]);
+        self.respond(&body)
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(serde_json::json!({}));
+        self.respond(&body)
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(serde_json::json!({}));
+        self.respond(&body)
+    }
+}
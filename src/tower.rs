@@ -0,0 +1,143 @@
+//! # Tower Integration
+//!
+//! This module exposes the server-side dispatcher as a [`tower::Service`], so it can
+//! be mounted in any framework built on `tower` and wrapped with standard tower
+//! middleware. Requires the `tower` feature.
+
+pub use crate::service::status_code_of;
+use crate::service::{RpcUserData, dispatch_bytes_with_context, error_response};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use std::{
+    any::Any,
+    convert::Infallible,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// A [`tower::Service`] dispatching HTTP request bodies to the registered RPC methods.
+///
+/// Successful dispatches produce a `200 OK` response carrying the JSON-RPC response.
+/// A dispatch error is returned as a JSON-RPC error response with a matching HTTP
+/// status, see [`status_code_of()`].
+///
+/// ### Example
+/// ```rust
+/// # use a_rs_jsonrpc::JsonRpcDispatcher;
+/// # struct AppState;
+/// # let app_state = AppState;
+/// let service = tower::ServiceBuilder::new()
+///     .concurrency_limit(64)
+///     .service(JsonRpcDispatcher::with_state(app_state));
+/// ```
+#[derive(Clone, Default)]
+pub struct JsonRpcDispatcher {
+    state: Option<RpcUserData>,
+}
+
+impl std::fmt::Debug for JsonRpcDispatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonRpcDispatcher")
+            .field("has_state", &self.state.is_some())
+            .finish()
+    }
+}
+
+impl JsonRpcDispatcher {
+    /// Creates a dispatcher without user data, like [`crate::dispatch_rpc_request`].
+    pub fn new() -> Self {
+        JsonRpcDispatcher { state: None }
+    }
+
+    /// Creates a dispatcher that hands `state` to handlers declared with `has_context = true`.
+    pub fn with_state<S: Any + Send + Sync>(state: S) -> Self {
+        JsonRpcDispatcher {
+            state: Some(Arc::new(state)),
+        }
+    }
+
+    /// Dispatches one request body into a complete HTTP response.
+    fn respond(&self, body: Bytes) -> BoxFuture<'static, http::Response<String>> {
+        let user_data = self.state.clone().unwrap_or_else(|| Arc::new(()));
+        Box::pin(async move {
+            let (status, text) = match dispatch_bytes_with_context(body.clone(), user_data).await {
+                Ok(text) => (http::StatusCode::OK, text),
+                Err(err) => (status_code_of(&err), error_response(&body, err)),
+            };
+            http::Response::builder()
+                .status(status)
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(text)
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl tower::Service<http::Request<Bytes>> for JsonRpcDispatcher {
+    type Response = http::Response<String>;
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: http::Request<Bytes>) -> Self::Future {
+        let resp = self.respond(req.into_body());
+        Box::pin(async move { Ok(resp.await) })
+    }
+}
+
+/// Layering the dispatcher over a service answers `POST` requests with JSON-RPC and
+/// passes every other request to the wrapped service.
+///
+/// ### Example
+/// ```rust
+/// # use a_rs_jsonrpc::JsonRpcDispatcher;
+/// # use axum::http;
+/// # let health_check = tower::service_fn(|_req: http::Request<a_rs_jsonrpc::bytes::Bytes>| async {
+/// #     Ok::<_, std::convert::Infallible>(http::Response::new(String::from("ok")))
+/// # });
+/// let service = tower::ServiceBuilder::new()
+///     .layer(JsonRpcDispatcher::new())
+///     .service(health_check);
+/// ```
+impl<S> tower::Layer<S> for JsonRpcDispatcher {
+    type Service = JsonRpcService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        JsonRpcService {
+            dispatcher: self.clone(),
+            inner,
+        }
+    }
+}
+
+/// The service produced by layering a [`JsonRpcDispatcher`] over `S`.
+#[derive(Debug, Clone)]
+pub struct JsonRpcService<S> {
+    dispatcher: JsonRpcDispatcher,
+    inner: S,
+}
+
+impl<S> tower::Service<http::Request<Bytes>> for JsonRpcService<S>
+where
+    S: tower::Service<http::Request<Bytes>, Response = http::Response<String>>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<String>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<Bytes>) -> Self::Future {
+        if req.method() != http::Method::POST {
+            return Box::pin(self.inner.call(req));
+        }
+        let resp = self.dispatcher.respond(req.into_body());
+        Box::pin(async move { Ok(resp.await) })
+    }
+}
@@ -0,0 +1,222 @@
+//! # WebSocket Client Transport
+//!
+//! This module provides [`WsJsonRpcClient`], a JSON-RPC client that keeps one persistent
+//! WebSocket connection open and multiplexes concurrent calls over it. Responses are
+//! matched to their requests by `id`, so calls may complete in any order.
+//!
+//! Requires the `ws` feature and a Tokio runtime.
+
+use crate::{
+    JsonRpcId, client::JsonRpcClient, error::RpcError, pending::PendingRequests,
+    request::JsonRpcRequest, response::JsonRpcResponse,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message;
+
+type PendingCalls = Arc<Mutex<PendingRequests>>;
+
+/// A JSON-RPC client over a single persistent WebSocket connection.
+///
+/// The connection is driven by background Tokio tasks: one writes outgoing request
+/// frames, the other reads response frames and hands each one to the call waiting
+/// for its `id`. Cloning the client shares the connection.
+///
+/// Parameters passed to [`WsJsonRpcClient::call_v1`] and [`WsJsonRpcClient::call_v2`]
+/// are serialized as-is, just like with [`crate::SharedClient`].
+///
+/// ### Example
+/// ```rust,no_run
+/// # use a_rs_jsonrpc::{JsonRpcResponse, RpcError, WsJsonRpcClient};
+/// # async fn example() -> Result<(), RpcError> {
+/// let client = WsJsonRpcClient::new("ws://localhost:3000/ws").await?;
+/// let resp: JsonRpcResponse<i32> = client.call_v2("addArray", (10, 20)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WsJsonRpcClient {
+    outgoing: mpsc::UnboundedSender<Message>,
+    pending: PendingCalls,
+}
+
+/// The only part of a response frame needed to route it to its caller.
+#[derive(Deserialize)]
+struct ResponseId {
+    id: Option<JsonRpcId>,
+}
+
+impl WsJsonRpcClient {
+    /// Connects to `url` and spawns the tasks driving the connection.
+    pub async fn new(url: &str) -> Result<Self, RpcError> {
+        let (stream, _) = tokio_tungstenite::connect_async(url)
+            .await
+            .map_err(std::io::Error::other)?;
+        let (mut sink, mut source) = stream.split();
+        let (outgoing, mut outgoing_rx) = mpsc::unbounded_channel::<Message>();
+        let pending = PendingCalls::default();
+
+        tokio::spawn(async move {
+            while let Some(frame) = outgoing_rx.recv().await {
+                if let Err(e) = sink.send(frame).await {
+                    tracing::warn!("websocket send failed: {}", e);
+                    break;
+                }
+            }
+        });
+
+        let reader_pending = pending.clone();
+        tokio::spawn(async move {
+            while let Some(frame) = source.next().await {
+                let text = match frame {
+                    Ok(Message::Text(text)) => text.as_str().to_owned(),
+                    Ok(Message::Close(_)) => break,
+                    Ok(_) => continue,
+                    Err(e) => {
+                        tracing::warn!("websocket receive failed: {}", e);
+                        break;
+                    }
+                };
+                tracing::debug!("jsonrpc response body: {}", text);
+                let Ok(ResponseId { id: Some(id) }) = serde_json::from_str::<ResponseId>(&text)
+                else {
+                    tracing::debug!("dropping websocket frame without an id");
+                    continue;
+                };
+                if !reader_pending.lock().unwrap().resolve(&id, text) {
+                    tracing::debug!("dropping response for unknown id {:?}", id);
+                }
+            }
+            // dropping the senders wakes every waiting call with a closed-connection error
+            reader_pending.lock().unwrap().clear();
+        });
+
+        Ok(WsJsonRpcClient { outgoing, pending })
+    }
+
+    /// Sends a JSON-RPC 1.0 request over the connection.
+    pub async fn call_v1<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v1(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.send(&body).await
+    }
+
+    /// Sends a JSON-RPC 2.0 request over the connection.
+    pub async fn call_v2<P, R>(
+        &self,
+        method: &str,
+        params: P,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        P: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let mut body: JsonRpcRequest<serde_json::Value> =
+            JsonRpcRequest::new_v2(JsonRpcId::next_number(), method);
+        body.set_params(serde_json::to_value(params)?);
+        self.send(&body).await
+    }
+
+    /// Sends one request frame and waits for the response frame carrying the same `id`.
+    async fn send<T, R>(&self, body: &JsonRpcRequest<T>) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        T: Serialize,
+        R: serde::de::DeserializeOwned,
+    {
+        let text = serde_json::to_string(body)?;
+        tracing::debug!("jsonrpc request body: {}", text);
+        let rx = self.pending.lock().unwrap().insert(body.id.clone());
+        if self.outgoing.send(Message::text(text)).is_err() {
+            self.pending.lock().unwrap().remove(&body.id);
+            return Err(connection_closed());
+        }
+        let text = rx.await.map_err(|_| connection_closed())?;
+        Ok(serde_json::from_str::<JsonRpcResponse<R>>(&text)?)
+    }
+}
+
+fn connection_closed() -> RpcError {
+    std::io::Error::new(
+        std::io::ErrorKind::ConnectionAborted,
+        "websocket connection closed",
+    )
+    .into()
+}
+
+/// Sends a request without parameters over the connection.
+///
+/// The connection is bound to the URL given to [`WsJsonRpcClient::new`], so the `url`
+/// and `content_type` arguments are ignored.
+#[async_trait::async_trait]
+impl JsonRpcClient for WsJsonRpcClient {
+    async fn send_v1_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(vec![]);
+        self.send(&body).await
+    }
+
+    async fn send_v2_request<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<Vec<serde_json::Value>> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(vec![]);
+        self.send(&body).await
+    }
+
+    async fn send_v1_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v1(id, method);
+        body.set_params(serde_json::json!({}));
+        self.send(&body).await
+    }
+
+    async fn send_v2_request_obj<R>(
+        &self,
+        _url: &str,
+        _content_type: &str,
+        method: &str,
+    ) -> Result<JsonRpcResponse<R>, RpcError>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        let id = JsonRpcId::next_number();
+        let mut body: JsonRpcRequest<serde_json::Value> = JsonRpcRequest::new_v2(id, method);
+        body.set_params(serde_json::json!({}));
+        self.send(&body).await
+    }
+}
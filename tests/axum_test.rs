@@ -0,0 +1,92 @@
+#![cfg(feature = "axum")]
+
+use a_rs_jsonrpc::{JsonRpcBody, RpcContext, RpcError, jsonrpc_service_fn_array};
+use axum::{Router, body::Body, http, routing::post};
+use std::sync::Arc;
+use tower::ServiceExt;
+
+#[jsonrpc_service_fn_array(method = "axumAdd", version = "v2")]
+async fn axum_add(a: i32, b: i32) -> Result<i32, RpcError> {
+    Ok(a + b)
+}
+
+#[jsonrpc_service_fn_array(method = "axumTenant", version = "v2", has_context = true)]
+async fn axum_tenant(ctx: &RpcContext) -> Result<String, RpcError> {
+    Ok(ctx.user_data::<String>().cloned().unwrap_or_default())
+}
+
+async fn call(app: Router, content_type: &str, body: &'static str) -> (http::StatusCode, String) {
+    let req = http::Request::post("/")
+        .header(http::header::CONTENT_TYPE, content_type)
+        .body(Body::from(body))
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    let status = resp.status();
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_json_rpc_body_handler() {
+    let app = Router::new().route("/", post(JsonRpcBody::handler));
+
+    let (status, body) = call(
+        app.clone(),
+        "application/json",
+        r#"{"jsonrpc":"2.0","method":"axumAdd","params":[1,2],"id":1}"#,
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["result"], 3);
+
+    let (status, body) = call(
+        app.clone(),
+        "application/json",
+        r#"[{"jsonrpc":"2.0","method":"axumAdd","params":[1,2],"id":1},
+            {"jsonrpc":"2.0","method":"axumAdd","params":[3,4],"id":2}]"#,
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body[1]["result"], 7);
+
+    let (status, body) = call(
+        app.clone(),
+        "application/json",
+        r#"{"jsonrpc":"2.0","method":"axumMissing","params":[],"id":3}"#,
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::NOT_FOUND);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["id"], 3);
+
+    let (status, _) = call(
+        app,
+        "text/plain",
+        r#"{"jsonrpc":"2.0","method":"axumAdd","params":[1,2],"id":4}"#,
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[tokio::test]
+async fn test_json_rpc_body_handler_with_state() {
+    let app = Router::new().route(
+        "/",
+        post(JsonRpcBody::handler_with_state(Arc::new(
+            "tenant-a".to_string(),
+        ))),
+    );
+    let (status, body) = call(
+        app,
+        "application/json",
+        r#"{"jsonrpc":"2.0","method":"axumTenant","params":[],"id":5}"#,
+    )
+    .await;
+    assert_eq!(status, http::StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body["result"], "tenant-a");
+}
@@ -66,6 +66,46 @@ async fn test_zero_params_request_with_rpc_method() {
     assert_eq!(resp.result, Some("pong".to_string()));
 }
 
+static PING_SUCCESS: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+fn on_ping_success(resp: &JsonRpcResponse<String>) {
+    tracing::debug!("ping succeeded: {:?}", resp.result);
+    PING_SUCCESS.store(true, std::sync::atomic::Ordering::SeqCst);
+}
+
+/// `on_success` callbacks are invoked with the response before it is returned.
+#[tokio::test]
+async fn test_zero_params_request_with_rpc_method_on_success() {
+    init_tracing();
+    #[rpc_method(
+        url = "http://localhost:3000/",
+        method = "ping",
+        version = "v2",
+        on_success = "on_ping_success"
+    )]
+    async fn ping() -> Result<JsonRpcResponse<String>, RpcError> {}
+    let resp = ping().await.unwrap();
+    assert_eq!(resp.result, Some("pong".to_string()));
+    assert!(PING_SUCCESS.load(std::sync::atomic::Ordering::SeqCst));
+}
+
+/// `timeout_ms` bounds each attempt and `retry` repeats transport failures.
+#[tokio::test]
+async fn test_zero_params_request_with_rpc_method_timeout_and_retry() {
+    init_tracing();
+    #[rpc_method(
+        url = "http://localhost:3000/",
+        method = "ping",
+        version = "v2",
+        timeout_ms = 5000,
+        retry = 2,
+        retry_delay_ms = 50
+    )]
+    async fn ping() -> Result<JsonRpcResponse<String>, RpcError> {}
+    let resp = ping().await.unwrap();
+    assert_eq!(resp.result, Some("pong".to_string()));
+}
+
 const HELLO: &str = "hello";
 
 // running all one param tests with `cargo run --example echo`
@@ -134,6 +174,19 @@ async fn test_one_params_request_with_rpc_method() {
     assert_eq!(resp.result, Some(HELLO.to_string()))
 }
 
+#[tokio::test]
+async fn test_one_params_request_with_rpc_method_log_level() {
+    init_tracing();
+    #[rpc_method(
+        url = "http://localhost:3000/",
+        method = "echoArray",
+        log_level = "info"
+    )]
+    async fn test_echo(msg: &str) -> Result<JsonRpcResponse<String>, RpcError> {}
+    let resp = test_echo(HELLO).await.unwrap();
+    assert_eq!(resp.result, Some(HELLO.to_string()))
+}
+
 // running all two params tests with `cargo run --example arith`
 
 /// Client tests for two parameter RPC requests to the arith service.
@@ -182,6 +235,96 @@ async fn test_two_params_request_with_struct_array() {
     assert_eq!(resp.result, Some(30));
 }
 
+#[test]
+fn test_struct_array_params_skip_serializing_if() {
+    #[derive(Clone, Serialize, JsonRpcClient)]
+    #[jsonrpc(
+        url = "http://localhost:3000/",
+        content_type = "application/json",
+        method = "addArray"
+    )]
+    struct AddParams {
+        a: i32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        b: Option<i32>,
+    }
+    let params = AddParams { a: 10, b: None };
+    assert_eq!(
+        params.debug_params_flatten(),
+        Some(vec![serde_json::json!(10)])
+    );
+    let params = AddParams { a: 10, b: Some(20) };
+    assert_eq!(
+        params.debug_params_flatten(),
+        Some(vec![serde_json::json!(10), serde_json::json!(20)])
+    );
+}
+
+#[test]
+fn test_dry_run_builds_request_without_sending() {
+    #[derive(Clone, Serialize, JsonRpcClient)]
+    #[jsonrpc(
+        url = "http://localhost:3000/",
+        content_type = "application/json",
+        method = "addArray"
+    )]
+    struct AddParams {
+        a: i32,
+        b: i32,
+    }
+    let req = AddParams { a: 10, b: 20 }
+        .dry_run(TEST_URL, APP_JSON, "addArray")
+        .unwrap();
+    assert_eq!(req.method, "addArray");
+    assert_eq!(req.jsonrpc, JsonRpcVersion::V2_0);
+    assert_eq!(req.params.into_option(), Some(serde_json::json!([10, 20])));
+
+    let req = (10, 20).dry_run(TEST_URL, APP_JSON, "addArray").unwrap();
+    assert_eq!(req.params.into_option(), Some(serde_json::json!([10, 20])));
+    let req = HELLO.dry_run(TEST_URL, APP_JSON, "echoArray").unwrap();
+    assert_eq!(req.params.into_option(), Some(serde_json::json!([HELLO])));
+    let req = None::<i32>.dry_run(TEST_URL, APP_JSON, "ping").unwrap();
+    assert_eq!(req.params.into_option(), Some(serde_json::json!([])));
+
+    // A reference uses the referenced type's overrides.
+    let params = AddParams { a: 10, b: 20 };
+    let req =
+        <&AddParams as JsonRpcClient>::dry_run(&&params, TEST_URL, APP_JSON, "addArray").unwrap();
+    assert_eq!(req.params.into_option(), Some(serde_json::json!([10, 20])));
+    let req = <&Option<i32> as JsonRpcClient>::dry_run(&&None, TEST_URL, APP_JSON, "ping").unwrap();
+    assert_eq!(req.params.into_option(), Some(serde_json::json!([])));
+}
+
+#[tokio::test]
+async fn test_two_params_request_with_struct_with_client() {
+    init_tracing();
+    #[derive(Clone, Serialize, JsonRpcClient)]
+    #[jsonrpc(
+        url = "http://localhost:3000/",
+        content_type = "application/json",
+        method = "addArray"
+    )]
+    struct AddParams {
+        a: i32,
+        b: i32,
+    }
+    let client = reqwest::Client::new();
+    use a_rs_jsonrpc::JsonRpcClientCall;
+    let resp: JsonRpcResponse<i32> = AddParams { a: 10, b: 20 }
+        .with_client(client.clone())
+        .call_rpc_v1()
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(30));
+
+    let bound: AddParamsWithClient = AddParams { a: 1, b: 2 }.with_client(client);
+    let resp: JsonRpcResponse<i32> = bound
+        .send_v1_request("http://localhost:3000/", "application/json", "addArray")
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(3));
+}
+
 /// construct a two parameter request send with array params with a struct object.
 /// then you can easily use the generated `call_rpc_v1` method to send the request.
 /// be sure to import `a_rs_jsonrpc::JsonRpcClientCall` trait into namespace.
@@ -239,3 +382,45 @@ async fn test_two_params_request_with_rpc_method_obj() {
     assert_eq!(resp.jsonrpc, JsonRpcVersion::V1_0);
     assert_eq!(resp.result, Some(30));
 }
+
+/// `rpc_method` keeps the visibility, generics and where clause of the annotated function.
+mod visibility {
+    use super::*;
+
+    pub mod nested {
+        use super::*;
+
+        #[rpc_method(
+            url = "http://localhost:3000/",
+            method = "addArray",
+            version = "v1",
+            mode = "array"
+        )]
+        pub(super) async fn add_super(a: i32, b: i32) -> Result<JsonRpcResponse<i32>, RpcError> {}
+    }
+
+    #[rpc_method(
+        url = "http://localhost:3000/",
+        method = "addObj",
+        version = "v1",
+        mode = "obj"
+    )]
+    pub(crate) async fn add_crate<T>(lhs: T, rhs: T) -> Result<JsonRpcResponse<i32>, RpcError>
+    where
+        T: Serialize + Clone,
+    {
+    }
+
+    pub(crate) async fn add_via_super(a: i32, b: i32) -> Result<JsonRpcResponse<i32>, RpcError> {
+        nested::add_super(a, b).await
+    }
+}
+
+#[tokio::test]
+async fn test_rpc_method_restricted_visibility() {
+    init_tracing();
+    let resp = visibility::add_via_super(10, 20).await.unwrap();
+    assert_eq!(resp.result, Some(30));
+    let resp = visibility::add_crate(10, 20).await.unwrap();
+    assert_eq!(resp.result, Some(30));
+}
@@ -0,0 +1,122 @@
+use a_rs_jsonrpc::{
+    RpcError, ServiceConfig, dispatch_rpc_batch_request, dispatch_rpc_request,
+    dispatch_rpc_request_from_method, init_rpc_service, jsonrpc_service_fn_array,
+    try_dispatch_rpc_request,
+};
+
+#[jsonrpc_service_fn_array(method = "configAdd", version = "v2")]
+async fn config_add(a: i32, b: i32) -> Result<i32, RpcError> {
+    Ok(a + b)
+}
+
+fn init_config() {
+    init_rpc_service(
+        ServiceConfig::strict()
+            .strict_version(false)
+            .max_request_bytes(128)
+            .enable_batch(false),
+    );
+}
+
+#[tokio::test]
+async fn test_service_config_validates_requests() {
+    init_config();
+
+    let resp =
+        dispatch_rpc_request(br#"{"jsonrpc":"2.0","method":"configAdd","params":[1,2],"id":1}"#)
+            .await
+            .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&resp).unwrap();
+    assert_eq!(resp["result"], 3);
+
+    // With `strict_version` disabled a v1 request reaches a v2 method.
+    let resp =
+        dispatch_rpc_request(br#"{"jsonrpc":"1.0","method":"configAdd","params":[2,3],"id":2}"#)
+            .await
+            .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&resp).unwrap();
+    assert_eq!(resp["result"], 5);
+
+    let err = dispatch_rpc_request(
+        br#"{"jsonrpc":"2.0","method":"configAdd","params":[1,2],"id":3,"extra":true}"#,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, RpcError::InvalidRequest(msg) if msg.contains("extra")));
+
+    let oversized = format!(
+        r#"{{"jsonrpc":"2.0","method":"configAdd","params":[1,2],"id":"{}"}}"#,
+        "x".repeat(128)
+    );
+    let err = dispatch_rpc_request(oversized.as_bytes())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RpcError::InvalidRequest(_)));
+
+    let err = dispatch_rpc_batch_request(
+        br#"[{"jsonrpc":"2.0","method":"configAdd","params":[1,2],"id":4}]"#,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, RpcError::InvalidRequest(_)));
+
+    // Oversized batches are rejected on size before the batch is parsed.
+    let oversized = format!(
+        r#"[{{"jsonrpc":"2.0","method":"configAdd","params":[1,2],"id":"{}"}}]"#,
+        "x".repeat(128)
+    );
+    let err = dispatch_rpc_batch_request(oversized.as_bytes())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RpcError::InvalidRequest(msg) if msg.contains("exceeds")));
+}
+
+#[tokio::test]
+async fn test_service_config_applies_to_try_dispatch_and_dispatch_from_method() {
+    init_config();
+    let oversized = format!(
+        r#"{{"jsonrpc":"2.0","method":"configAdd","params":[1,2],"id":"{}"}}"#,
+        "x".repeat(128)
+    );
+
+    let err = try_dispatch_rpc_request(oversized.as_bytes())
+        .unwrap()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RpcError::InvalidRequest(msg) if msg.contains("exceeds")));
+    let err = try_dispatch_rpc_request(
+        br#"{"jsonrpc":"2.0","method":"configAdd","params":[1,2],"id":1,"extra":true}"#,
+    )
+    .unwrap()
+    .await
+    .unwrap_err();
+    assert!(matches!(err, RpcError::InvalidRequest(msg) if msg.contains("extra")));
+
+    let err = dispatch_rpc_request_from_method("configAdd", oversized.as_bytes())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RpcError::InvalidRequest(msg) if msg.contains("exceeds")));
+    let err = dispatch_rpc_request_from_method(
+        "configAdd",
+        br#"{"jsonrpc":"2.0","params":[1,2],"id":2,"extra":true}"#,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, RpcError::InvalidRequest(msg) if msg.contains("extra")));
+    let err = dispatch_rpc_request_from_method(
+        "configAdd",
+        br#"{"jsonrpc":"2.0","params":{"a":1,"b":2},"id":3}"#,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, RpcError::InvalidParams(_)));
+
+    let resp = dispatch_rpc_request_from_method(
+        "configAdd",
+        br#"{"jsonrpc":"2.0","params":[1,2],"id":4}"#,
+    )
+    .await
+    .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&resp).unwrap();
+    assert_eq!(resp["result"], 3);
+}
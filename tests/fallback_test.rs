@@ -0,0 +1,22 @@
+use a_rs_jsonrpc::{
+    RpcError, dispatch_rpc_request, service::RpcUserData, set_rpc_fallback_handler,
+};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+
+/// A fallback that answers every unknown method with the raw request echoed back.
+fn echo_fallback(
+    req: Bytes,
+    _user_data: RpcUserData,
+) -> BoxFuture<'static, Result<String, RpcError>> {
+    let body = String::from_utf8_lossy(&req).into_owned();
+    Box::pin(async move { Ok(body) })
+}
+
+#[tokio::test]
+async fn test_fallback_handler_receives_unknown_methods() {
+    set_rpc_fallback_handler(echo_fallback);
+    let body = br#"{"jsonrpc":"2.0","method":"unknown","id":1}"#;
+    let text = dispatch_rpc_request(body).await.unwrap();
+    assert_eq!(text.as_bytes(), body);
+}
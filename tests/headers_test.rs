@@ -0,0 +1,291 @@
+use a_rs_jsonrpc::{JsonRpcClient, JsonRpcResponse, RpcError, response::JsonRpcError, rpc_method};
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+/// Starts a mock JSON-RPC endpoint on a fixed address, since `rpc_method` urls are literals.
+async fn start_mock_server(addr: &str) -> MockServer {
+    let listener = std::net::TcpListener::bind(addr).unwrap();
+    MockServer::builder().listener(listener).start().await
+}
+
+#[tokio::test]
+async fn test_rpc_method_sends_custom_headers() {
+    let server = start_mock_server("127.0.0.1:3901").await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::header("X-Api-Key", "secret"))
+        .and(matchers::header("X-Tenant", "acme"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": 30,
+            "id": 1
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    #[rpc_method(
+        url = "http://127.0.0.1:3901/",
+        method = "addArray",
+        headers = [("X-Api-Key", "secret"), ("X-Tenant", "acme")]
+    )]
+    async fn add_array(a: i32, b: i32) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(
+        url = "http://127.0.0.1:3901/",
+        method = "addObj",
+        mode = "obj",
+        headers = [("X-Api-Key", "secret"), ("X-Tenant", "acme")]
+    )]
+    async fn add_obj(lhs: i32, rhs: i32) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    assert_eq!(add_array(10, 20).await.unwrap().result, Some(30));
+    assert_eq!(add_obj(10, 20).await.unwrap().result, Some(30));
+}
+
+#[tokio::test]
+async fn test_rpc_method_options_keep_params() {
+    let server = start_mock_server("127.0.0.1:3906").await;
+    let sum_params = vec![1, 2, 3]
+        .dry_run("http://127.0.0.1:3906/", "application/json", "sum")
+        .unwrap()
+        .params;
+    let none_params = None::<i32>
+        .dry_run("http://127.0.0.1:3906/", "application/json", "maybe")
+        .unwrap()
+        .params;
+    for (method, params) in [("sum", sum_params), ("maybe", none_params)] {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(
+                serde_json::json!({ "method": method, "params": params }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "result": 6,
+                "id": 1
+            })))
+            .expect(4)
+            .mount(&server)
+            .await;
+    }
+
+    #[rpc_method(url = "http://127.0.0.1:3906/", method = "sum")]
+    async fn sum(values: Vec<i32>) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(
+        url = "http://127.0.0.1:3906/",
+        method = "sum",
+        headers = [("X-Api-Key", "secret")]
+    )]
+    async fn sum_with_headers(values: Vec<i32>) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(url = "http://127.0.0.1:3906/", method = "sum", log_level = "info")]
+    async fn sum_logged(values: Vec<i32>) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(url = "http://127.0.0.1:3906/", method = "sum", id = 7)]
+    async fn sum_with_id(values: Vec<i32>) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(url = "http://127.0.0.1:3906/", method = "maybe")]
+    async fn maybe(value: Option<i32>) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(
+        url = "http://127.0.0.1:3906/",
+        method = "maybe",
+        headers = [("X-Api-Key", "secret")]
+    )]
+    async fn maybe_with_headers(value: Option<i32>) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(url = "http://127.0.0.1:3906/", method = "maybe", log_level = "info")]
+    async fn maybe_logged(value: Option<i32>) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(url = "http://127.0.0.1:3906/", method = "maybe", id = "fixed")]
+    async fn maybe_with_id(value: Option<i32>) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    // The mocks only answer requests carrying the parameters of a dry run.
+    assert_eq!(sum(vec![1, 2, 3]).await.unwrap().result, Some(6));
+    assert_eq!(
+        sum_with_headers(vec![1, 2, 3]).await.unwrap().result,
+        Some(6)
+    );
+    assert_eq!(sum_logged(vec![1, 2, 3]).await.unwrap().result, Some(6));
+    assert_eq!(sum_with_id(vec![1, 2, 3]).await.unwrap().result, Some(6));
+    assert_eq!(maybe(None).await.unwrap().result, Some(6));
+    assert_eq!(maybe_with_headers(None).await.unwrap().result, Some(6));
+    assert_eq!(maybe_logged(None).await.unwrap().result, Some(6));
+    assert_eq!(maybe_with_id(None).await.unwrap().result, Some(6));
+}
+
+fn configured_method() -> String {
+    "addFromConfig".to_string()
+}
+
+#[tokio::test]
+async fn test_rpc_method_with_runtime_method_name() {
+    let server = start_mock_server("127.0.0.1:3902").await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_partial_json(
+            serde_json::json!({ "method": "addFromConfig" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": 30,
+            "id": 1
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    #[rpc_method(url = "http://127.0.0.1:3902/", method_fn = "configured_method")]
+    async fn add(a: i32, b: i32) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    assert_eq!(add(10, 20).await.unwrap().result, Some(30));
+}
+
+fn tag_error(err: JsonRpcError) -> RpcError {
+    RpcError::InvalidParams(format!("remote: {}", err.message).into())
+}
+
+#[tokio::test]
+async fn test_rpc_method_unwrap_result_and_map_error() {
+    let server = start_mock_server("127.0.0.1:3903").await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_partial_json(
+            serde_json::json!({ "method": "ok" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": 30,
+            "id": 1
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_partial_json(
+            serde_json::json!({ "method": "fail" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "error": { "code": -32601, "message": "boom" },
+            "id": 1
+        })))
+        .mount(&server)
+        .await;
+
+    #[rpc_method(url = "http://127.0.0.1:3903/", method = "ok", unwrap_result = true)]
+    async fn ok(a: i32, b: i32) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(url = "http://127.0.0.1:3903/", method = "fail", unwrap_result = true)]
+    async fn fail(a: i32, b: i32) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    #[rpc_method(
+        url = "http://127.0.0.1:3903/",
+        method = "fail",
+        map_error = "tag_error"
+    )]
+    async fn fail_mapped(a: i32, b: i32) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    let value: i32 = ok(10, 20).await.unwrap();
+    assert_eq!(value, 30);
+    assert!(matches!(fail(10, 20).await, Err(RpcError::MethodNotFound)));
+    assert!(
+        matches!(fail_mapped(10, 20).await, Err(RpcError::InvalidParams(msg)) if msg == "remote: boom")
+    );
+}
+
+#[tokio::test]
+async fn test_rpc_method_with_fixed_id() {
+    let server = start_mock_server("127.0.0.1:3904").await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_partial_json(
+            serde_json::json!({ "method": "health", "id": 42 }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": "ok",
+            "id": 42
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_partial_json(
+            serde_json::json!({ "method": "addObj", "id": "my-fixed-id" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": 30,
+            "id": "my-fixed-id"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    #[rpc_method(url = "http://127.0.0.1:3904/", method = "health", id = 42)]
+    async fn health(verbose: bool) -> Result<JsonRpcResponse<String>, RpcError> {}
+
+    #[rpc_method(
+        url = "http://127.0.0.1:3904/",
+        method = "addObj",
+        mode = "obj",
+        id = "my-fixed-id"
+    )]
+    async fn add_obj(lhs: i32, rhs: i32) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    for _ in 0..2 {
+        assert_eq!(
+            health(true).await.unwrap().id,
+            a_rs_jsonrpc::JsonRpcId::Number(42)
+        );
+    }
+    assert_eq!(add_obj(10, 20).await.unwrap().result, Some(30));
+}
+
+#[tokio::test]
+async fn test_derive_enum_variant_methods() {
+    let server = start_mock_server("127.0.0.1:3905").await;
+    // A skipped variant sends no `params` member at all.
+    for (request, result) in [
+        (
+            serde_json::json!({ "method": "getBlock", "params": [7] }),
+            "by number",
+        ),
+        (
+            serde_json::json!({ "method": "getBlockByHash", "params": ["0xabc"] }),
+            "by hash",
+        ),
+        (serde_json::json!({ "method": "getLatestBlock" }), "latest"),
+    ] {
+        Mock::given(matchers::method("POST"))
+            .and(matchers::body_partial_json(request))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "result": result,
+                "id": 1
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    #[derive(Clone, serde::Serialize, JsonRpcClient)]
+    #[jsonrpc(
+        url = "http://127.0.0.1:3905/",
+        content_type = "application/json",
+        method = "getBlock"
+    )]
+    enum BlockQuery {
+        ByNumber(u64),
+        #[jsonrpc(method = "getBlockByHash")]
+        ByHash(String),
+        #[jsonrpc(skip, method = "getLatestBlock")]
+        Latest,
+    }
+
+    use a_rs_jsonrpc::JsonRpcClientCall;
+    for (query, expected) in [
+        (BlockQuery::ByNumber(7), "by number"),
+        (BlockQuery::ByHash("0xabc".to_string()), "by hash"),
+        (BlockQuery::Latest, "latest"),
+    ] {
+        let resp: JsonRpcResponse<String> = query.call_rpc_v2().await.unwrap();
+        assert_eq!(resp.result.as_deref(), Some(expected));
+    }
+}
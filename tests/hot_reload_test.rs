@@ -0,0 +1,74 @@
+use a_rs_jsonrpc::{
+    ParamMode, RPC_SERVICES, RpcError, RpcServiceEntry, describe_rpc_method, dispatch_rpc_request,
+    hot_reload_rpc_methods, jsonrpc_service_fn_array, list_rpc_methods, service::RpcUserData,
+};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+
+#[jsonrpc_service_fn_array(method = "version", version = "v2")]
+async fn version() -> Result<String, RpcError> {
+    Ok("v1".to_string())
+}
+
+/// The replacement handler of `version`.
+fn version_v2(req: Bytes, _user_data: RpcUserData) -> BoxFuture<'static, Result<String, RpcError>> {
+    let id = serde_json::from_slice::<serde_json::Value>(&req).unwrap()["id"].clone();
+    Box::pin(async move {
+        Ok(serde_json::json!({ "jsonrpc": "2.0", "result": "v2", "id": id }).to_string())
+    })
+}
+
+static VERSION_V2: RpcServiceEntry = RpcServiceEntry {
+    method: "version",
+    description: Some("The reloaded version handler."),
+    param_mode: ParamMode::Array,
+    schema: None,
+    handler: version_v2,
+    stream_handler: None,
+};
+
+static ADDED: RpcServiceEntry = RpcServiceEntry {
+    method: "addedOnReload",
+    description: None,
+    param_mode: ParamMode::Array,
+    schema: None,
+    handler: version_v2,
+    stream_handler: None,
+};
+
+async fn call(method: &str) -> Result<serde_json::Value, RpcError> {
+    let body = format!(
+        r#"{{"jsonrpc":"2.0","method":"{}","params":[],"id":1}}"#,
+        method
+    );
+    let text = dispatch_rpc_request(body.as_bytes()).await?;
+    Ok(serde_json::from_str::<serde_json::Value>(&text).unwrap()["result"].clone())
+}
+
+#[tokio::test]
+async fn test_hot_reload_methods_changes_routing() {
+    assert_eq!(call("version").await.unwrap(), "v1");
+    assert!(matches!(
+        call("addedOnReload").await,
+        Err(RpcError::MethodNotFound)
+    ));
+
+    hot_reload_rpc_methods(RPC_SERVICES.iter().chain([&VERSION_V2, &ADDED]));
+    assert_eq!(call("version").await.unwrap(), "v2");
+    assert_eq!(call("addedOnReload").await.unwrap(), "v2");
+    assert!(list_rpc_methods().contains(&"addedOnReload"));
+    assert_eq!(
+        describe_rpc_method("version").unwrap().description,
+        Some("The reloaded version handler.")
+    );
+
+    hot_reload_rpc_methods([&VERSION_V2]);
+    assert_eq!(list_rpc_methods(), vec!["version"]);
+
+    hot_reload_rpc_methods(RPC_SERVICES.iter());
+    assert_eq!(call("version").await.unwrap(), "v1");
+    assert!(matches!(
+        call("addedOnReload").await,
+        Err(RpcError::MethodNotFound)
+    ));
+}
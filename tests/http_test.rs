@@ -0,0 +1,54 @@
+#![cfg(feature = "http")]
+
+use a_rs_jsonrpc::{RpcError, dispatch_rpc_http, jsonrpc_service_fn_array};
+use bytes::Bytes;
+
+#[jsonrpc_service_fn_array(method = "httpAdd", version = "v2")]
+async fn http_add(a: i32, b: i32) -> Result<i32, RpcError> {
+    Ok(a + b)
+}
+
+fn post(content_type: &str, body: &'static str) -> http::Request<Bytes> {
+    http::Request::post("/")
+        .header(http::header::CONTENT_TYPE, content_type)
+        .body(Bytes::from_static(body.as_bytes()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_dispatch_http() {
+    let resp = dispatch_rpc_http(post(
+        "application/json; charset=utf-8",
+        r#"{"jsonrpc":"2.0","method":"httpAdd","params":[1,2],"id":1}"#,
+    ))
+    .await;
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "application/json");
+    let body: serde_json::Value = serde_json::from_str(resp.body()).unwrap();
+    assert_eq!(body["result"], 3);
+
+    let resp = dispatch_rpc_http(post(
+        "application/json",
+        r#"{"jsonrpc":"2.0","method":"httpMissing","params":[],"id":2}"#,
+    ))
+    .await;
+    assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    let body: serde_json::Value = serde_json::from_str(resp.body()).unwrap();
+    assert_eq!(body["id"], 2);
+
+    let resp = dispatch_rpc_http(post("application/json", "{not json")).await;
+    assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_dispatch_http_rejects_non_json_content_type() {
+    let resp = dispatch_rpc_http(post(
+        "text/plain",
+        r#"{"jsonrpc":"2.0","method":"httpAdd","params":[1,2],"id":3}"#,
+    ))
+    .await;
+    assert_eq!(resp.status(), http::StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    let body: serde_json::Value = serde_json::from_str(resp.body()).unwrap();
+    assert_eq!(body["id"], 3);
+    assert!(body["error"].is_object());
+}
@@ -0,0 +1,50 @@
+use a_rs_jsonrpc::{
+    Metrics, RpcError, dispatch_rpc_request_with_metrics, jsonrpc_service_fn_array,
+};
+use std::sync::atomic::Ordering;
+
+#[jsonrpc_service_fn_array(method = "metricsDiv", version = "v2")]
+async fn metrics_div(a: i32, b: i32) -> Result<i32, RpcError> {
+    if b == 0 {
+        return Err(RpcError::invalid_params_static("division by zero"));
+    }
+    Ok(a / b)
+}
+
+#[tokio::test]
+async fn test_dispatch_with_metrics() {
+    let metrics = Metrics::new();
+    let ok = br#"{"jsonrpc":"2.0","method":"metricsDiv","params":[6,3],"id":1}"#;
+    let err = br#"{"jsonrpc":"2.0","method":"metricsDiv","params":[6,0],"id":2}"#;
+    dispatch_rpc_request_with_metrics(ok, &metrics)
+        .await
+        .unwrap();
+    dispatch_rpc_request_with_metrics(ok, &metrics)
+        .await
+        .unwrap();
+    dispatch_rpc_request_with_metrics(err, &metrics)
+        .await
+        .unwrap_err();
+
+    let snapshot = metrics.snapshot();
+    let div = &snapshot["metricsDiv"];
+    assert_eq!(div.calls.load(Ordering::Relaxed), 3);
+    assert_eq!(div.errors.load(Ordering::Relaxed), 1);
+
+    metrics.reset();
+    assert!(metrics.snapshot().is_empty());
+}
+
+#[cfg(feature = "prometheus")]
+#[tokio::test]
+async fn test_metrics_to_prometheus_text() {
+    let metrics = Metrics::new();
+    let ok = br#"{"jsonrpc":"2.0","method":"metricsDiv","params":[6,3],"id":1}"#;
+    dispatch_rpc_request_with_metrics(ok, &metrics)
+        .await
+        .unwrap();
+    let text = metrics.to_prometheus_text();
+    assert!(text.contains("# TYPE jsonrpc_calls_total counter"));
+    assert!(text.contains("jsonrpc_calls_total{method=\"metricsDiv\"} 1"));
+    assert!(text.contains("jsonrpc_errors_total{method=\"metricsDiv\"} 0"));
+}
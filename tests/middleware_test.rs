@@ -0,0 +1,137 @@
+use a_rs_jsonrpc::{
+    AuthMiddleware, LoggingMiddleware, Middleware, RateLimiter, RpcError, ValidationMiddleware,
+    dispatch_rpc_request_with_middleware, dispatch_rpc_request_with_middleware_and_headers,
+    dispatch_rpc_request_with_rate_limit, jsonrpc_service_fn_array,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static LIMITED_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[jsonrpc_service_fn_array(method = "limitedPing", version = "v2")]
+async fn limited_ping() -> Result<String, RpcError> {
+    LIMITED_CALLS.fetch_add(1, Ordering::SeqCst);
+    Ok("pong".to_string())
+}
+
+#[jsonrpc_service_fn_array(method = "perMethodPing", version = "v2")]
+async fn per_method_ping() -> Result<String, RpcError> {
+    Ok("pong".to_string())
+}
+
+#[jsonrpc_service_fn_array(method = "unlimitedPing", version = "v2")]
+async fn unlimited_ping() -> Result<String, RpcError> {
+    Ok("pong".to_string())
+}
+
+async fn call(method: &str, limiter: &RateLimiter) -> serde_json::Value {
+    let body = format!(
+        r#"{{"jsonrpc":"2.0","method":"{}","params":[],"id":7}}"#,
+        method
+    );
+    let text = dispatch_rpc_request_with_rate_limit(body.as_bytes(), limiter)
+        .await
+        .unwrap();
+    serde_json::from_str(&text).unwrap()
+}
+
+#[tokio::test]
+async fn test_rate_limiter_rejects_when_exhausted() {
+    let limiter = RateLimiter::new(2, 0);
+    let before = LIMITED_CALLS.load(Ordering::SeqCst);
+    assert_eq!(call("limitedPing", &limiter).await["result"], "pong");
+    assert_eq!(call("limitedPing", &limiter).await["result"], "pong");
+
+    let resp = call("limitedPing", &limiter).await;
+    assert_eq!(resp["error"]["code"], -32029);
+    assert_eq!(resp["error"]["message"], "rate limit exceeded");
+    assert_eq!(resp["id"], 7);
+    assert_eq!(LIMITED_CALLS.load(Ordering::SeqCst) - before, 2);
+}
+
+#[tokio::test]
+async fn test_rate_limiter_per_method() {
+    let limiter = RateLimiter::per_method(HashMap::from([("perMethodPing", (1, 0))]));
+    assert_eq!(call("perMethodPing", &limiter).await["result"], "pong");
+    assert_eq!(
+        call("perMethodPing", &limiter).await["error"]["code"],
+        -32029
+    );
+    for _ in 0..3 {
+        assert_eq!(call("unlimitedPing", &limiter).await["result"], "pong");
+    }
+}
+
+/// Tags the response so the order of `after` hooks is visible.
+struct Tag(&'static str);
+
+#[async_trait::async_trait]
+impl Middleware for Tag {
+    async fn before(&self, _req: &[u8]) -> Result<(), RpcError> {
+        Ok(())
+    }
+
+    async fn after(&self, _req: &[u8], resp: &str) -> Result<String, RpcError> {
+        let mut resp: serde_json::Value = serde_json::from_str(resp)?;
+        let trail = resp["trail"].as_str().unwrap_or_default().to_string();
+        resp["trail"] = serde_json::Value::String(trail + self.0);
+        Ok(resp.to_string())
+    }
+}
+
+#[tokio::test]
+async fn test_middleware_after_runs_in_reverse_order() {
+    let middlewares: Vec<Arc<dyn Middleware>> = vec![
+        Arc::new(LoggingMiddleware::new()),
+        Arc::new(Tag("a")),
+        Arc::new(Tag("b")),
+    ];
+    let body = br#"{"jsonrpc":"2.0","method":"unlimitedPing","params":[],"id":8}"#;
+    let text = dispatch_rpc_request_with_middleware(body, &middlewares)
+        .await
+        .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(resp["result"], "pong");
+    assert_eq!(resp["trail"], "ba");
+}
+
+#[tokio::test]
+async fn test_validation_middleware_rejects_bad_version() {
+    let middlewares: Vec<Arc<dyn Middleware>> = vec![Arc::new(ValidationMiddleware)];
+    let body = br#"{"jsonrpc":"3.0","method":"unlimitedPing","params":[],"id":9}"#;
+    let text = dispatch_rpc_request_with_middleware(body, &middlewares)
+        .await
+        .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert!(resp["error"].is_object());
+    assert!(resp["result"].is_null());
+}
+
+#[tokio::test]
+async fn test_auth_middleware_checks_authorization_header() {
+    let middlewares: Vec<Arc<dyn Middleware>> =
+        vec![Arc::new(AuthMiddleware("secret".to_string()))];
+    let body = br#"{"jsonrpc":"2.0","method":"unlimitedPing","params":[],"id":10}"#;
+
+    let headers = HashMap::from([("authorization".to_string(), "Bearer secret".to_string())]);
+    let text = dispatch_rpc_request_with_middleware_and_headers(body, &headers, &middlewares)
+        .await
+        .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(resp["result"], "pong");
+
+    let headers = HashMap::from([("Authorization".to_string(), "wrong".to_string())]);
+    let text = dispatch_rpc_request_with_middleware_and_headers(body, &headers, &middlewares)
+        .await
+        .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert!(resp["error"].is_object());
+    assert_eq!(resp["id"], 10);
+
+    let text = dispatch_rpc_request_with_middleware(body, &middlewares)
+        .await
+        .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert!(resp["error"].is_object());
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "msgpack")]
+
+use a_rs_jsonrpc::{
+    JsonRpcClient, JsonRpcId, JsonRpcParams, JsonRpcRequest, JsonRpcResponse, RpcError,
+    request::JsonRpcVersion, response::JsonRpcError, rpc_method,
+};
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+#[test]
+fn test_request_and_response_round_trip_as_msgpack() {
+    let mut req: JsonRpcRequest<(i32, String)> =
+        JsonRpcRequest::new_v2(JsonRpcId::from("id-1"), "echo");
+    req.set_params((10, "hello".to_string()));
+    let bytes = rmp_serde::to_vec_named(&req).unwrap();
+    assert_eq!(
+        rmp_serde::from_slice::<JsonRpcRequest<(i32, String)>>(&bytes).unwrap(),
+        req
+    );
+
+    let req = JsonRpcRequest::<Vec<i32>>::new_v1(JsonRpcId::Null, "ping").null_params();
+    let bytes = rmp_serde::to_vec_named(&req).unwrap();
+    let parsed: JsonRpcRequest<Vec<i32>> = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(parsed.params, JsonRpcParams::Null);
+    assert_eq!(parsed.id, JsonRpcId::Null);
+
+    let resp: JsonRpcResponse<i32> = JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2_0,
+        result: None,
+        error: Some(JsonRpcError {
+            code: -32601,
+            message: "method not found".to_string(),
+            data: Some(serde_json::json!({ "method": "missing" })),
+        }),
+        id: JsonRpcId::Number(7),
+    };
+    let bytes = rmp_serde::to_vec_named(&resp).unwrap();
+    assert_eq!(
+        rmp_serde::from_slice::<JsonRpcResponse<i32>>(&bytes).unwrap(),
+        resp
+    );
+}
+
+#[tokio::test]
+async fn test_send_v2_request_msgpack() {
+    let server = MockServer::start().await;
+    let body = rmp_serde::to_vec_named(&JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2_0,
+        result: Some(30),
+        error: None,
+        id: JsonRpcId::Number(1),
+    })
+    .unwrap();
+    Mock::given(matchers::method("POST"))
+        .and(matchers::header("Content-Type", "application/msgpack"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/msgpack"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let resp: JsonRpcResponse<i32> = (10, 20)
+        .send_v2_request_msgpack(&server.uri(), "addArray")
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(30));
+}
+
+#[tokio::test]
+async fn test_rpc_method_msgpack_keeps_params() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:3907").unwrap();
+    let server = MockServer::builder().listener(listener).start().await;
+    let body = rmp_serde::to_vec_named(&JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2_0,
+        result: Some(6),
+        error: None,
+        id: JsonRpcId::Number(1),
+    })
+    .unwrap();
+    Mock::given(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/msgpack"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    #[rpc_method(url = "http://127.0.0.1:3907/", method = "sum", encoding = "msgpack")]
+    async fn sum(values: Vec<i32>) -> Result<JsonRpcResponse<i32>, RpcError> {}
+
+    assert_eq!(sum(vec![1, 2, 3]).await.unwrap().result, Some(6));
+    let requests = server.received_requests().await.unwrap();
+    let sent: JsonRpcRequest<serde_json::Value> = rmp_serde::from_slice(&requests[0].body).unwrap();
+    let expected = vec![1, 2, 3]
+        .dry_run("http://127.0.0.1:3907/", "application/msgpack", "sum")
+        .unwrap();
+    assert_eq!(sent.params, expected.params);
+}
@@ -0,0 +1,92 @@
+use a_rs_jsonrpc::{
+    RpcError, ServiceConfig, dispatch_rpc_request, dispatch_rpc_request_from_method,
+    dispatch_rpc_request_streaming, init_rpc_service, jsonrpc_service_fn_array,
+    response::JsonRpcError, try_dispatch_rpc_request,
+};
+use futures::{Stream, StreamExt};
+
+#[jsonrpc_service_fn_array(method = "explode", version = "v2")]
+async fn explode(fuse: Option<i32>) -> Result<i32, RpcError> {
+    Ok(fuse.expect("fuse is missing"))
+}
+
+#[jsonrpc_service_fn_array(method = "explodeStream", version = "v2", streaming = true)]
+async fn explode_stream(fuse: Option<u32>) -> Result<impl Stream<Item = u32>, RpcError> {
+    let fuse = fuse.expect("fuse is missing");
+    Ok(futures::stream::iter(0..3).map(move |i| {
+        assert!(i < fuse, "fuse burnt at {}", i);
+        i
+    }))
+}
+
+fn catch_panics() {
+    init_rpc_service(ServiceConfig::default().catch_panics(true));
+}
+
+fn assert_internal_error(err: RpcError, message: &str) {
+    assert!(matches!(&err, RpcError::InternalError(msg) if msg == message));
+    assert_eq!(JsonRpcError::from(err).code, -32603);
+}
+
+#[tokio::test]
+async fn test_catch_panics_turns_panic_into_internal_error() {
+    catch_panics();
+
+    let body = br#"{"jsonrpc":"2.0","method":"explode","params":[1],"id":1}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], 1);
+
+    let body = br#"{"jsonrpc":"2.0","method":"explode","params":[null],"id":2}"#;
+    let err = dispatch_rpc_request(body).await.unwrap_err();
+    assert_internal_error(err, "fuse is missing");
+}
+
+#[tokio::test]
+async fn test_catch_panics_in_try_dispatch() {
+    catch_panics();
+
+    let body = br#"{"jsonrpc":"2.0","method":"explode","params":[null],"id":3}"#;
+    let err = try_dispatch_rpc_request(body).unwrap().await.unwrap_err();
+    assert_internal_error(err, "fuse is missing");
+}
+
+#[tokio::test]
+async fn test_catch_panics_in_dispatch_from_method() {
+    catch_panics();
+
+    let body = br#"{"jsonrpc":"2.0","params":[null],"id":4}"#;
+    let err = dispatch_rpc_request_from_method("explode", body)
+        .await
+        .unwrap_err();
+    assert_internal_error(err, "fuse is missing");
+}
+
+#[tokio::test]
+async fn test_catch_panics_in_dispatch_streaming() {
+    catch_panics();
+
+    let body = br#"{"jsonrpc":"2.0","method":"explodeStream","params":[null],"id":5}"#;
+    let err = match dispatch_rpc_request_streaming(body).await {
+        Ok(_) => panic!("expected the handler panic to be caught"),
+        Err(err) => err,
+    };
+    assert_internal_error(err, "fuse is missing");
+
+    // A panic while producing an item ends the stream with an error response.
+    let body = br#"{"jsonrpc":"2.0","method":"explodeStream","params":[1],"id":6}"#;
+    let lines: Vec<serde_json::Value> = dispatch_rpc_request_streaming(body)
+        .await
+        .unwrap()
+        .map(|line| serde_json::from_str(&line).unwrap())
+        .collect()
+        .await;
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["result"], 0);
+    assert_eq!(lines[1]["error"]["code"], -32603);
+    assert_eq!(
+        lines[1]["error"]["message"],
+        "Internal error: fuse burnt at 1"
+    );
+    assert_eq!(lines[1]["id"], 6);
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "ws")]
+
+use a_rs_jsonrpc::{JsonRpcId, PendingRequests};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_pending_requests_resolve_by_id() {
+    let mut pending = PendingRequests::new();
+    let first = pending.insert(JsonRpcId::Number(1));
+    let second = pending.insert(JsonRpcId::Number(2));
+
+    assert!(pending.resolve(&JsonRpcId::Number(2), "two".to_string()));
+    assert!(!pending.resolve(&JsonRpcId::Number(3), "three".to_string()));
+    assert_eq!(second.await.unwrap(), "two");
+    assert_eq!(pending.len(), 1);
+
+    assert_eq!(pending.cleanup_timed_out(Duration::ZERO), 1);
+    assert!(pending.is_empty());
+    assert!(first.await.is_err());
+}
@@ -0,0 +1,29 @@
+use a_rs_jsonrpc::{
+    RpcContext, RpcError, dispatch_rpc_request, jsonrpc_service_fn_array, list_rpc_methods,
+};
+
+#[jsonrpc_service_fn_array(method = "known", version = "v2")]
+async fn known() -> Result<String, RpcError> {
+    Ok("known".to_string())
+}
+
+#[jsonrpc_service_fn_array(fallback = true, version = "v2", has_context = true)]
+async fn unknown(ctx: &RpcContext) -> Result<String, RpcError> {
+    Ok(format!("fallback for {}", ctx.method))
+}
+
+#[tokio::test]
+async fn test_registered_fallback_catches_unknown_methods() {
+    let body = br#"{"jsonrpc":"2.0","method":"known","params":[],"id":1}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], "known");
+
+    let body = br#"{"jsonrpc":"2.0","method":"missing","params":[],"id":2}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], "fallback for missing");
+    assert_eq!(resp["id"], 2);
+
+    assert_eq!(list_rpc_methods(), ["known"]);
+}
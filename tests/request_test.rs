@@ -0,0 +1,249 @@
+use a_rs_jsonrpc::{
+    JsonRpcId, JsonRpcParams, JsonRpcRequest, JsonRpcRequestBuilder, JsonRpcResponse, RpcError,
+    request::JsonRpcVersion,
+};
+
+#[test]
+fn test_request_round_trip_with_array_params() {
+    let mut req: JsonRpcRequest<Vec<serde_json::Value>> =
+        JsonRpcRequest::new_v2(JsonRpcId::Number(1), "addArray");
+    req.add_param(10);
+    req.add_param(20);
+    let text = serde_json::to_string(&req).unwrap();
+    let parsed: JsonRpcRequest<Vec<serde_json::Value>> = serde_json::from_str(&text).unwrap();
+    assert_eq!(parsed.jsonrpc, JsonRpcVersion::V2_0);
+    assert_eq!(parsed.method, "addArray");
+    assert_eq!(
+        parsed.params,
+        JsonRpcParams::Present(vec![serde_json::json!(10), serde_json::json!(20)])
+    );
+    assert_eq!(parsed.id, JsonRpcId::Number(1));
+}
+
+#[test]
+fn test_request_round_trip_with_tuple_params() {
+    let mut req: JsonRpcRequest<(i32, String)> =
+        JsonRpcRequest::new_v1(JsonRpcId::from("id-1"), "echo");
+    req.set_params((10, "hello".to_string()));
+    let text = serde_json::to_string(&req).unwrap();
+    let parsed: JsonRpcRequest<(i32, String)> = serde_json::from_str(&text).unwrap();
+    assert_eq!(parsed.jsonrpc, JsonRpcVersion::V1_0);
+    assert_eq!(
+        parsed.params,
+        JsonRpcParams::Present((10, "hello".to_string()))
+    );
+    assert_eq!(parsed.id, JsonRpcId::from("id-1"));
+}
+
+#[test]
+fn test_request_deserialize_without_params() {
+    let text = r#"{"jsonrpc":"2.0","method":"ping","id":7}"#;
+    let parsed: JsonRpcRequest<Vec<serde_json::Value>> = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed.method, "ping");
+    assert_eq!(parsed.params, JsonRpcParams::Absent);
+    assert_eq!(parsed.id, JsonRpcId::Number(7));
+}
+
+#[test]
+fn test_request_null_params_kept_apart_from_absent() {
+    let text = r#"{"jsonrpc":"2.0","method":"ping","params":null,"id":8}"#;
+    let parsed: JsonRpcRequest<Vec<serde_json::Value>> = serde_json::from_str(text).unwrap();
+    assert_eq!(parsed.params, JsonRpcParams::Null);
+    assert_eq!(serde_json::to_string(&parsed).unwrap(), text);
+
+    let req = JsonRpcRequest::<Vec<serde_json::Value>>::new_v2(JsonRpcId::Number(8), "ping")
+        .with_params(vec![])
+        .no_params();
+    assert_eq!(
+        serde_json::to_string(&req).unwrap(),
+        r#"{"jsonrpc":"2.0","method":"ping","id":8}"#
+    );
+    let req = req.null_params();
+    assert_eq!(serde_json::to_string(&req).unwrap(), text);
+}
+
+#[test]
+fn test_request_builder_with_array_params() {
+    let req = JsonRpcRequestBuilder::v2("addArray")
+        .id(JsonRpcId::Number(3))
+        .add_param(serde_json::json!(10))
+        .add_param(serde_json::json!(20))
+        .build()
+        .unwrap();
+    assert_eq!(req.jsonrpc, JsonRpcVersion::V2_0);
+    assert_eq!(req.method, "addArray");
+    assert_eq!(
+        req.params,
+        JsonRpcParams::Present(vec![serde_json::json!(10), serde_json::json!(20)])
+    );
+    assert_eq!(req.id, JsonRpcId::Number(3));
+}
+
+#[test]
+fn test_request_builder_rejects_empty_method() {
+    let result = JsonRpcRequestBuilder::<(i32, i32)>::v1("")
+        .params((1, 2))
+        .build();
+    assert!(matches!(result, Err(RpcError::InvalidRequest(_))));
+}
+
+#[test]
+fn test_id_eq_value_across_variants() {
+    assert_ne!(JsonRpcId::Number(42), JsonRpcId::from("42"));
+    assert!(JsonRpcId::Number(42).eq_value(&JsonRpcId::from("42")));
+    assert!(JsonRpcId::from("42").eq_value(&JsonRpcId::Number(42)));
+    assert!(!JsonRpcId::Number(42).eq_value(&JsonRpcId::from("id-42")));
+    assert!(JsonRpcId::from("a").eq_value(&JsonRpcId::from("a")));
+}
+
+#[test]
+fn test_notification_and_response_correlation() {
+    let req: JsonRpcRequest<serde_json::Value> =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","method":"ping","id":null}"#).unwrap();
+    assert!(req.is_notification());
+    assert_eq!(
+        serde_json::to_value(&req.id).unwrap(),
+        serde_json::Value::Null
+    );
+
+    let req: JsonRpcRequest<serde_json::Value> =
+        JsonRpcRequest::new_v2(JsonRpcId::Number(7), "ping");
+    assert!(!req.is_notification());
+    let resp: JsonRpcResponse<String> =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","result":"pong","id":7}"#).unwrap();
+    assert!(resp.correlates_to(&req));
+    let resp: JsonRpcResponse<String> =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","result":"pong","id":"7"}"#).unwrap();
+    assert!(!resp.correlates_to(&req));
+}
+
+#[test]
+fn test_request_into_reqwest_body() {
+    let mut req: JsonRpcRequest<serde_json::Value> =
+        JsonRpcRequest::new_v2(JsonRpcId::Number(8), "addArray");
+    req.set_params(serde_json::json!([10, 20]));
+    let expected = serde_json::to_vec(&req).unwrap();
+    let body = req.into_reqwest_body();
+    assert_eq!(body.as_bytes(), Some(expected.as_slice()));
+}
+
+#[test]
+fn test_request_raw_params_pass_through() {
+    let text = r#"{"jsonrpc":"2.0","method":"add","params":[10,  20],"id":9}"#;
+    let parsed: JsonRpcRequest<Box<serde_json::value::RawValue>> =
+        serde_json::from_str(text).unwrap();
+    assert_eq!(parsed.params.as_option().unwrap().get(), "[10,  20]");
+    assert_eq!(serde_json::to_string(&parsed).unwrap(), text);
+
+    let req = JsonRpcRequest::new_v2_raw(JsonRpcId::Number(9), "add", r#"{"lhs":1}"#).unwrap();
+    assert_eq!(
+        serde_json::to_string(&req).unwrap(),
+        r#"{"jsonrpc":"2.0","method":"add","params":{"lhs":1},"id":9}"#
+    );
+    assert!(JsonRpcRequest::new_v2_raw(JsonRpcId::Number(9), "add", "[1,").is_err());
+}
+
+#[test]
+fn test_request_with_ext_adds_top_level_fields() {
+    let req = JsonRpcRequest::<Vec<i32>>::new_v2(JsonRpcId::Number(10), "ping")
+        .with_ext("x-trace-id", "abc")
+        .with_ext("x-attempt", 2)
+        .with_ext("id", 99);
+    assert_eq!(
+        serde_json::to_value(&req).unwrap(),
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "ping",
+            "id": 10,
+            "x-trace-id": "abc",
+            "x-attempt": 2
+        })
+    );
+}
+
+#[test]
+fn test_version_as_str_display_and_try_from() {
+    assert_eq!(JsonRpcVersion::V1_0.as_str(), "1.0");
+    assert_eq!(JsonRpcVersion::V2_0.to_string(), "2.0");
+    assert_eq!(
+        JsonRpcVersion::try_from("2.0").unwrap(),
+        JsonRpcVersion::V2_0
+    );
+    assert!(matches!(
+        JsonRpcVersion::try_from("3.0"),
+        Err(RpcError::InvalidJsonRpcVersion(_))
+    ));
+    let versions: std::collections::HashSet<_> = [
+        JsonRpcVersion::V1_0,
+        JsonRpcVersion::V2_0,
+        JsonRpcVersion::V2_0,
+    ]
+    .into();
+    assert_eq!(versions.len(), 2);
+}
+
+#[test]
+fn test_id_ordering_display_and_accessors() {
+    let mut ids = vec![
+        JsonRpcId::Null,
+        JsonRpcId::from("b"),
+        JsonRpcId::from(10),
+        JsonRpcId::from("a"),
+        JsonRpcId::from(2u32),
+    ];
+    ids.sort();
+    assert_eq!(
+        ids,
+        [
+            JsonRpcId::Number(2),
+            JsonRpcId::Number(10),
+            JsonRpcId::from("a"),
+            JsonRpcId::from("b"),
+            JsonRpcId::Null,
+        ]
+    );
+
+    assert_eq!(JsonRpcId::from(7i64).to_string(), "7");
+    assert_eq!(JsonRpcId::from("id-7").to_string(), "id-7");
+    assert_eq!(JsonRpcId::from(-1).as_str(), Some("-1"));
+    assert_eq!(JsonRpcId::from(7).as_number(), Some(7));
+    assert_eq!(JsonRpcId::from("7").as_number(), None);
+}
+
+#[test]
+fn test_request_add_named_param() {
+    let mut req = JsonRpcRequest::new_v2_obj(JsonRpcId::from(1), "addObj");
+    assert_eq!(
+        serde_json::to_value(&req).unwrap()["params"],
+        serde_json::json!({})
+    );
+    req.add_named_param("lhs", 10);
+    req.add_named_param("rhs", 20);
+    assert_eq!(
+        serde_json::to_value(&req).unwrap(),
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "addObj",
+            "params": { "lhs": 10, "rhs": 20 },
+            "id": 1
+        })
+    );
+}
+
+#[test]
+fn test_request_into_deserializer() {
+    use serde::Deserialize;
+    use serde::de::IntoDeserializer;
+
+    #[derive(Deserialize)]
+    struct Proxied {
+        method: String,
+        params: (i32, i32),
+    }
+
+    let mut req = JsonRpcRequest::new_v2(JsonRpcId::from(1), "add");
+    req.set_params(vec![10, 20]);
+    let proxied = Proxied::deserialize(req.into_deserializer()).unwrap();
+    assert_eq!(proxied.method, "add");
+    assert_eq!(proxied.params, (10, 20));
+}
@@ -0,0 +1,276 @@
+use a_rs_jsonrpc::{
+    JsonRpcClient, JsonRpcClientExt, JsonRpcClientPool, JsonRpcClientSession, JsonRpcResponse,
+    LoadBalancing, RpcError, SharedClient,
+};
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+async fn mock_add_server(params: serde_json::Value) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_partial_json(
+            serde_json::json!({ "params": params }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": 30,
+            "id": 1
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    server
+}
+
+#[tokio::test]
+async fn test_reqwest_client_sends_without_params() {
+    let server = mock_add_server(serde_json::json!([])).await;
+    let client = reqwest::Client::new();
+    let resp: JsonRpcResponse<i32> = client
+        .send_v2_request(&server.uri(), "application/json", "ping")
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(30));
+}
+
+#[tokio::test]
+async fn test_reqwest_client_send_v2_request_typed() {
+    let server = mock_add_server(serde_json::json!([10, 20])).await;
+    let client = reqwest::Client::new();
+    let resp: JsonRpcResponse<i32> = client
+        .send_v2_request_typed(&(10, 20), &server.uri(), "application/json", "addArray")
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(30));
+}
+
+#[tokio::test]
+async fn test_reqwest_client_send_v2_request_typed_scalar() {
+    let server = mock_add_server(serde_json::json!([30])).await;
+    let client = reqwest::Client::new();
+    let resp: JsonRpcResponse<i32> = client
+        .send_v2_request_typed(&30, &server.uri(), "application/json", "echo")
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(30));
+}
+
+#[cfg(feature = "ndarray")]
+#[tokio::test]
+async fn test_ndarray_params() {
+    let server = mock_add_server(serde_json::json!([[1, 2], [3, 4]])).await;
+    let matrix = ndarray::array![[1, 2], [3, 4]];
+    let resp: JsonRpcResponse<i32> = matrix
+        .send_v2_request(&server.uri(), "application/json", "sumMatrix")
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(30));
+}
+
+#[cfg(feature = "caching")]
+#[tokio::test]
+async fn test_caching_client_reuses_responses() {
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_partial_json(
+            serde_json::json!({ "params": [10, 20] }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": 30,
+            "id": 1
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client =
+        a_rs_jsonrpc::CachingClient::new((10, 20)).with_ttl(std::time::Duration::from_secs(60));
+    for _ in 0..3 {
+        let resp: JsonRpcResponse<i32> = client
+            .send_v2_request(&server.uri(), "application/json", "addArray")
+            .await
+            .unwrap();
+        assert_eq!(resp.result, Some(30));
+    }
+
+    client.invalidate(&server.uri(), "addArray");
+    let resp: JsonRpcResponse<i32> = client
+        .send_v2_request(&server.uri(), "application/json", "addArray")
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(30));
+
+    // Another endpoint is not answered from the first one's cache.
+    let other = mock_add_server(serde_json::json!([10, 20])).await;
+    let resp: JsonRpcResponse<i32> = client
+        .send_v2_request(&other.uri(), "application/json", "addArray")
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(30));
+}
+
+#[tokio::test]
+async fn test_client_session_counts_calls_and_bytes() {
+    let server = mock_add_server(serde_json::json!([10, 20])).await;
+    let session = JsonRpcClientSession::new(SharedClient::new(
+        reqwest::Client::new(),
+        &server.uri(),
+        "application/json",
+    ));
+    let resp: JsonRpcResponse<i32> = session.call_v2("addArray", (10, 20)).await.unwrap();
+    assert_eq!(resp.result, Some(30));
+
+    let metrics = session.metrics_snapshot()["addArray"];
+    assert_eq!(metrics.calls, 1);
+    assert_eq!(metrics.errors, 0);
+    assert!(metrics.bytes_sent > 0);
+    assert!(metrics.bytes_received > 0);
+}
+
+#[tokio::test]
+async fn test_map_params_sent_as_object() {
+    let server = mock_add_server(serde_json::json!({ "lhs": 10, "rhs": 20 })).await;
+    let mut params = serde_json::Map::new();
+    params.insert("lhs".to_string(), serde_json::json!(10));
+    params.insert("rhs".to_string(), serde_json::json!(20));
+    let resp: JsonRpcResponse<i32> = params
+        .send_v2_request_obj(&server.uri(), "application/json", "addObj")
+        .await
+        .unwrap();
+    assert_eq!(resp.result, Some(30));
+}
+
+#[tokio::test]
+async fn test_reference_params_delegate_to_referenced_value() {
+    let server = mock_add_server(serde_json::json!({ "lhs": 10, "rhs": 20 })).await;
+    let mut params = serde_json::Map::new();
+    params.insert("lhs".to_string(), serde_json::json!(10));
+    params.insert("rhs".to_string(), serde_json::json!(20));
+    let params = &params;
+    let resp: JsonRpcResponse<i32> =
+        JsonRpcClient::send_v2_request_obj(&params, &server.uri(), "application/json", "addObj")
+            .await
+            .unwrap();
+    assert_eq!(resp.result, Some(30));
+}
+
+#[tokio::test]
+async fn test_client_pool_round_robin_spreads_calls() {
+    // Each mock server expects exactly one call.
+    let servers = [
+        mock_add_server(serde_json::json!([10, 20])).await,
+        mock_add_server(serde_json::json!([10, 20])).await,
+    ];
+    let pool = JsonRpcClientPool::new(
+        servers
+            .iter()
+            .map(|server| {
+                JsonRpcClientSession::new(SharedClient::new(
+                    reqwest::Client::new(),
+                    &server.uri(),
+                    "application/json",
+                ))
+            })
+            .collect(),
+        LoadBalancing::RoundRobin,
+    );
+    for _ in 0..2 {
+        let resp: JsonRpcResponse<i32> = pool.call_v2("addArray", (10, 20)).await.unwrap();
+        assert_eq!(resp.result, Some(30));
+    }
+    for session in pool.sessions() {
+        assert_eq!(session.metrics_snapshot()["addArray"].calls, 1);
+    }
+}
+
+#[tokio::test]
+async fn test_error_retryable_and_http_status() {
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+    let err: RpcError = reqwest::Client::new()
+        .post(server.uri())
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap_err()
+        .into();
+    assert_eq!(err.http_status_code(), Some(503));
+    assert!(!err.is_retryable());
+
+    // Nothing listens on port 1, so the connection is refused.
+    let err: RpcError = reqwest::Client::new()
+        .post("http://127.0.0.1:1/")
+        .send()
+        .await
+        .unwrap_err()
+        .into();
+    assert!(err.is_retryable());
+    assert_eq!(err.http_status_code(), None);
+
+    assert!(!RpcError::MethodNotFound.is_retryable());
+    assert!(RpcError::Timeout.is_retryable());
+}
+
+#[test]
+fn test_is_retryable_io_errors() {
+    use std::io::{Error, ErrorKind};
+
+    for kind in [
+        ErrorKind::ConnectionRefused,
+        ErrorKind::ConnectionReset,
+        ErrorKind::ConnectionAborted,
+        ErrorKind::TimedOut,
+        ErrorKind::Interrupted,
+        ErrorKind::UnexpectedEof,
+    ] {
+        let err = RpcError::from(Error::from(kind));
+        assert!(err.is_retryable(), "{:?}", kind);
+    }
+    for kind in [
+        ErrorKind::NotFound,
+        ErrorKind::PermissionDenied,
+        ErrorKind::InvalidData,
+        ErrorKind::Other,
+    ] {
+        let err = RpcError::from(Error::from(kind));
+        assert!(!err.is_retryable(), "{:?}", kind);
+    }
+}
+
+#[tokio::test]
+async fn test_health_check() {
+    let server = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .and(matchers::body_partial_json(
+            serde_json::json!({ "method": "rpc.health" }),
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": { "status": "ok" },
+            "id": 1
+        })))
+        .mount(&server)
+        .await;
+    assert!(SharedClient::health_check(&server.uri()).await.unwrap());
+
+    let degraded = MockServer::start().await;
+    Mock::given(matchers::method("POST"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": { "status": "degraded" },
+            "id": 1
+        })))
+        .mount(&degraded)
+        .await;
+    assert!(!SharedClient::health_check(&degraded.uri()).await.unwrap());
+
+    assert!(
+        SharedClient::health_check("http://127.0.0.1:1/")
+            .await
+            .is_err()
+    );
+}
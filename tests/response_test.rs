@@ -0,0 +1,251 @@
+use a_rs_jsonrpc::{
+    JsonRpcErrorKind, JsonRpcId, JsonRpcRawResponse, JsonRpcResponse, RpcError,
+    request::JsonRpcVersion, response::JsonRpcError,
+};
+
+fn error_response() -> JsonRpcResponse<String> {
+    JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2_0,
+        result: None,
+        error: Some(JsonRpcError {
+            code: -32601,
+            message: "method not found".to_string(),
+            data: Some(serde_json::json!({"method": "missing"})),
+        }),
+        id: JsonRpcId::Number(1),
+    }
+}
+
+#[test]
+fn test_response_equality() {
+    let lhs = JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2_0,
+        result: Some("pong".to_string()),
+        error: None,
+        id: JsonRpcId::Number(1),
+    };
+    let rhs = JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2_0,
+        result: Some("pong".to_string()),
+        error: None,
+        id: JsonRpcId::Number(1),
+    };
+    assert_eq!(lhs, rhs);
+    assert_eq!(error_response(), error_response());
+    assert_ne!(lhs, error_response());
+}
+
+#[test]
+fn test_response_clone_is_independent() {
+    let original = error_response();
+    let mut cloned = original.clone();
+    assert_eq!(original, cloned);
+    cloned.error.as_mut().unwrap().message = "changed".to_string();
+    assert_eq!(original.error.as_ref().unwrap().message, "method not found");
+    assert_ne!(original, cloned);
+}
+
+#[test]
+fn test_error_to_rpc_error_mapping() {
+    let err = |code| JsonRpcError {
+        code,
+        message: "boom".to_string(),
+        data: None,
+    };
+    assert!(matches!(
+        err(-32700).to_rpc_error(),
+        RpcError::ParseError(_)
+    ));
+    assert!(matches!(
+        err(-32600).to_rpc_error(),
+        RpcError::InvalidRequest(_)
+    ));
+    assert!(matches!(
+        err(-32601).to_rpc_error(),
+        RpcError::MethodNotFound
+    ));
+    assert!(matches!(
+        err(-32602).to_rpc_error(),
+        RpcError::InvalidParams(_)
+    ));
+    match RpcError::from(err(42)) {
+        RpcError::ApplicationError {
+            code,
+            message,
+            data,
+        } => {
+            assert_eq!(code, 42);
+            assert_eq!(message, "boom");
+            assert_eq!(data, None);
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn test_application_error_round_trip() {
+    let sent = JsonRpcError::from(RpcError::ApplicationError {
+        code: 42,
+        message: "insufficient funds".to_string(),
+        data: Some(serde_json::json!({ "balance": 10 })),
+    });
+    let received = RpcError::from(sent.clone());
+    assert!(matches!(
+        &received,
+        RpcError::ApplicationError { code: 42, message, data: Some(data) }
+            if message == "insufficient funds" && data["balance"] == 10
+    ));
+    assert_eq!(JsonRpcError::from(received), sent);
+}
+
+#[test]
+fn test_response_into_result() {
+    let ok = JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2_0,
+        result: Some("pong".to_string()),
+        error: None,
+        id: JsonRpcId::Number(1),
+    };
+    assert_eq!(ok.into_result().unwrap(), "pong");
+    assert!(matches!(
+        error_response().into_result(),
+        Err(RpcError::MethodNotFound)
+    ));
+}
+
+#[cfg(feature = "axum")]
+#[tokio::test]
+async fn test_response_into_axum_response() {
+    use axum::response::IntoResponse;
+    let resp = JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2_0,
+        result: Some("pong".to_string()),
+        error: None,
+        id: JsonRpcId::Number(1),
+    }
+    .into_response();
+    assert_eq!(resp.headers()["content-type"], "application/json");
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(value["result"], "pong");
+}
+
+#[test]
+fn test_rpc_error_into_jsonrpc_error_response() {
+    let body = RpcError::MethodNotFound
+        .into_jsonrpc_error_response(JsonRpcId::Number(7), JsonRpcVersion::V2_0);
+    let resp: JsonRpcResponse<()> = serde_json::from_str(&body).unwrap();
+    assert_eq!(resp.id, JsonRpcId::Number(7));
+    assert_eq!(resp.jsonrpc, JsonRpcVersion::V2_0);
+    assert_eq!(resp.error.unwrap().code, -32601);
+}
+
+#[test]
+fn test_application_error_passes_code_and_data_through() {
+    let err = RpcError::ApplicationError {
+        code: 1001,
+        message: "insufficient funds".to_string(),
+        data: Some(serde_json::json!({ "balance": 5 })),
+    };
+    assert_eq!(
+        JsonRpcError::from(err),
+        JsonRpcError {
+            code: 1001,
+            message: "insufficient funds".to_string(),
+            data: Some(serde_json::json!({ "balance": 5 })),
+        }
+    );
+}
+
+#[test]
+fn test_error_classification() {
+    let err = |code| JsonRpcError {
+        code,
+        message: "boom".to_string(),
+        data: None,
+    };
+    assert!(err(-32601).is_protocol_error());
+    assert!(!err(-32601).is_application_error());
+    assert!(err(-32001).is_server_error());
+    assert!(err(-32001).is_application_error());
+    assert!(err(42).is_application_error());
+
+    assert_eq!(err(-32700).error_kind(), JsonRpcErrorKind::ParseError);
+    assert_eq!(err(-32602).error_kind(), JsonRpcErrorKind::InvalidParams);
+    assert_eq!(err(-32603).error_kind(), JsonRpcErrorKind::InternalError);
+    assert_eq!(err(-32004).error_kind(), JsonRpcErrorKind::ServerError);
+    assert_eq!(err(42).error_kind(), JsonRpcErrorKind::ApplicationError);
+}
+
+#[test]
+fn test_response_page_splits_list_result() {
+    let resp = JsonRpcResponse {
+        jsonrpc: JsonRpcVersion::V2_0,
+        result: Some(vec![1, 2, 3]),
+        error: None,
+        id: JsonRpcId::Number(4),
+    };
+    let pages = resp.page(|items| items.into_iter().map(|n| n * 10).collect());
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[1].result, Some(20));
+    assert!(
+        pages
+            .iter()
+            .all(|p| p.id == JsonRpcId::Number(4) && p.jsonrpc == JsonRpcVersion::V2_0)
+    );
+
+    let pages = error_response().page(|s| s.chars().collect::<Vec<_>>());
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].result, None);
+    assert_eq!(pages[0].error.as_ref().unwrap().code, -32601);
+}
+
+#[test]
+fn test_static_error_constructors() {
+    const MISSING_NAME: RpcError = RpcError::invalid_params_static("name is required");
+    assert_eq!(
+        JsonRpcError::from(MISSING_NAME),
+        JsonRpcError {
+            code: -32602,
+            message: "Invalid parameters: name is required".to_string(),
+            data: None,
+        }
+    );
+    const UNAVAILABLE: RpcError = RpcError::custom_error_static("unavailable");
+    assert_eq!(JsonRpcError::from(UNAVAILABLE).code, -32003);
+}
+
+#[test]
+fn test_raw_response_converts_to_typed() {
+    let text = r#"{"jsonrpc":"2.0","result":{"sum":30},"id":1}"#;
+    let raw: JsonRpcRawResponse = serde_json::from_str(text).unwrap();
+    assert_eq!(raw.raw_result(), Some(&serde_json::json!({ "sum": 30 })));
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Sum {
+        sum: i32,
+    }
+    let resp = JsonRpcResponse::<Sum>::from_raw(raw.clone()).unwrap();
+    assert_eq!(resp.result, Some(Sum { sum: 30 }));
+    assert_eq!(resp.id, JsonRpcId::Number(1));
+
+    assert!(JsonRpcResponse::<String>::from_raw(raw).is_err());
+}
+
+#[test]
+fn test_into_items() {
+    let resp: JsonRpcResponse<Vec<i32>> =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","result":[1,2,3],"id":1}"#).unwrap();
+    let items: Vec<_> = resp.into_items().collect();
+    assert_eq!(items, vec![Ok(1), Ok(2), Ok(3)]);
+
+    let resp: JsonRpcResponse<Vec<i32>> = serde_json::from_str(
+        r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"method not found"},"id":2}"#,
+    )
+    .unwrap();
+    let items: Vec<_> = resp.into_items().collect();
+    assert_eq!(items.len(), 1);
+    assert!(matches!(&items[0], Err(err) if err.code == -32601));
+}
@@ -0,0 +1,51 @@
+use a_rs_jsonrpc::{
+    RpcError, dispatch_rpc_request, dispatch_rpc_request_with_selector, jsonrpc_service_fn_array,
+    register_additional_rpc_handler,
+    service::{RpcHandlerFn, RpcUserData},
+};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+
+#[jsonrpc_service_fn_array(method = "greet", version = "v2")]
+async fn greet() -> Result<String, RpcError> {
+    Ok("stable".to_string())
+}
+
+/// A canary version of `greet`.
+fn greet_canary(
+    _req: Bytes,
+    _user_data: RpcUserData,
+) -> BoxFuture<'static, Result<String, RpcError>> {
+    Box::pin(async { Ok(r#"{"jsonrpc":"2.0","result":"canary","id":1}"#.to_string()) })
+}
+
+fn pick_last(method: &str, handlers: &[RpcHandlerFn]) -> RpcHandlerFn {
+    assert_eq!(method, "greet");
+    assert_eq!(handlers.len(), 2);
+    handlers[handlers.len() - 1]
+}
+
+#[tokio::test]
+async fn test_dispatch_with_selector_routes_between_handlers() {
+    register_additional_rpc_handler("greet", greet_canary);
+    let body = br#"{"jsonrpc":"2.0","method":"greet","params":[],"id":1}"#;
+
+    let resp = dispatch_rpc_request_with_selector(body, pick_last)
+        .await
+        .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&resp).unwrap();
+    assert_eq!(resp["result"], "canary");
+
+    // Other dispatchers keep using the registered handler.
+    let resp = dispatch_rpc_request(body).await.unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&resp).unwrap();
+    assert_eq!(resp["result"], "stable");
+
+    let err = dispatch_rpc_request_with_selector(
+        br#"{"jsonrpc":"2.0","method":"missing","id":2}"#,
+        pick_last,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, RpcError::MethodNotFound));
+}
@@ -0,0 +1,499 @@
+use a_rs_jsonrpc::{
+    ParamMode, RPC_SERVICES, RpcContext, RpcError, ServiceConfig, describe_rpc_method,
+    dispatch_rpc_batch_request, dispatch_rpc_bytes, dispatch_rpc_json_str, dispatch_rpc_request,
+    dispatch_rpc_request_from_method, dispatch_rpc_request_with_context, hot_reload_rpc_methods,
+    init_rpc_service, jsonrpc_service_fn, jsonrpc_service_fn_array, jsonrpc_service_fn_both,
+    jsonrpc_service_fn_obj, list_rpc_methods, try_dispatch_rpc_request,
+};
+use std::sync::Arc;
+
+/// A handler returning a `serde_json::Value` that itself contains a `result` key.
+#[jsonrpc_service_fn_obj(method = "wrapValue", version = "v2")]
+async fn wrap_value(msg: String) -> Result<serde_json::Value, RpcError> {
+    Ok(serde_json::json!({ "result": msg }))
+}
+
+#[tokio::test]
+async fn test_obj_value_result_is_not_unwrapped() {
+    let body = br#"{"jsonrpc":"2.0","method":"wrapValue","params":{"msg":"hello"},"id":1}"#;
+    let text = dispatch_rpc_request(body).await.unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(
+        resp,
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "result": { "result": "hello" },
+            "id": 1
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_dispatch_from_method_injects_method_name() {
+    let body = br#"{"jsonrpc":"2.0","params":{"msg":"hello"},"id":2}"#;
+    let text = dispatch_rpc_request_from_method("wrapValue", body)
+        .await
+        .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(resp["result"], serde_json::json!({ "result": "hello" }));
+    assert_eq!(resp["id"], serde_json::json!(2));
+}
+
+#[tokio::test]
+async fn test_dispatch_from_method_unknown_method() {
+    let body = br#"{"jsonrpc":"2.0","params":{},"id":3}"#;
+    let result = dispatch_rpc_request_from_method("missing", body).await;
+    assert!(matches!(result, Err(RpcError::MethodNotFound)));
+}
+
+#[test]
+fn test_list_methods_is_sorted() {
+    let methods = list_rpc_methods();
+    assert!(methods.contains(&"wrapValue"));
+    assert!(methods.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[tokio::test]
+async fn test_builtin_list_and_describe_methods() {
+    init_rpc_service(ServiceConfig::default());
+    let body = br#"{"jsonrpc":"2.0","method":"rpc.listMethods","id":4}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], serde_json::json!(list_rpc_methods()));
+
+    let body = br#"{"jsonrpc":"2.0","method":"rpc.describe","params":["wrapValue"],"id":5}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"]["method"], "wrapValue");
+    assert_eq!(
+        resp["result"]["description"],
+        "A handler returning a `serde_json::Value` that itself contains a `result` key."
+    );
+    assert_eq!(resp["id"], 5);
+}
+
+/// Overridden by the `desc` key.
+#[jsonrpc_service_fn_array(method = "described", version = "v2", desc = "Adds two numbers")]
+async fn described(a: i32, b: i32) -> Result<i32, RpcError> {
+    Ok(a + b)
+}
+
+#[test]
+fn test_describe_method_desc_key() {
+    let info = describe_rpc_method("described").unwrap();
+    assert_eq!(info.method, "described");
+    assert_eq!(info.description, Some("Adds two numbers"));
+    assert!(describe_rpc_method("notRegistered").is_none());
+}
+
+#[tokio::test]
+async fn test_dispatch_batch() {
+    let body = br#"[
+        {"jsonrpc":"2.0","method":"wrapValue","params":{"msg":"a"},"id":10},
+        {"jsonrpc":"2.0","method":"wrapValue","params":{"msg":"b"}},
+        {"jsonrpc":"2.0","method":"missing","id":11}
+    ]"#;
+    let text = dispatch_rpc_batch_request(body).await.unwrap();
+    let resp: Vec<serde_json::Value> = serde_json::from_str(&text).unwrap();
+    assert_eq!(resp.len(), 2);
+    assert_eq!(resp[0]["result"], serde_json::json!({ "result": "a" }));
+    assert_eq!(resp[0]["id"], 10);
+    assert_eq!(resp[1]["error"]["code"], -32601);
+    assert_eq!(resp[1]["id"], 11);
+}
+
+#[tokio::test]
+async fn test_dispatch_batch_rejects_empty_array() {
+    let result = dispatch_rpc_batch_request(b"[]").await;
+    assert!(matches!(result, Err(RpcError::InvalidParams(_))));
+}
+
+/// Selected as array mode: the tuple receives the whole positional `params` array.
+#[jsonrpc_service_fn(method = "sumPair", version = "v2")]
+async fn sum_pair((a, b): (i64, i64)) -> Result<i64, RpcError> {
+    Ok(a + b)
+}
+
+/// Selected as object mode: every parameter is plainly named.
+#[jsonrpc_service_fn(method = "greet", version = "v2")]
+async fn greet(first_name: String) -> Result<String, RpcError> {
+    Ok(format!("hello {}", first_name))
+}
+
+#[tokio::test]
+async fn test_unified_service_fn_selects_mode() {
+    let body = br#"{"jsonrpc":"2.0","method":"sumPair","params":[1,2],"id":20}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], 3);
+
+    let body = br#"{"jsonrpc":"2.0","method":"greet","params":{"firstName":"rust"},"id":21}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], "hello rust");
+}
+
+/// Receives the request context ahead of the positional parameters.
+#[jsonrpc_service_fn_array(method = "whoami", version = "v2", has_context = true)]
+async fn whoami(ctx: &RpcContext, greeting: String) -> Result<String, RpcError> {
+    let name = ctx.user_data::<String>().cloned().unwrap_or_default();
+    Ok(format!("{} {} via {}", greeting, name, ctx.method))
+}
+
+#[tokio::test]
+async fn test_dispatch_with_context() {
+    let body = br#"{"jsonrpc":"2.0","method":"whoami","params":["hi"],"id":30}"#;
+    let text = dispatch_rpc_request_with_context(body, Arc::new("alice".to_string()))
+        .await
+        .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(resp["result"], "hi alice via whoami");
+
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], "hi  via whoami");
+}
+
+/// A user item named like the generated registration static of `wrap_value`.
+#[allow(dead_code)]
+static REG_WRAP_VALUE: () = ();
+
+#[tokio::test]
+async fn test_dispatch_json_str() {
+    let text = dispatch_rpc_json_str(
+        r#"{"jsonrpc":"2.0","method":"wrapValue","params":{"msg":"hi"},"id":40}"#,
+    )
+    .await
+    .unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(resp["id"], 40);
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct Paging {
+    offset: u32,
+    limit: u32,
+}
+
+/// Reads `offset` and `limit` from the top level of `params`.
+#[jsonrpc_service_fn_obj(method = "searchPaged", version = "v2")]
+async fn search_paged(
+    query: String,
+    #[jsonrpc(flatten)] paging: Paging,
+) -> Result<String, RpcError> {
+    Ok(format!("{}:{}:{}", query, paging.offset, paging.limit))
+}
+
+#[tokio::test]
+async fn test_dispatch_bytes() {
+    let body = a_rs_jsonrpc::bytes::Bytes::from_static(
+        br#"{"jsonrpc":"2.0","method":"greet","params":{"firstName":"Ann"},"id":50}"#,
+    );
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_bytes(body).await.unwrap()).unwrap();
+    assert_eq!(resp["id"], 50);
+    assert_eq!(resp["result"], "hello Ann");
+}
+
+#[jsonrpc_service_fn_obj(method = "sayRepeated", version = "v2", rename = "snake_case")]
+async fn say_repeated(
+    #[rpc_param(rename = "msg")] message: String,
+    repeat_count: usize,
+) -> Result<String, RpcError> {
+    Ok(message.repeat(repeat_count))
+}
+
+#[tokio::test]
+async fn test_obj_rename_params() {
+    let body =
+        br#"{"jsonrpc":"2.0","method":"sayRepeated","params":{"msg":"ab","repeat_count":2},"id":52}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], "abab");
+
+    let body = br#"{"jsonrpc":"2.0","method":"sayRepeated","params":{"message":"ab","repeatCount":2},"id":53}"#;
+    assert!(dispatch_rpc_request(body).await.is_err());
+}
+
+#[tokio::test]
+async fn test_obj_flatten_param() {
+    let body = br#"{"jsonrpc":"2.0","method":"searchPaged","params":{"query":"rust","offset":5,"limit":10},"id":41}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], "rust:5:10");
+}
+
+#[tokio::test]
+async fn test_hot_reload_methods_keeps_routes() {
+    let before = list_rpc_methods();
+    hot_reload_rpc_methods(RPC_SERVICES.iter());
+    assert_eq!(list_rpc_methods(), before);
+    let body = br#"{"jsonrpc":"2.0","method":"described","params":[1,2],"id":42}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], 3);
+}
+
+#[tokio::test]
+async fn test_param_mode_rejects_wrong_params_type() {
+    assert_eq!(
+        describe_rpc_method("described").unwrap().param_mode,
+        ParamMode::Array
+    );
+    assert_eq!(
+        describe_rpc_method("wrapValue").unwrap().param_mode,
+        ParamMode::Object
+    );
+
+    let body = br#"{"jsonrpc":"2.0","method":"described","params":{"a":1,"b":2},"id":43}"#;
+    let result = dispatch_rpc_request(body).await;
+    assert!(matches!(result, Err(RpcError::InvalidParams(_))));
+
+    let body = br#"{"jsonrpc":"2.0","method":"wrapValue","params":["hi"],"id":44}"#;
+    let result = dispatch_rpc_request(body).await;
+    assert!(matches!(result, Err(RpcError::InvalidParams(_))));
+}
+
+#[tokio::test]
+async fn test_malformed_body_parse_error_vs_invalid_request() {
+    let result = dispatch_rpc_request(br#"{"jsonrpc":"2.0","method":"#).await;
+    assert!(matches!(result, Err(RpcError::ParseError(_))));
+
+    let result = dispatch_rpc_request(br#"{"jsonrpc":"2.0","method":42,"id":1}"#).await;
+    assert!(matches!(result, Err(RpcError::InvalidRequest(_))));
+
+    let result = dispatch_rpc_request(b"[1, 2]").await;
+    assert!(matches!(result, Err(RpcError::InvalidRequest(_))));
+}
+
+#[jsonrpc_service_fn_both(method = "subtractBoth", version = "v2")]
+async fn subtract_both(minuend: i32, subtrahend: i32) -> Result<i32, RpcError> {
+    Ok(minuend - subtrahend)
+}
+
+#[tokio::test]
+async fn test_service_fn_both_accepts_either_params_form() {
+    assert_eq!(
+        describe_rpc_method("subtractBoth").unwrap().param_mode,
+        ParamMode::Both
+    );
+
+    let body = br#"{"jsonrpc":"2.0","method":"subtractBoth","params":[5,3],"id":60}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], 2);
+
+    let body = br#"{"jsonrpc":"2.0","method":"subtractBoth","params":{"subtrahend":3,"minuend":5},"id":61}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], 2);
+}
+
+#[tokio::test]
+async fn test_try_dispatch_unknown_method_returns_none() {
+    let body = br#"{"jsonrpc":"2.0","method":"missing","params":[],"id":45}"#;
+    assert!(try_dispatch_rpc_request(body).is_none());
+
+    let body = br#"{"jsonrpc":"2.0","method":"described","params":[2,3],"id":46}"#;
+    let text = try_dispatch_rpc_request(body).unwrap().await.unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(resp["result"], 5);
+}
+
+#[cfg(feature = "path-errors")]
+#[tokio::test]
+async fn test_deserialize_error_reports_path() {
+    let body = br#"{"jsonrpc":"2.0","method":"described","params":[1,"two"],"id":47}"#;
+    match dispatch_rpc_request(body).await {
+        Err(RpcError::SerdeError { path, .. }) => assert_eq!(path.as_deref(), Some("params[1]")),
+        other => panic!("unexpected result: {other:?}"),
+    }
+}
+
+#[cfg(feature = "schema")]
+#[jsonrpc_service_fn_obj(method = "movePoint", version = "v2", schema = true)]
+async fn move_point(point_x: i32, point_y: i32) -> Result<i32, RpcError> {
+    Ok(point_x + point_y)
+}
+
+#[cfg(feature = "schema")]
+#[tokio::test]
+async fn test_get_schema() {
+    let schema = a_rs_jsonrpc::get_rpc_schema("movePoint").unwrap();
+    let schema = serde_json::to_value(schema).unwrap();
+    assert_eq!(schema["properties"]["pointX"]["type"], "integer");
+    assert!(a_rs_jsonrpc::get_rpc_schema("described").is_none());
+
+    init_rpc_service(ServiceConfig::default());
+    let body =
+        br#"{"jsonrpc":"2.0","method":"rpc.getSchema","params":{"method":"movePoint"},"id":48}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], schema);
+
+    let body = br#"{"jsonrpc":"2.0","method":"rpc.getSchema","params":["described"],"id":49}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert!(resp["result"].is_null());
+
+    let document = a_rs_jsonrpc::generate_openrpc_document();
+    let method = document["methods"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|method| method["name"] == "movePoint")
+        .unwrap();
+    assert_eq!(method["params"][0]["name"], "pointX");
+    assert_eq!(method["params"][0]["required"], true);
+}
+
+#[tokio::test]
+async fn test_openrpc_document_lists_methods() {
+    let document = a_rs_jsonrpc::generate_openrpc_document();
+    assert_eq!(document["openrpc"], "1.2.6");
+    let methods = document["methods"].as_array().unwrap();
+    let described = methods
+        .iter()
+        .find(|method| method["name"] == "described")
+        .unwrap();
+    assert_eq!(described["description"], "Adds two numbers");
+    assert_eq!(described["paramStructure"], "by-position");
+
+    init_rpc_service(ServiceConfig::default());
+    let body = br#"{"jsonrpc":"2.0","method":"rpc.openrpc","id":51}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"]["openrpc"], "1.2.6");
+}
+
+#[tokio::test]
+async fn test_generated_test_helpers_bypass_envelope() {
+    assert_eq!(test_described((1, 2)).await.unwrap(), 3);
+    assert_eq!(test_sum_pair((4, 5)).await.unwrap(), 9);
+}
+
+#[tokio::test]
+async fn test_generated_request_builder() {
+    let req = DescribedArrayRequestBuilder::new()
+        .param_a(10)
+        .param_b(20)
+        .build_v2(60);
+    assert_eq!(req.method, "described");
+    assert_eq!(
+        req.params.clone().into_option(),
+        Some(vec![10.into(), 20.into()])
+    );
+    let body = serde_json::to_vec(&req).unwrap();
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(&body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], 30);
+    assert_eq!(resp["id"], 60);
+
+    let req = DescribedArrayRequestBuilder::new().param_b(20).build_v2(61);
+    assert_eq!(
+        req.params.into_option(),
+        Some(vec![serde_json::Value::Null, 20.into()])
+    );
+}
+
+#[jsonrpc_service_fn_array(method = "keepSecond", version = "v2")]
+async fn keep_second(_a: i32, a: i32) -> Result<i32, RpcError> {
+    Ok(a)
+}
+
+#[tokio::test]
+async fn test_request_builder_keeps_underscored_names() {
+    let req = KeepSecondArrayRequestBuilder::new()
+        .param__a(1)
+        .param_a(2)
+        .build_v2(62);
+    assert_eq!(req.params.into_option(), Some(vec![1.into(), 2.into()]));
+}
+
+#[jsonrpc_service_fn_array(method = "slowPing", version = "v2")]
+async fn slow_ping() -> Result<String, RpcError> {
+    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    Ok("pong".to_string())
+}
+
+#[tokio::test]
+async fn test_dispatch_with_timeout() {
+    let body = br#"{"jsonrpc":"2.0","method":"slowPing","params":[],"id":54}"#;
+    let result =
+        a_rs_jsonrpc::dispatch_rpc_request_with_timeout(body, std::time::Duration::from_millis(20))
+            .await;
+    assert!(matches!(result, Err(RpcError::Timeout)));
+
+    let body = br#"{"jsonrpc":"2.0","method":"described","params":[1,2],"id":55}"#;
+    let text =
+        a_rs_jsonrpc::dispatch_rpc_request_with_timeout(body, std::time::Duration::from_secs(5))
+            .await
+            .unwrap();
+    assert!(text.contains("\"result\":3"));
+}
+
+#[tokio::test]
+async fn test_dispatch_raw_and_to_writer() {
+    let body = br#"{"jsonrpc":"2.0","method":"described","params":[1,2],"id":56}"#;
+    let raw = a_rs_jsonrpc::dispatch_rpc_request_raw(body).await.unwrap();
+    let resp: serde_json::Value = serde_json::from_slice(&raw).unwrap();
+    assert_eq!(resp["result"], 3);
+
+    let mut out = Vec::new();
+    let written = a_rs_jsonrpc::dispatch_rpc_request_to_writer(body, &mut out)
+        .await
+        .unwrap();
+    assert_eq!(written, out.len());
+    assert_eq!(out, raw);
+}
+
+/// An object-mode handler without parameters, like `todoList` in the `unit` example.
+#[jsonrpc_service_fn_obj(method = "listTodos", version = "v2")]
+async fn list_todos() -> Result<Vec<String>, RpcError> {
+    Ok(vec!["learn rust".to_string()])
+}
+
+#[tokio::test]
+async fn test_obj_handler_without_params() {
+    for params in [r#","params":{}"#, r#","params":null"#, ""] {
+        let body = format!(
+            r#"{{"jsonrpc":"2.0","method":"listTodos"{},"id":57}}"#,
+            params
+        );
+        let resp: serde_json::Value =
+            serde_json::from_str(&dispatch_rpc_request(body.as_bytes()).await.unwrap()).unwrap();
+        assert_eq!(
+            resp["result"],
+            serde_json::json!(["learn rust"]),
+            "{}",
+            body
+        );
+        assert_eq!(resp["id"], 57);
+    }
+}
+
+#[jsonrpc_service_fn_array(method = "instrumentedAdd", version = "v2", has_context = true)]
+#[tracing::instrument(skip(ctx), fields(method = %ctx.method))]
+async fn instrumented_add(ctx: &RpcContext, a: i32, b: i32) -> Result<i32, RpcError> {
+    Ok(a + b)
+}
+
+#[tracing::instrument(level = "debug")]
+#[jsonrpc_service_fn_obj(method = "instrumentedSub", version = "v2")]
+async fn instrumented_sub(lhs: i32, rhs: i32) -> Result<i32, RpcError> {
+    Ok(lhs - rhs)
+}
+
+#[tokio::test]
+async fn test_tracing_instrument_passthrough() {
+    let body = br#"{"jsonrpc":"2.0","method":"instrumentedAdd","params":[1,2],"id":58}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], 3);
+
+    let body =
+        br#"{"jsonrpc":"2.0","method":"instrumentedSub","params":{"lhs":5,"rhs":2},"id":59}"#;
+    let resp: serde_json::Value =
+        serde_json::from_str(&dispatch_rpc_request(body).await.unwrap()).unwrap();
+    assert_eq!(resp["result"], 3);
+}
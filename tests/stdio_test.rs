@@ -0,0 +1,32 @@
+#![cfg(feature = "stdio")]
+
+use a_rs_jsonrpc::{
+    JsonRpcResponse,
+    stdio::{FramingMode, StdioClient},
+};
+use tokio::process::Command;
+
+/// Spawns a shell that reads one framed request and answers with a canned response.
+fn canned_server(script: &str, framing: FramingMode) -> StdioClient {
+    StdioClient::spawn(Command::new("sh").arg("-c").arg(script), framing).unwrap()
+}
+
+#[tokio::test]
+async fn test_stdio_client_newline_framing() {
+    let client = canned_server(
+        r#"read line; echo '{"jsonrpc":"2.0","result":30,"id":1}'"#,
+        FramingMode::Newline,
+    );
+    let resp: JsonRpcResponse<i32> = client.call_v2("addArray", (10, 20)).await.unwrap();
+    assert_eq!(resp.result, Some(30));
+}
+
+#[tokio::test]
+async fn test_stdio_client_content_length_framing() {
+    let client = canned_server(
+        r#"read header; read blank; printf 'Content-Length: 36\r\n\r\n{"jsonrpc":"2.0","result":30,"id":1}'"#,
+        FramingMode::ContentLength,
+    );
+    let resp: JsonRpcResponse<i32> = client.call_v2("addArray", (10, 20)).await.unwrap();
+    assert_eq!(resp.result, Some(30));
+}
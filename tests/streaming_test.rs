@@ -0,0 +1,82 @@
+use a_rs_jsonrpc::{
+    RpcError, SharedClient, dispatch_rpc_request, dispatch_rpc_request_streaming,
+    jsonrpc_service_fn_array,
+};
+use futures::{Stream, StreamExt};
+use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+#[tokio::test]
+async fn test_send_v2_request_streaming_reads_ndjson_lines() {
+    let server = MockServer::start().await;
+    let body = concat!(
+        r#"{"jsonrpc":"2.0","result":1,"id":1}"#,
+        "\n",
+        "\n",
+        r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"gone"},"id":1}"#,
+        "\n",
+        r#"{"jsonrpc":"2.0","result":3,"id":1}"#,
+    );
+    Mock::given(matchers::method("POST"))
+        .and(matchers::header("Accept", "application/x-ndjson"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(body, "application/x-ndjson"))
+        .mount(&server)
+        .await;
+
+    let client = SharedClient::new(reqwest::Client::new(), &server.uri(), "application/json");
+    let items: Vec<Result<i32, RpcError>> = client
+        .send_v2_request_streaming("subscribe", ["ticks"])
+        .collect()
+        .await;
+
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].as_ref().unwrap(), &1);
+    assert!(matches!(items[1], Err(RpcError::MethodNotFound)));
+    assert_eq!(items[2].as_ref().unwrap(), &3);
+}
+
+#[jsonrpc_service_fn_array(method = "countUp", version = "v2")]
+async fn count_up(n: u32) -> Result<impl Stream<Item = u32>, RpcError> {
+    Ok(futures::stream::iter(1..=n))
+}
+
+#[jsonrpc_service_fn_array(method = "tailLines", version = "v2", streaming = true)]
+async fn tail_lines(n: usize) -> Result<impl Stream<Item = String>, RpcError> {
+    Ok(futures::stream::iter((0..n).map(|i| format!("line {}", i))))
+}
+
+#[tokio::test]
+async fn test_stream_result_is_collected() {
+    let body = br#"{"jsonrpc":"2.0","method":"countUp","params":[3],"id":1}"#;
+    let text = dispatch_rpc_request(body).await.unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(resp["result"], serde_json::json!([1, 2, 3]));
+}
+
+#[tokio::test]
+async fn test_dispatch_streaming_sends_one_response_per_item() {
+    let body = br#"{"jsonrpc":"2.0","method":"tailLines","params":[2],"id":7}"#;
+    let lines: Vec<String> = dispatch_rpc_request_streaming(body)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(
+        lines,
+        [
+            "{\"jsonrpc\":\"2.0\",\"result\":\"line 0\",\"id\":7}\n",
+            "{\"jsonrpc\":\"2.0\",\"result\":\"line 1\",\"id\":7}\n",
+        ]
+    );
+
+    let text = dispatch_rpc_request(body).await.unwrap();
+    let resp: serde_json::Value = serde_json::from_str(&text).unwrap();
+    assert_eq!(resp["result"], serde_json::json!(["line 0", "line 1"]));
+
+    let body = br#"{"jsonrpc":"2.0","method":"countUp","params":[2],"id":8}"#;
+    let lines: Vec<String> = dispatch_rpc_request_streaming(body)
+        .await
+        .unwrap()
+        .collect()
+        .await;
+    assert_eq!(lines, ["{\"jsonrpc\":\"2.0\",\"result\":[1,2],\"id\":8}\n"]);
+}
@@ -0,0 +1,83 @@
+use a_rs_jsonrpc::{
+    JsonRpcClient, JsonRpcResponse, RpcContext, RpcError, jsonrpc_service_fn_array,
+    testing::{InProcessTransport, MockTransport},
+};
+
+#[jsonrpc_service_fn_array(method = "inProcessAdd", version = "v2")]
+async fn in_process_add(a: i32, b: i32) -> Result<i32, RpcError> {
+    Ok(a + b)
+}
+
+#[jsonrpc_service_fn_array(method = "inProcessPing", version = "v2")]
+async fn in_process_ping() -> Result<String, RpcError> {
+    Ok("pong".to_string())
+}
+
+#[jsonrpc_service_fn_array(method = "inProcessTenant", version = "v2", has_context = true)]
+async fn in_process_tenant(ctx: &RpcContext) -> Result<String, RpcError> {
+    Ok(ctx.user_data::<String>().cloned().unwrap_or_default())
+}
+
+/// Exercises client-side code against the registered handlers, without a server.
+#[tokio::test]
+async fn test_in_process_transport_calls_handlers() {
+    let transport = InProcessTransport::new();
+
+    let resp: JsonRpcResponse<i32> = transport.call_v2("inProcessAdd", (10, 20)).await.unwrap();
+    assert_eq!(resp.result, Some(30));
+
+    let resp: JsonRpcResponse<String> = transport
+        .send_v2_request("", "", "inProcessPing")
+        .await
+        .unwrap();
+    assert_eq!(resp.result.as_deref(), Some("pong"));
+}
+
+#[tokio::test]
+async fn test_in_process_transport_with_state() {
+    let transport = InProcessTransport::with_state("acme".to_string());
+    let resp: JsonRpcResponse<String> = transport
+        .call_v2("inProcessTenant", serde_json::json!([]))
+        .await
+        .unwrap();
+    assert_eq!(resp.result.as_deref(), Some("acme"));
+}
+
+#[tokio::test]
+async fn test_in_process_transport_unknown_method() {
+    let transport = InProcessTransport::new();
+    let resp: JsonRpcResponse<i32> = transport.call_v2("inProcessMissing", (1, 2)).await.unwrap();
+    assert!(matches!(resp.into_result(), Err(RpcError::MethodNotFound)));
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn test_mock_transport_expectations() {
+    let mock = MockTransport::new();
+    mock.expect(
+        "addArray",
+        serde_json::json!([10, 20]),
+        serde_json::json!(30),
+    );
+    mock.expect("ping", serde_json::json!([]), serde_json::json!("pong"));
+
+    let resp: JsonRpcResponse<i32> = mock.call_v2("addArray", (10, 20)).await.unwrap();
+    assert_eq!(resp.result, Some(30));
+    let resp: JsonRpcResponse<String> = mock.send_v2_request("", "", "ping").await.unwrap();
+    assert_eq!(resp.result.as_deref(), Some("pong"));
+    mock.assert_all_called();
+
+    let err = mock
+        .call_v2::<_, i32>("addArray", (10, 20))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RpcError::CustomError(msg) if msg.starts_with("unexpected call")));
+}
+
+#[test]
+#[should_panic(expected = "expected calls not made")]
+fn test_mock_transport_assert_all_called_panics() {
+    let mock = MockTransport::new();
+    mock.expect("addArray", serde_json::json!([1, 2]), serde_json::json!(3));
+    mock.assert_all_called();
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "tower")]
+
+use a_rs_jsonrpc::{JsonRpcDispatcher, RpcError, jsonrpc_service_fn_array};
+use bytes::Bytes;
+use tower::{Layer, Service};
+
+#[jsonrpc_service_fn_array(method = "towerAdd", version = "v2")]
+async fn tower_add(a: i32, b: i32) -> Result<i32, RpcError> {
+    Ok(a + b)
+}
+
+fn post(body: &'static str) -> http::Request<Bytes> {
+    http::Request::post("/")
+        .body(Bytes::from_static(body.as_bytes()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_dispatcher_service() {
+    let mut service = JsonRpcDispatcher::new();
+
+    let resp = service
+        .call(post(
+            r#"{"jsonrpc":"2.0","method":"towerAdd","params":[1,2],"id":1}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), http::StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "application/json");
+    let body: serde_json::Value = serde_json::from_str(resp.body()).unwrap();
+    assert_eq!(body["result"], 3);
+
+    let resp = service
+        .call(post(
+            r#"{"jsonrpc":"2.0","method":"towerMissing","params":[],"id":2}"#,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    let body: serde_json::Value = serde_json::from_str(resp.body()).unwrap();
+    assert_eq!(body["error"]["code"], -32601);
+    assert_eq!(body["id"], 2);
+
+    let resp = service.call(post("not json")).await.unwrap();
+    assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_dispatcher_layer_passes_through_non_post() {
+    let inner = tower::service_fn(|_req: http::Request<Bytes>| async {
+        Ok::<_, std::convert::Infallible>(http::Response::new("ok".to_string()))
+    });
+    let mut service = JsonRpcDispatcher::new().layer(inner);
+
+    let resp = service
+        .call(http::Request::get("/health").body(Bytes::new()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.body(), "ok");
+
+    let resp = service
+        .call(post(
+            r#"{"jsonrpc":"2.0","method":"towerAdd","params":[2,3],"id":3}"#,
+        ))
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_str(resp.body()).unwrap();
+    assert_eq!(body["result"], 5);
+}
@@ -0,0 +1,135 @@
+use a_rs_jsonrpc::{
+    RpcError, dispatch_rpc_request, dispatch_rpc_request_from_method,
+    dispatch_rpc_request_streaming, jsonrpc_service_fn_array, try_dispatch_rpc_request,
+};
+use futures::{Stream, StreamExt};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tracing::field::{Field, Visit};
+use tracing_subscriber::{Layer, layer::Context, prelude::*, registry::LookupSpan};
+
+#[jsonrpc_service_fn_array(method = "tracedDiv", version = "v2")]
+async fn traced_div(a: i32, b: i32) -> Result<i32, RpcError> {
+    a.checked_div(b)
+        .ok_or_else(|| RpcError::InvalidParams("division by zero".into()))
+}
+
+#[jsonrpc_service_fn_array(method = "tracedCount", version = "v2", streaming = true)]
+async fn traced_count(n: u32) -> Result<impl Stream<Item = u32>, RpcError> {
+    Ok(futures::stream::iter(0..n))
+}
+
+type Fields = Arc<Mutex<Vec<HashMap<String, String>>>>;
+
+/// Collects the fields of every `rpc_dispatch` span, once the span is closed.
+struct DispatchSpans(Fields);
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+impl<S: tracing::Subscriber + for<'a> LookupSpan<'a>> Layer<S> for DispatchSpans {
+    fn on_new_span(
+        &self,
+        attrs: &tracing::span::Attributes<'_>,
+        id: &tracing::span::Id,
+        ctx: Context<'_, S>,
+    ) {
+        if attrs.metadata().name() != "rpc_dispatch" {
+            return;
+        }
+        let mut fields = HashMap::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        ctx.span(id).unwrap().extensions_mut().insert(fields);
+    }
+
+    fn on_record(
+        &self,
+        id: &tracing::span::Id,
+        values: &tracing::span::Record<'_>,
+        ctx: Context<'_, S>,
+    ) {
+        let span = ctx.span(id).unwrap();
+        if let Some(fields) = span.extensions_mut().get_mut::<HashMap<String, String>>() {
+            values.record(&mut FieldVisitor(fields));
+        }
+    }
+
+    fn on_close(&self, id: tracing::span::Id, ctx: Context<'_, S>) {
+        let span = ctx.span(&id).unwrap();
+        if let Some(fields) = span.extensions_mut().remove::<HashMap<String, String>>() {
+            self.0.lock().unwrap().push(fields);
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_dispatch_records_span_fields() {
+    let spans = Fields::default();
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(DispatchSpans(spans.clone())),
+    );
+
+    dispatch_rpc_request(br#"{"jsonrpc":"2.0","method":"tracedDiv","params":[6,3],"id":7}"#)
+        .await
+        .unwrap();
+    dispatch_rpc_request(br#"{"jsonrpc":"2.0","method":"tracedDiv","params":[6,0],"id":"div-0"}"#)
+        .await
+        .unwrap_err();
+
+    let spans = spans.lock().unwrap();
+    assert_eq!(spans.len(), 2);
+    assert_eq!(spans[0]["method"], "tracedDiv");
+    assert_eq!(spans[0]["id"], "7");
+    assert!(spans[0].contains_key("latency_us"));
+    assert!(!spans[0].contains_key("error"));
+    assert_eq!(spans[1]["id"], "div-0");
+    assert!(spans[1]["error"].contains("division by zero"));
+}
+
+#[tokio::test]
+async fn test_other_entry_points_record_span_fields() {
+    let spans = Fields::default();
+    let _guard = tracing::subscriber::set_default(
+        tracing_subscriber::registry().with(DispatchSpans(spans.clone())),
+    );
+
+    try_dispatch_rpc_request(br#"{"jsonrpc":"2.0","method":"tracedDiv","params":[6,3],"id":1}"#)
+        .unwrap()
+        .await
+        .unwrap();
+    dispatch_rpc_request_from_method("tracedDiv", br#"{"jsonrpc":"2.0","params":[6,0],"id":2}"#)
+        .await
+        .unwrap_err();
+    dispatch_rpc_request_streaming(
+        br#"{"jsonrpc":"2.0","method":"tracedCount","params":[2],"id":3}"#,
+    )
+    .await
+    .unwrap()
+    .collect::<Vec<_>>()
+    .await;
+
+    let spans = spans.lock().unwrap();
+    assert_eq!(spans.len(), 3);
+    for (span, (method, id)) in
+        spans
+            .iter()
+            .zip([("tracedDiv", "1"), ("tracedDiv", "2"), ("tracedCount", "3")])
+    {
+        assert_eq!(span["method"], method);
+        assert_eq!(span["id"], id);
+        assert!(span.contains_key("latency_us"));
+    }
+    assert!(spans[1]["error"].contains("division by zero"));
+}